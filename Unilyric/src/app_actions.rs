@@ -137,6 +137,9 @@ pub enum ProcessorType {
     MetadataStripper,
     SyllableSmoother,
     AgentRecognizer,
+    BackgroundVocalExtractor,
+    SongStructureDetector,
+    LineReflow,
 }
 
 #[derive(Debug, Clone)]
@@ -6,9 +6,10 @@ use lyrics_helper_core::{
     ConversionInput, ConversionOptions, InputFile, LyricFormat, MatchType, RawLyrics, SearchResult,
 };
 use lyrics_helper_rs::SearchMode;
+use lyrics_helper_rs::search::normalizer::{NormalizedQuery, QueryNormalizer};
 use smtc_suite::NowPlayingInfo;
 
-use lyrics_helper_core::model::track::{LyricsAndMetadata, Track};
+use lyrics_helper_core::model::track::LyricsAndMetadata;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio_util::sync::CancellationToken;
@@ -58,6 +59,24 @@ fn is_track_match(
     false
 }
 
+/// 用 `QueryNormalizer` 整理 SMTC 或用户输入的元数据，拆出客串艺术家和版本信息。
+///
+/// 识别出的版本类型会随查询一起传给搜索，避免把录音室版匹配到现场版。
+pub(crate) fn normalize_now_playing(
+    title: &str,
+    artists: &[String],
+    album: Option<&str>,
+    duration: Option<u64>,
+) -> NormalizedQuery {
+    let artist = artists.join("/");
+    QueryNormalizer::new().normalize(
+        title,
+        Some(artist.as_str()).filter(|a| !a.is_empty()),
+        album,
+        duration,
+    )
+}
+
 pub fn initial_auto_fetch_and_send_lyrics(app: &mut UniLyricApp, track_info: NowPlayingInfo) {
     info!(
         "[initial_auto_fetch_and_send_lyrics] 封面哈希: {:?}",
@@ -235,17 +254,14 @@ pub fn initial_auto_fetch_and_send_lyrics(app: &mut UniLyricApp, track_info: Now
     *app.fetcher.amll_db_status.lock().unwrap() = AutoSearchStatus::Searching;
 
     runtime.spawn(async move {
-        let artists_slices: Vec<&str> = smtc_artists.iter().map(|s| s.as_str()).collect();
-        let track_to_search = Track {
-            title: Some(&smtc_title),
-            artists: if smtc_artists.is_empty() {
-                None
-            } else {
-                Some(&artists_slices)
-            },
-            album: smtc_album.as_deref(),
-            duration: smtc_duration,
-        };
+        let query = normalize_now_playing(
+            &smtc_title,
+            &smtc_artists,
+            smtc_album.as_deref(),
+            smtc_duration,
+        );
+        let artists_slices = query.artist_names();
+        let track_to_search = query.to_track(&artists_slices);
 
         let mut final_lyrics: Option<LyricsAndMetadata> = None;
         let mut final_candidates: Vec<SearchResult> = Vec::new();
@@ -478,17 +494,14 @@ pub fn trigger_manual_refetch_for_source(
     app.fetcher.current_fetch_cancellation_token = Some(cancellation_token.clone());
 
     runtime.spawn(async move {
-        let artists_slices: Vec<&str> = smtc_artists.iter().map(|s| s.as_str()).collect();
-        let track_to_search = Track {
-            title: Some(&smtc_title),
-            artists: if artists_slices.is_empty() {
-                None
-            } else {
-                Some(&artists_slices)
-            },
-            album: track_info.album_title.as_deref(),
-            duration: track_info.duration_ms,
-        };
+        let query = normalize_now_playing(
+            &smtc_title,
+            &smtc_artists,
+            track_info.album_title.as_deref(),
+            track_info.duration_ms,
+        );
+        let artists_slices = query.artist_names();
+        let track_to_search = query.to_track(&artists_slices);
 
         let provider_enum = match source_to_refetch {
             AutoSearchSource::QqMusic => lyrics_helper_rs::ProviderName::QQMusic,
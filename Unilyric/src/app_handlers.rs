@@ -17,7 +17,7 @@ use crate::error::{AppError, AppResult};
 use crate::types::{AutoSearchStatus, LrcContentType, ProviderState};
 use lyrics_helper_core::{
     ChineseConversionConfig, ChineseConversionMode, ChineseConversionOptions, ContentType,
-    ConversionInput, ConversionOptions, InputFile, LyricFormat, Track,
+    ConversionInput, ConversionOptions, InputFile, LyricFormat, SongStructureOptions,
 };
 use smtc_suite::{MediaCommand, TextConversionMode};
 use tracing::warn;
@@ -475,20 +475,28 @@ impl UniLyricApp {
 
                 info!("[Processor] 应用后处理器: {:?}", processor);
 
-                let (stripper_options, smoother_options) = {
+                let (stripper_options, smoother_options, reflow_options) = {
                     let settings = self.app_settings.lock().unwrap();
                     (
                         settings.metadata_stripper.clone(),
                         settings.syllable_smoothing,
+                        settings.line_reflow,
                     )
                 };
 
                 match processor {
                     ProcessorType::MetadataStripper => {
-                        lyrics_helper_rs::converter::processors::metadata_stripper::strip_descriptive_metadata_lines(
+                        let credits = lyrics_helper_rs::converter::processors::metadata_stripper::strip_and_extract_credits(
                             &mut parsed_data.lines,
                             &stripper_options,
                         );
+                        for (key, value) in credits {
+                            parsed_data
+                                .raw_metadata
+                                .entry(key.to_string())
+                                .or_default()
+                                .push(value);
+                        }
                     }
                     ProcessorType::SyllableSmoother => {
                         lyrics_helper_rs::converter::processors::syllable_smoothing::apply_smoothing(
@@ -501,6 +509,23 @@ impl UniLyricApp {
                             parsed_data,
                         );
                     }
+                    ProcessorType::BackgroundVocalExtractor => {
+                        lyrics_helper_rs::converter::processors::background_vocal_extractor::extract_background_vocals(
+                            &mut parsed_data.lines,
+                        );
+                    }
+                    ProcessorType::SongStructureDetector => {
+                        lyrics_helper_rs::converter::processors::song_structure::detect_song_structure(
+                            &mut parsed_data.lines,
+                            &SongStructureOptions::default(),
+                        );
+                    }
+                    ProcessorType::LineReflow => {
+                        lyrics_helper_rs::converter::processors::line_reflow::reflow_lines(
+                            &mut parsed_data.lines,
+                            &reflow_options,
+                        );
+                    }
                 }
                 self.dispatch_regeneration_task();
                 ActionResult::Success
@@ -540,22 +565,20 @@ impl UniLyricApp {
                 let action_tx = self.action_tx.clone();
 
                 self.tokio_runtime.spawn(async move {
-                    let artists_vec: Vec<&str> = if artist.is_empty() {
-                        vec![]
-                    } else {
-                        artist.split(&['/', ',', ';']).map(|s| s.trim()).collect()
-                    };
-
-                    let track_to_search = Track {
-                        title: Some(&title),
-                        artists: if artists_vec.is_empty() {
-                            None
-                        } else {
-                            Some(&artists_vec)
-                        },
-                        album: if album.is_empty() { None } else { Some(&album) },
-                        duration: if duration == 0 { None } else { Some(duration) },
-                    };
+                    let artists: Vec<String> = artist
+                        .split(&['/', ',', ';'])
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty())
+                        .collect();
+
+                    let query = crate::app_fetch_core::normalize_now_playing(
+                        &title,
+                        &artists,
+                        Some(album.as_str()).filter(|a| !a.is_empty()),
+                        Some(duration).filter(|&d| d != 0),
+                    );
+                    let artists_vec = query.artist_names();
+                    let track_to_search = query.to_track(&artists_vec);
 
                     let result = helper.lock().await.search_track(&track_to_search).await;
 
@@ -1199,10 +1222,18 @@ impl UniLyricApp {
         let helper_clone = Arc::clone(&self.lyrics_helper_state.helper);
 
         self.tokio_runtime.spawn(async move {
-            let result = match helper_clone.lock().await.load_providers().await {
+            let mut helper = helper_clone.lock().await;
+            if let Err(e) = helper.load_saved_http_client_config().await {
+                warn!("[LyricsHelper Task] 加载网络配置失败，使用默认配置: {e}");
+            }
+            let result = match helper.load_providers().await {
                 Ok(_) => Ok(()),
                 Err(e) => Err(e.to_string()),
             };
+            if let Err(e) = helper.load_saved_aliases().await {
+                warn!("[LyricsHelper Task] 加载别名表失败: {e}");
+            }
+            drop(helper);
 
             if tx.send(result).is_err() {
                 warn!("[LyricsHelper Task] 发送提供商加载结果失败，UI可能已关闭。");
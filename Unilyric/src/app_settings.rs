@@ -1,6 +1,8 @@
 use directories::ProjectDirs;
 use log::LevelFilter;
-use lyrics_helper_core::{LyricFormat, MetadataStripperOptions, SyllableSmoothingOptions};
+use lyrics_helper_core::{
+    LineReflowOptions, LyricFormat, MetadataStripperOptions, SyllableSmoothingOptions,
+};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
@@ -61,6 +63,8 @@ pub struct AppSettings {
 
     pub metadata_stripper: MetadataStripperOptions,
     pub syllable_smoothing: SyllableSmoothingOptions,
+    #[serde(default)]
+    pub line_reflow: LineReflowOptions,
     pub auto_apply_metadata_stripper: bool,
     pub auto_apply_agent_recognizer: bool,
     pub amll_mirror: AppAmllMirror,
@@ -92,6 +96,7 @@ impl Default for AppSettings {
             last_target_format: LyricFormat::Ttml,
             metadata_stripper: Default::default(),
             syllable_smoothing: Default::default(),
+            line_reflow: Default::default(),
             auto_apply_metadata_stripper: true,
             auto_apply_agent_recognizer: true,
             amll_mirror: AppAmllMirror::default(),
@@ -480,6 +480,19 @@ fn draw_settings_postprocessors(app: &mut UniLyricApp, ui: &mut egui::Ui) {
             );
         }
 
+        let mut credits_enabled = options
+            .flags
+            .contains(lyrics_helper_core::MetadataStripperFlags::EXTRACT_CREDITS);
+        if stripper_ui
+            .checkbox(&mut credits_enabled, "将制作人员行保存为元数据")
+            .changed()
+        {
+            options.flags.set(
+                lyrics_helper_core::MetadataStripperFlags::EXTRACT_CREDITS,
+                credits_enabled,
+            );
+        }
+
         stripper_ui.label("关键词 (每行一个):");
         let mut keywords_text = options.keywords.join("\n");
         if stripper_ui
@@ -519,4 +532,29 @@ fn draw_settings_postprocessors(app: &mut UniLyricApp, ui: &mut egui::Ui) {
             h_ui.add(egui::DragValue::new(&mut options.gap_threshold_ms).speed(1.0));
         });
     });
+
+    ui.collapsing("歌词行拆分与合并", |reflow_ui| {
+        let options = &mut app.ui.temp_edit_settings.line_reflow;
+
+        reflow_ui.horizontal(|h_ui| {
+            h_ui.label("单行最大字符数 (0 为不限制):");
+            h_ui.add(egui::DragValue::new(&mut options.max_chars).speed(1.0));
+        });
+        reflow_ui.horizontal(|h_ui| {
+            h_ui.label("单行最大时长 (ms, 0 为不限制):");
+            h_ui.add(egui::DragValue::new(&mut options.max_duration_ms).speed(100.0));
+        });
+        reflow_ui.horizontal(|h_ui| {
+            h_ui.label("短行合并阈值 (ms, 0 为不合并):");
+            h_ui.add(egui::DragValue::new(&mut options.min_duration_ms).speed(10.0));
+        });
+        reflow_ui.horizontal(|h_ui| {
+            h_ui.label("拆分间隔阈值 (ms):");
+            h_ui.add(egui::DragValue::new(&mut options.boundary_gap_ms).speed(1.0));
+        });
+        reflow_ui.horizontal(|h_ui| {
+            h_ui.label("最大合并间隔 (ms):");
+            h_ui.add(egui::DragValue::new(&mut options.max_merge_gap_ms).speed(1.0));
+        });
+    });
 }
@@ -114,6 +114,36 @@ pub fn draw_toolbar(app: &mut UniLyricApp, ui: &mut egui::Ui) {
                     ProcessorType::AgentRecognizer,
                 ))));
             }
+
+            if postprocess_menu
+                .add_enabled(lyrics_loaded, egui::Button::new("提取括号背景人声"))
+                .on_disabled_hover_text("需要先成功解析歌词")
+                .clicked()
+            {
+                app.send_action(UserAction::Lyrics(Box::new(LyricsAction::ApplyProcessor(
+                    ProcessorType::BackgroundVocalExtractor,
+                ))));
+            }
+
+            if postprocess_menu
+                .add_enabled(lyrics_loaded, egui::Button::new("识别歌曲结构"))
+                .on_disabled_hover_text("需要先成功解析歌词")
+                .clicked()
+            {
+                app.send_action(UserAction::Lyrics(Box::new(LyricsAction::ApplyProcessor(
+                    ProcessorType::SongStructureDetector,
+                ))));
+            }
+
+            if postprocess_menu
+                .add_enabled(lyrics_loaded, egui::Button::new("拆分/合并歌词行"))
+                .on_disabled_hover_text("需要先成功解析歌词")
+                .clicked()
+            {
+                app.send_action(UserAction::Lyrics(Box::new(LyricsAction::ApplyProcessor(
+                    ProcessorType::LineReflow,
+                ))));
+            }
         });
 
         ui_bar.menu_button("简繁转换", |tools_menu| {
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use bitflags::bitflags;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
@@ -181,6 +183,9 @@ pub struct AssGenerationOptions {
     /// 自定义的 [V4+ Styles] 部分内容。如果为 `None`，则使用默认值。
     /// 用户提供的内容应包含 `[V4+ Styles]` 头部和 `Format:` 行。
     pub styles: Option<String>,
+    /// 是否在每个歌曲结构段落（`song_part`）开始处输出一条注释事件。
+    #[serde(default)]
+    pub emit_song_part_comments: bool,
 }
 
 bitflags! {
@@ -191,6 +196,8 @@ bitflags! {
         const ENABLED                 = 1 << 0;
         /// 启用基于正则表达式的行移除
         const ENABLE_REGEX_STRIPPING  = 1 << 1;
+        /// 将被移除的制作人员行（如“作词：X”）解析为结构化元数据
+        const EXTRACT_CREDITS         = 1 << 2;
     }
 }
 
@@ -383,6 +390,9 @@ pub struct LrcGenerationOptions {
     pub sub_lines_output_mode: LrcSubLinesOutputMode,
     /// 控制行结束时间标记的输出方式
     pub end_time_output_mode: LrcEndTimeOutputMode,
+    /// 是否在每个歌曲结构段落（`song_part`）开始处输出一行 `[#:Chorus]` 形式的注释。
+    #[serde(default)]
+    pub emit_song_part_comments: bool,
 }
 
 impl Default for LrcGenerationOptions {
@@ -390,6 +400,7 @@ impl Default for LrcGenerationOptions {
         Self {
             sub_lines_output_mode: LrcSubLinesOutputMode::Ignore,
             end_time_output_mode: LrcEndTimeOutputMode::Never,
+            emit_song_part_comments: false,
         }
     }
 }
@@ -422,3 +433,202 @@ impl Default for SyllableSmoothingOptions {
         }
     }
 }
+
+// =============================================================================
+// 10. 歌曲结构识别选项
+// =============================================================================
+
+/// 控制歌曲结构（主歌、副歌等）识别的选项。
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Builder)]
+#[builder(setter(into), default)]
+pub struct SongStructureOptions {
+    /// 两行歌词被视为重复时所需的最低文本相似度 (0.0 ~ 1.0)。
+    pub line_similarity_threshold: f64,
+    /// 两个段落被视为同一部分时，较短段落中需要有重复行的最低比例 (0.0 ~ 1.0)。
+    pub section_similarity_threshold: f64,
+    /// 相邻两行的间隔超过该值（毫秒）时，视为进入新的段落。
+    pub section_gap_ms: u64,
+    /// 一个段落最少包含的行数，更短的段落会被并入前一个段落。
+    pub min_section_lines: usize,
+    /// 是否覆盖已有的 `song_part` 标记（例如来自 TTML 的 `itunes:song-part`）。
+    pub overwrite_existing: bool,
+}
+
+impl Default for SongStructureOptions {
+    fn default() -> Self {
+        Self {
+            line_similarity_threshold: 0.8,
+            section_similarity_threshold: 0.6,
+            section_gap_ms: 4000,
+            min_section_lines: 2,
+            overwrite_existing: false,
+        }
+    }
+}
+
+// =============================================================================
+// 11. 歌词行拆分与合并选项
+// =============================================================================
+
+/// 控制歌词行拆分与合并的选项。
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Builder)]
+#[builder(setter(into), default)]
+pub struct LineReflowOptions {
+    /// 单行主歌词允许的最大字符数（不含空白），超过时拆分。为 0 时不按字符数拆分。
+    pub max_chars: usize,
+    /// 单行允许的最大时长（毫秒），超过时拆分。为 0 时不按时长拆分。
+    pub max_duration_ms: u64,
+    /// 时长低于该值（毫秒）的行会尝试与相邻行合并。为 0 时不合并。
+    pub min_duration_ms: u64,
+    /// 相邻音节的间隔达到该值（毫秒）时，视为一个自然的拆分点。
+    pub boundary_gap_ms: u64,
+    /// 两行的间隔超过该值（毫秒）时不会被合并。
+    pub max_merge_gap_ms: u64,
+}
+
+impl Default for LineReflowOptions {
+    fn default() -> Self {
+        Self {
+            max_chars: 32,
+            max_duration_ms: 10_000,
+            min_duration_ms: 1000,
+            boundary_gap_ms: 300,
+            max_merge_gap_ms: 500,
+        }
+    }
+}
+
+// =============================================================================
+// 12. 多来源歌词合成选项
+// =============================================================================
+
+/// 控制多来源歌词合成的选项。
+///
+/// 每种轨道都有一个提供商偏好列表，排在前面的提供商优先。
+/// 不在列表中的提供商排在列表之后，按候选项的原始顺序参与选择。
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+#[builder(setter(into), default)]
+#[serde(default)]
+pub struct LyricsCompositionOptions {
+    /// 主歌词（含背景人声和演唱者）的提供商偏好。
+    pub main_preference: Vec<String>,
+    /// 翻译的提供商偏好。
+    pub translation_preference: Vec<String>,
+    /// 罗马音的提供商偏好。
+    pub romanization_preference: Vec<String>,
+    /// 两行文本相同时，允许的最大时间差（毫秒）。
+    ///
+    /// 不同提供商的时间轴常有整体偏移，文本相同且在该范围内的行会对齐到主歌词的时间。
+    pub text_alignment_window_ms: u64,
+    /// 对齐后辅助轨道与主歌词的匹配策略。
+    pub matching_strategy: AuxiliaryLineMatchingStrategy,
+}
+
+impl Default for LyricsCompositionOptions {
+    fn default() -> Self {
+        Self {
+            main_preference: vec![
+                "amll-ttml-database".to_string(),
+                "qq".to_string(),
+                "kugou".to_string(),
+                "netease".to_string(),
+            ],
+            translation_preference: vec![
+                "netease".to_string(),
+                "amll-ttml-database".to_string(),
+                "qq".to_string(),
+                "kugou".to_string(),
+            ],
+            romanization_preference: vec![
+                "amll-ttml-database".to_string(),
+                "netease".to_string(),
+                "qq".to_string(),
+                "kugou".to_string(),
+            ],
+            text_alignment_window_ms: 2000,
+            matching_strategy: AuxiliaryLineMatchingStrategy::default(),
+        }
+    }
+}
+
+// =============================================================================
+// 13. AMLL TTML Database 投稿校验选项
+// =============================================================================
+
+/// 控制 AMLL TTML Database 投稿校验和规范化的选项。
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+#[builder(setter(into), default)]
+#[serde(default)]
+pub struct AmllDbValidationOptions {
+    /// 平台 ID 元数据键，投稿时至少需要提供其中一个。
+    pub platform_id_keys: Vec<String>,
+    /// 翻译缺少 `xml:lang` 时，规范化输出使用的语言代码。为 `None` 时不补全。
+    pub default_translation_language: Option<String>,
+    /// 罗马音缺少 `xml:lang` 时，规范化输出使用的语言代码。为 `None` 时不补全。
+    pub default_romanization_language: Option<String>,
+    /// 是否输出带缩进的 TTML。数据库中的文件不带缩进。
+    pub format_output: bool,
+}
+
+impl Default for AmllDbValidationOptions {
+    fn default() -> Self {
+        Self {
+            platform_id_keys: vec![
+                "ncmMusicId".to_string(),
+                "qqMusicId".to_string(),
+                "spotifyId".to_string(),
+                "appleMusicId".to_string(),
+            ],
+            default_translation_language: None,
+            default_romanization_language: None,
+            format_output: false,
+        }
+    }
+}
+
+// =============================================================================
+// 14. 批量下载选项
+// =============================================================================
+
+/// 批量下载专辑、歌手或歌单歌词的选项。
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+#[builder(setter(into), default)]
+#[serde(default)]
+pub struct CollectionDownloadOptions {
+    /// 歌词文件的输出目录，不存在时会被创建。
+    pub output_dir: PathBuf,
+    /// 输出的歌词格式。
+    pub target_format: LyricFormat,
+    /// 生成歌词时使用的转换选项。
+    pub conversion: ConversionOptions,
+    /// 文件名模板，不含扩展名。
+    ///
+    /// 支持 `{index}`、`{title}`、`{artists}`、`{album}`、`{id}` 和 `{provider}` 占位符，
+    /// `{index}` 会按歌曲总数补零。
+    pub file_name_template: String,
+    /// 同时获取歌词的最大数量。
+    pub concurrency: usize,
+    /// 分页获取歌曲列表时每页的歌曲数量。
+    pub page_size: u32,
+    /// 最多下载的歌曲数量，为 `None` 时下载全部。
+    pub max_songs: Option<usize>,
+    /// 是否覆盖已存在的文件。
+    ///
+    /// 为 `false` 时已存在的文件会被跳过，重新运行同一任务即可从中断处继续。
+    pub overwrite: bool,
+}
+
+impl Default for CollectionDownloadOptions {
+    fn default() -> Self {
+        Self {
+            output_dir: PathBuf::from("."),
+            target_format: LyricFormat::Lrc,
+            conversion: ConversionOptions::default(),
+            file_name_template: "{index} - {artists} - {title}".to_string(),
+            concurrency: 4,
+            page_size: 50,
+            max_songs: None,
+            overwrite: false,
+        }
+    }
+}
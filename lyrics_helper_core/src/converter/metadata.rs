@@ -180,7 +180,8 @@ impl MetadataStore {
     /// - ti
     /// - ar
     /// - al
-    /// - by
+    /// - by（逐词歌词作者和在平台上传歌词、翻译的用户）
+    /// - lyricist / composer / arranger / producer / mixer / mastering
     /// - language
     /// - offset
     ///
@@ -188,38 +189,53 @@ impl MetadataStore {
     #[must_use]
     pub fn generate_lrc_header(&self) -> String {
         let mut output = String::new();
-        let mut written_keys: HashSet<&CanonicalMetadataKey> = HashSet::new();
-
-        // 定义LRC标签和对应的CanonicalMetadataKey的映射
-        let lrc_tags_to_write: Vec<(CanonicalMetadataKey, &str)> = vec![
-            (CanonicalMetadataKey::Title, "ti"),
-            (CanonicalMetadataKey::Artist, "ar"),
-            (CanonicalMetadataKey::Album, "al"),
-            (CanonicalMetadataKey::TtmlAuthorGithubLogin, "by"),
-            (CanonicalMetadataKey::Language, "language"),
-            (CanonicalMetadataKey::Offset, "offset"),
+
+        // 定义LRC标签和对应的CanonicalMetadataKey的映射，一个标签可以汇集多个键的值
+        let lrc_tags_to_write: Vec<(&[CanonicalMetadataKey], &str)> = vec![
+            (&[CanonicalMetadataKey::Title], "ti"),
+            (&[CanonicalMetadataKey::Artist], "ar"),
+            (&[CanonicalMetadataKey::Album], "al"),
+            (
+                &[
+                    CanonicalMetadataKey::TtmlAuthorGithubLogin,
+                    CanonicalMetadataKey::LyricsContributor,
+                    CanonicalMetadataKey::TranslationContributor,
+                ],
+                "by",
+            ),
+            (&[CanonicalMetadataKey::Lyricist], "lyricist"),
+            (&[CanonicalMetadataKey::Composer], "composer"),
+            (&[CanonicalMetadataKey::Arranger], "arranger"),
+            (&[CanonicalMetadataKey::Producer], "producer"),
+            (&[CanonicalMetadataKey::MixingEngineer], "mixer"),
+            (&[CanonicalMetadataKey::MasteringEngineer], "mastering"),
+            (&[CanonicalMetadataKey::Language], "language"),
+            (&[CanonicalMetadataKey::Offset], "offset"),
         ];
 
-        for (key_type, lrc_tag_name) in &lrc_tags_to_write {
-            if let Some(values) = self.data.get(key_type) {
-                if values.is_empty() {
-                    continue;
+        for (key_types, lrc_tag_name) in &lrc_tags_to_write {
+            // offset 只写第一个值，即使为空也保留 [offset:] 标签
+            if *lrc_tag_name == "offset" {
+                if let Some(first_value) = self.get_single_value(&CanonicalMetadataKey::Offset) {
+                    let _ = writeln!(output, "[offset:{}]", first_value.trim());
                 }
+                continue;
+            }
 
-                // 对所有非 offset 的键，都用 "/" 连接多个值
-                let value_to_write = if key_type != &CanonicalMetadataKey::Offset {
-                    values.join("/")
-                } else if let Some(first_value) = values.first() {
-                    first_value.clone()
-                } else {
-                    continue;
-                };
-
-                if !value_to_write.trim().is_empty() || *lrc_tag_name == "offset" {
-                    let _ = writeln!(output, "[{}:{}]", lrc_tag_name, value_to_write.trim());
-                    written_keys.insert(key_type);
-                }
+            let mut seen = HashSet::new();
+            let values: Vec<&str> = key_types
+                .iter()
+                .filter_map(|key_type| self.data.get(key_type))
+                .flatten()
+                .map(|v| v.trim())
+                .filter(|v| !v.is_empty() && seen.insert(*v))
+                .collect();
+            if values.is_empty() {
+                continue;
             }
+
+            // 对所有非 offset 的键，都用 "/" 连接多个值
+            let _ = writeln!(output, "[{lrc_tag_name}:{}]", values.join("/"));
         }
         output
     }
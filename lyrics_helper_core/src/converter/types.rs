@@ -559,6 +559,18 @@ pub enum CanonicalMetadataKey {
     Offset,
     /// 词曲作者。
     Songwriter,
+    /// 作词。
+    Lyricist,
+    /// 作曲。
+    Composer,
+    /// 编曲。
+    Arranger,
+    /// 制作人。
+    Producer,
+    /// 混音工程师。
+    MixingEngineer,
+    /// 母带工程师。
+    MasteringEngineer,
     /// 网易云音乐 ID。
     NcmMusicId,
     /// QQ音乐 ID。
@@ -569,10 +581,37 @@ pub enum CanonicalMetadataKey {
     AppleMusicId,
     /// 国际标准音像制品编码 (International Standard Recording Code)。
     Isrc,
+    /// 酷狗音乐歌曲 Hash。
+    KugouHash,
+    /// 酷狗音乐 `album_audio_id`。
+    KugouAlbumAudioId,
+    /// 网易云音乐专辑 ID。
+    NcmAlbumId,
+    /// QQ音乐专辑 ID。
+    QqAlbumId,
+    /// MusicBrainz 录音 ID (Recording MBID)。
+    MusicBrainzRecordingId,
+    /// 发行年份。
+    ReleaseYear,
+    /// 曲目在专辑中的序号。
+    TrackNumber,
+    /// 歌曲时长（毫秒）。
+    Duration,
     /// 逐词歌词作者 Github ID。
     TtmlAuthorGithub,
     /// 逐词歌词作者 GitHub 用户名。
     TtmlAuthorGithubLogin,
+    /// 在提供商平台上传歌词的用户。
+    LyricsContributor,
+    /// 在提供商平台上传翻译的用户。
+    TranslationContributor,
+
+    /// 本地化的歌曲标题，参数为 BCP 47 语言标签。
+    #[strum(disabled)]
+    LocalizedTitle(String),
+    /// 本地化的艺术家名，参数为 BCP 47 语言标签。
+    #[strum(disabled)]
+    LocalizedArtist(String),
 
     /// 用于所有其他未明确定义的标准或非标准元数据键。
     #[strum(disabled)]
@@ -588,13 +627,32 @@ impl fmt::Display for CanonicalMetadataKey {
             CanonicalMetadataKey::Language => "Language",
             CanonicalMetadataKey::Offset => "Offset",
             CanonicalMetadataKey::Songwriter => "Songwriter",
+            CanonicalMetadataKey::Lyricist => "Lyricist",
+            CanonicalMetadataKey::Composer => "Composer",
+            CanonicalMetadataKey::Arranger => "Arranger",
+            CanonicalMetadataKey::Producer => "Producer",
+            CanonicalMetadataKey::MixingEngineer => "MixingEngineer",
+            CanonicalMetadataKey::MasteringEngineer => "MasteringEngineer",
             CanonicalMetadataKey::NcmMusicId => "NCMMusicId",
             CanonicalMetadataKey::QqMusicId => "QQMusicId",
             CanonicalMetadataKey::SpotifyId => "SpotifyId",
             CanonicalMetadataKey::AppleMusicId => "AppleMusicId",
             CanonicalMetadataKey::Isrc => "ISRC",
+            CanonicalMetadataKey::KugouHash => "KugouHash",
+            CanonicalMetadataKey::KugouAlbumAudioId => "KugouAlbumAudioId",
+            CanonicalMetadataKey::NcmAlbumId => "NCMAlbumId",
+            CanonicalMetadataKey::QqAlbumId => "QQAlbumId",
+            CanonicalMetadataKey::MusicBrainzRecordingId => "MusicBrainzRecordingId",
+            CanonicalMetadataKey::ReleaseYear => "ReleaseYear",
+            CanonicalMetadataKey::TrackNumber => "TrackNumber",
+            CanonicalMetadataKey::Duration => "Duration",
+            // 本地化键使用 `Title[ja]` 的形式，可被 `FromStr` 解析回来
+            CanonicalMetadataKey::LocalizedTitle(lang) => return write!(f, "Title[{lang}]"),
+            CanonicalMetadataKey::LocalizedArtist(lang) => return write!(f, "Artist[{lang}]"),
             CanonicalMetadataKey::TtmlAuthorGithub => "TtmlAuthorGithub",
             CanonicalMetadataKey::TtmlAuthorGithubLogin => "TtmlAuthorGithubLogin",
+            CanonicalMetadataKey::LyricsContributor => "LyricsContributor",
+            CanonicalMetadataKey::TranslationContributor => "TranslationContributor",
             CanonicalMetadataKey::Custom(s) => s.as_str(),
         };
         write!(f, "{key_name}")
@@ -610,13 +668,31 @@ impl CanonicalMetadataKey {
             Self::Title
                 | Self::Artist
                 | Self::Album
+                | Self::Lyricist
+                | Self::Composer
+                | Self::Arranger
+                | Self::Producer
+                | Self::MixingEngineer
+                | Self::MasteringEngineer
                 | Self::NcmMusicId
                 | Self::QqMusicId
                 | Self::SpotifyId
                 | Self::AppleMusicId
                 | Self::Isrc
+                | Self::KugouHash
+                | Self::KugouAlbumAudioId
+                | Self::NcmAlbumId
+                | Self::QqAlbumId
+                | Self::MusicBrainzRecordingId
+                | Self::ReleaseYear
+                | Self::TrackNumber
+                | Self::Duration
+                | Self::LocalizedTitle(_)
+                | Self::LocalizedArtist(_)
                 | Self::TtmlAuthorGithub
                 | Self::TtmlAuthorGithubLogin
+                | Self::LyricsContributor
+                | Self::TranslationContributor
         )
     }
 
@@ -629,13 +705,31 @@ impl CanonicalMetadataKey {
             Self::Songwriter => 3,
             Self::Language => 4,
             Self::Offset => 5,
-            Self::NcmMusicId => 10,
-            Self::QqMusicId => 11,
-            Self::SpotifyId => 12,
-            Self::AppleMusicId => 13,
-            Self::Isrc => 14,
-            Self::TtmlAuthorGithub => 20,
-            Self::TtmlAuthorGithubLogin => 21,
+            Self::Lyricist => 6,
+            Self::Composer => 7,
+            Self::Arranger => 8,
+            Self::Producer => 9,
+            Self::MixingEngineer => 10,
+            Self::MasteringEngineer => 11,
+            Self::NcmMusicId => 12,
+            Self::QqMusicId => 13,
+            Self::SpotifyId => 14,
+            Self::AppleMusicId => 15,
+            Self::Isrc => 16,
+            Self::KugouHash => 17,
+            Self::KugouAlbumAudioId => 18,
+            Self::NcmAlbumId => 19,
+            Self::QqAlbumId => 20,
+            Self::MusicBrainzRecordingId => 21,
+            Self::TtmlAuthorGithub => 22,
+            Self::TtmlAuthorGithubLogin => 23,
+            Self::LyricsContributor => 24,
+            Self::TranslationContributor => 25,
+            Self::ReleaseYear => 40,
+            Self::TrackNumber => 41,
+            Self::Duration => 42,
+            Self::LocalizedTitle(_) => 50,
+            Self::LocalizedArtist(_) => 51,
             Self::Custom(_) => 1000,
         }
     }
@@ -645,6 +739,10 @@ impl FromStr for CanonicalMetadataKey {
     type Err = ParseCanonicalMetadataKeyError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(key) = Self::parse_localized(s) {
+            return Ok(key);
+        }
+
         match s.to_lowercase().as_str() {
             "ti" | "title" | "musicname" => Ok(Self::Title),
             "ar" | "artist" | "artists" => Ok(Self::Artist),
@@ -653,18 +751,55 @@ impl FromStr for CanonicalMetadataKey {
             "language" | "lang" => Ok(Self::Language),
             "offset" => Ok(Self::Offset),
             "songwriter" | "songwriters" => Ok(Self::Songwriter),
+            "lyricist" | "lyricists" | "作词" => Ok(Self::Lyricist),
+            "composer" | "composers" | "作曲" => Ok(Self::Composer),
+            "arranger" | "arrangers" | "编曲" => Ok(Self::Arranger),
+            "producer" | "producers" | "制作人" => Ok(Self::Producer),
+            "mixingengineer" | "mixer" => Ok(Self::MixingEngineer),
+            "masteringengineer" | "mastering" => Ok(Self::MasteringEngineer),
             "ncmmusicid" => Ok(Self::NcmMusicId),
             "qqmusicid" => Ok(Self::QqMusicId),
             "spotifyid" => Ok(Self::SpotifyId),
             "applemusicid" => Ok(Self::AppleMusicId),
             "isrc" => Ok(Self::Isrc),
+            "kugouhash" => Ok(Self::KugouHash),
+            "kugoualbumaudioid" | "albumaudioid" => Ok(Self::KugouAlbumAudioId),
+            "ncmalbumid" => Ok(Self::NcmAlbumId),
+            "qqalbumid" => Ok(Self::QqAlbumId),
+            "musicbrainzrecordingid" | "musicbrainz_recordingid" => {
+                Ok(Self::MusicBrainzRecordingId)
+            }
+            "releaseyear" | "year" => Ok(Self::ReleaseYear),
+            "tracknumber" | "trackno" => Ok(Self::TrackNumber),
+            "duration" => Ok(Self::Duration),
             "ttmlauthorgithub" => Ok(Self::TtmlAuthorGithub),
+            "lyricscontributor" | "lyricsuploader" => Ok(Self::LyricsContributor),
+            "translationcontributor" | "translationuploader" => Ok(Self::TranslationContributor),
             _ if !s.is_empty() => Ok(Self::Custom(s.to_string())),
             _ => Err(ParseCanonicalMetadataKeyError(s.to_string())),
         }
     }
 }
 
+impl CanonicalMetadataKey {
+    /// 解析 `Title[ja]`、`musicName[zh-Hant]` 形式的本地化键。
+    ///
+    /// 语言标签保持原样，不做大小写转换。
+    fn parse_localized(s: &str) -> Option<Self> {
+        let (base, rest) = s.split_once('[')?;
+        let lang = rest.strip_suffix(']')?.trim();
+        if lang.is_empty() {
+            return None;
+        }
+
+        match base.trim().to_lowercase().as_str() {
+            "ti" | "title" | "musicname" => Some(Self::LocalizedTitle(lang.to_string())),
+            "ar" | "artist" | "artists" => Some(Self::LocalizedArtist(lang.to_string())),
+            _ => None,
+        }
+    }
+}
+
 /// 存储从源文件解析出的、准备进行进一步处理或转换的歌词数据。
 /// 这是解析阶段的主要输出，也是后续处理和生成阶段的主要输入。
 #[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
@@ -690,6 +825,89 @@ pub struct ParsedSourceData {
     pub source_name: String,
 }
 
+/// 校验问题的严重程度。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValidationSeverity {
+    /// 不影响投稿，但审核时可能会被要求修改。
+    Warning,
+    /// 不符合数据库的规范，必须修复后才能投稿。
+    Error,
+}
+
+/// AMLL TTML Database 投稿校验发现的问题类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AmllDbIssueKind {
+    /// 缺少必需的 `amll:meta` 元数据（歌曲名、艺术家、专辑）。
+    MissingMetadata,
+    /// 没有提供任何平台 ID。
+    MissingPlatformId,
+    /// 歌词行使用了未在 `<head>` 中声明的演唱者。
+    UndeclaredAgent,
+    /// 声明了演唱者但没有任何歌词行使用。
+    UnusedAgent,
+    /// `<tt>` 缺少 `itunes:timing` 属性。
+    MissingTimingMode,
+    /// `itunes:timing` 的值不是 `Word` 或 `Line`。
+    InvalidTimingMode,
+    /// 翻译或罗马音缺少 `xml:lang` 语言标签。
+    MissingLanguageTag,
+    /// 语言标签不是合法的 BCP 47 标签。
+    InvalidLanguageTag,
+    /// 同一轨道中相邻音节的时间互相重叠。
+    OverlappingSpans,
+    /// 音节文本或行末带有多余的空白。
+    TrailingWhitespace,
+}
+
+/// AMLL TTML Database 投稿校验发现的一个问题。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AmllDbIssue {
+    /// 问题类型。
+    pub kind: AmllDbIssueKind,
+    /// 严重程度。
+    pub severity: ValidationSeverity,
+    /// 人类可读的描述。
+    pub message: String,
+    /// 问题所在的歌词行（`<p>` 元素的序号，从 0 开始），与具体行无关时为 `None`。
+    pub line_index: Option<usize>,
+    /// 是否已在规范化输出中自动修复。
+    pub fixed: bool,
+}
+
+/// AMLL TTML Database 投稿校验报告。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AmllDbValidationReport {
+    /// 发现的所有问题，按发现顺序排列。
+    pub issues: Vec<AmllDbIssue>,
+}
+
+impl AmllDbValidationReport {
+    /// 规范化输出是否可以直接投稿，即不存在未修复的错误。
+    #[must_use]
+    pub fn is_submittable(&self) -> bool {
+        !self
+            .issues
+            .iter()
+            .any(|issue| issue.severity == ValidationSeverity::Error && !issue.fixed)
+    }
+
+    /// 返回指定类型的所有问题。
+    pub fn issues_of_kind(&self, kind: AmllDbIssueKind) -> impl Iterator<Item = &AmllDbIssue> {
+        self.issues.iter().filter(move |issue| issue.kind == kind)
+    }
+}
+
+/// AMLL TTML Database 投稿校验的结果。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AmllDbSubmission {
+    /// 校验报告。
+    pub report: AmllDbValidationReport,
+    /// 修复了可自动修复的问题后、可直接投稿的 TTML。
+    pub normalized_ttml: String,
+}
+
 /// 表示从ASS中提取的标记信息。
 /// 元组的第一个元素是原始行号，第二个元素是标记文本。
 pub type MarkerInfo = (usize, String);
@@ -2,7 +2,10 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::{converter::types::ParsedSourceData, model::generic::Artist};
+use crate::{
+    converter::types::{CanonicalMetadataKey, ParsedSourceData},
+    model::generic::Artist,
+};
 
 /// 代表搜索结果与原始查询元数据的匹配程度。
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Default, Serialize)]
@@ -26,6 +29,220 @@ pub enum MatchType {
     Perfect = 100,
 }
 
+/// 名称（标题/专辑）的匹配程度。
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+pub enum NameMatchType {
+    /// 不匹配。
+    #[default]
+    NoMatch,
+    /// 匹配度低。
+    Low,
+    /// 匹配度中等。
+    Medium,
+    /// 按别名表或跨文字转写（拼音、罗马字等）后一致。
+    Transliterated,
+    /// 匹配度高。
+    High,
+    /// 匹配度非常高。
+    VeryHigh,
+    /// 完全一致。
+    Perfect,
+}
+
+/// 艺术家列表的匹配程度。
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+pub enum ArtistMatchType {
+    /// 不匹配。
+    #[default]
+    NoMatch,
+    /// 匹配度低。
+    Low,
+    /// 匹配度中等。
+    Medium,
+    /// 按别名表或跨文字转写（拼音、罗马字等）后一致。
+    Transliterated,
+    /// 匹配度高。
+    High,
+    /// 匹配度非常高。
+    VeryHigh,
+    /// 完全一致。
+    Perfect,
+}
+
+/// 时长的匹配程度。
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+pub enum DurationMatchType {
+    /// 不匹配。
+    #[default]
+    NoMatch,
+    /// 匹配度低。
+    Low,
+    /// 匹配度中等。
+    Medium,
+    /// 匹配度高。
+    High,
+    /// 匹配度非常高。
+    VeryHigh,
+    /// 完全一致。
+    Perfect,
+}
+
+/// 匹配评分中各项的权重。
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MatchWeights {
+    /// 标题的权重。
+    pub title: f64,
+    /// 艺术家的权重。
+    pub artist: f64,
+    /// 专辑的权重。
+    pub album: f64,
+    /// 时长的权重。
+    pub duration: f64,
+}
+
+impl Default for MatchWeights {
+    fn default() -> Self {
+        Self {
+            title: 1.0,
+            artist: 1.0,
+            album: 0.4,
+            duration: 1.0,
+        }
+    }
+}
+
+/// 用户维护的别名表。每组中的名称视为同一个艺术家或同一首歌，例如 `["周杰伦", "Jay Chou"]`。
+///
+/// 名称比较时忽略大小写和首尾空白。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AliasTable {
+    groups: Vec<Vec<String>>,
+}
+
+impl AliasTable {
+    /// 创建一个空的别名表。
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加一组互为别名的名称。
+    pub fn add_group<I, S>(&mut self, names: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let group: Vec<String> = names.into_iter().map(Into::into).collect();
+        if group.len() > 1 {
+            self.groups.push(group);
+        }
+    }
+
+    /// 所有别名组。
+    #[must_use]
+    pub fn groups(&self) -> &[Vec<String>] {
+        &self.groups
+    }
+
+    /// 名称所在别名组的序号。
+    #[must_use]
+    pub fn group_of(&self, name: &str) -> Option<usize> {
+        let name = name.trim();
+        self.groups.iter().position(|group| {
+            group
+                .iter()
+                .any(|alias| alias.trim().to_lowercase() == name.to_lowercase())
+        })
+    }
+
+    /// 两个名称是否属于同一个别名组。
+    #[must_use]
+    pub fn are_aliases(&self, a: &str, b: &str) -> bool {
+        self.group_of(a)
+            .is_some_and(|group| self.group_of(b) == Some(group))
+    }
+}
+
+/// 匹配评分的配置，包括各项权重和匹配等级的分数阈值。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MatchScoringConfig {
+    /// 各项的权重。
+    pub weights: MatchWeights,
+    /// 单项比较能得到的最高分。
+    pub max_single_score: f64,
+    /// 查询与结果的版本类型不一致时，从加权总分中扣除的分数。
+    pub version_mismatch_penalty: f64,
+    /// 用户维护的别名表，比较标题和艺术家时会参考。
+    pub aliases: AliasTable,
+    /// 分数阈值及对应的匹配等级。归一化总分严格大于阈值时取该等级，有多个时取阈值最高者。
+    pub thresholds: Vec<(f64, MatchType)>,
+}
+
+impl Default for MatchScoringConfig {
+    fn default() -> Self {
+        Self {
+            weights: MatchWeights::default(),
+            max_single_score: 7.0,
+            version_mismatch_penalty: 7.0,
+            aliases: AliasTable::default(),
+            thresholds: vec![
+                (21.0, MatchType::Perfect),
+                (19.0, MatchType::VeryHigh),
+                (17.0, MatchType::High),
+                (15.0, MatchType::PrettyHigh),
+                (11.0, MatchType::Medium),
+                (6.5, MatchType::Low),
+                (2.5, MatchType::VeryLow),
+            ],
+        }
+    }
+}
+
+/// 匹配评分的明细，用于解释某个搜索结果为何得到当前的匹配等级。
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct MatchBreakdown {
+    /// 标题的匹配程度，查询中没有标题时为 `None`。
+    pub title: Option<NameMatchType>,
+    /// 艺术家的匹配程度，查询中没有艺术家时为 `None`。
+    pub artist: Option<ArtistMatchType>,
+    /// 专辑的匹配程度，查询或结果中没有专辑时为 `None`。
+    pub album: Option<NameMatchType>,
+    /// 时长的匹配程度，查询或结果中没有时长时为 `None`。
+    pub duration: Option<DurationMatchType>,
+    /// 结果时长减去查询时长（毫秒）。
+    pub duration_delta_ms: Option<i64>,
+    /// 评分时使用的权重。
+    pub weights: MatchWeights,
+    /// 加权后的原始总分，已扣除版本不一致的分数。
+    pub raw_score: f64,
+    /// 根据查询中已有的字段算出的理论最高分。
+    pub possible_score: f64,
+    /// 按查询完整程度放大后的总分，用于和阈值比较。
+    pub normalized_score: f64,
+    /// 查询与结果的版本类型（现场版、混音版等）是否不一致。
+    pub version_mismatch: bool,
+    /// 越过的分数阈值，没有越过任何阈值时为 `None`。
+    pub threshold: Option<f64>,
+}
+
+/// 歌曲的版本类型提示，例如现场版或混音版。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum VersionHint {
+    /// 现场版。
+    Live,
+    /// 混音版。
+    Remix,
+    /// 原声（不插电）版。
+    Acoustic,
+    /// 伴奏版。
+    Instrumental,
+    /// 动画等使用的 TV 尺寸版。
+    TvSize,
+}
+
 /// 代表一个可搜索的歌曲元数据，用作搜索函数的输入参数。
 #[derive(Default, Debug, Clone)]
 pub struct Track<'a> {
@@ -37,12 +254,15 @@ pub struct Track<'a> {
     pub album: Option<&'a str>,
     /// 歌曲时长（毫秒）。
     pub duration: Option<u64>,
+    /// 歌曲的版本提示。`None` 表示未知，此时会从标题中识别；
+    /// 空切片表示明确是原版（录音室版）。
+    pub version_hints: Option<&'a [VersionHint]>,
 }
 
 /// 代表一个标准化的搜索结果条目。
 ///
 /// 这是所有 Provider 的 `search_songs` 方法需要返回的类型。
-#[derive(Debug, Deserialize, Clone, Default, Serialize, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Clone, Default, Serialize, PartialEq)]
 pub struct SearchResult {
     /// 搜索结果的歌曲标题。
     pub title: String,
@@ -66,6 +286,101 @@ pub struct SearchResult {
     pub cover_url: Option<String>,
     /// 语言代码。
     pub language: Option<Language>,
+    /// 匹配评分的明细，只有经过搜索模块评分的结果才会有。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub match_breakdown: Option<MatchBreakdown>,
+}
+
+/// 歌曲引用所属的平台或标识体系。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReferencePlatform {
+    /// QQ音乐，ID 为歌曲 mid。
+    Qq,
+    /// 网易云音乐，ID 为数字歌曲 ID。
+    Netease,
+    /// 酷狗音乐，ID 为文件 hash。
+    Kugou,
+    /// Apple Music，ID 为数字歌曲 ID。
+    AppleMusic,
+    /// Spotify，ID 为 22 位的曲目 ID。
+    Spotify,
+    /// 国际标准录音代码。
+    Isrc,
+}
+
+impl ReferencePlatform {
+    /// 可以直接用该 ID 获取歌词的提供商名称；没有对应提供商时返回 `None`。
+    #[must_use]
+    pub const fn provider_name(self) -> Option<&'static str> {
+        match self {
+            Self::Qq => Some("qq"),
+            Self::Netease => Some("netease"),
+            Self::Kugou => Some("kugou"),
+            Self::AppleMusic | Self::Spotify | Self::Isrc => None,
+        }
+    }
+}
+
+/// 一个已知平台上的歌曲引用，通常由分享链接或 ID 解析而来。
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SongReference {
+    /// 引用所属的平台。
+    pub platform: ReferencePlatform,
+    /// 在该平台上的 ID。
+    pub id: String,
+}
+
+/// 歌曲集合的类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CollectionKind {
+    /// 专辑。
+    Album,
+    /// 歌手的歌曲。
+    Artist,
+    /// 歌单。
+    Playlist,
+}
+
+/// 一个已知平台上的专辑、歌手或歌单的引用。
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CollectionReference {
+    /// 引用所属的平台。
+    pub platform: ReferencePlatform,
+    /// 集合的类型。
+    pub kind: CollectionKind,
+    /// 在该平台上的 ID。
+    pub id: String,
+}
+
+/// 歌词贡献者在歌词中的角色。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ContributorRole {
+    /// 上传主歌词。
+    Lyrics,
+    /// 上传翻译。
+    Translation,
+}
+
+impl ContributorRole {
+    /// 返回记录该角色贡献者的元数据键。
+    #[must_use]
+    pub const fn metadata_key(self) -> CanonicalMetadataKey {
+        match self {
+            Self::Lyrics => CanonicalMetadataKey::LyricsContributor,
+            Self::Translation => CanonicalMetadataKey::TranslationContributor,
+        }
+    }
+}
+
+/// 在提供商平台上传歌词或翻译的用户。
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LyricContributor {
+    /// 贡献者的角色。
+    pub role: ContributorRole,
+    /// 贡献者在平台上的昵称。
+    pub name: String,
+    /// 贡献者在平台上的用户 ID。
+    pub user_id: Option<String>,
 }
 
 /// 代表从 API 获取的、未经解析的原始歌词内容。
@@ -82,10 +397,13 @@ pub struct RawLyrics {
     pub translation: Option<String>,
     /// 可选的、与主歌词一同获取的音译歌词。
     pub romanization: Option<String>,
+    /// 提供商返回的歌词贡献者。
+    #[serde(default)]
+    pub contributors: Vec<LyricContributor>,
 }
 
 /// 代表完整的歌词获取结果，包括解析后的数据和原始副本。
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct FullLyricsResult {
     /// 经过统一解析和合并后的标准歌词数据。
     pub parsed: ParsedSourceData,
@@ -93,6 +411,23 @@ pub struct FullLyricsResult {
     pub raw: RawLyrics,
 }
 
+impl FullLyricsResult {
+    /// 记录一位歌词贡献者，并以对应的规范化键写入解析结果的元数据。
+    ///
+    /// 昵称为空或重复的贡献者会被忽略。
+    pub fn add_contributor(&mut self, contributor: LyricContributor) {
+        if contributor.name.trim().is_empty() || self.raw.contributors.contains(&contributor) {
+            return;
+        }
+        self.parsed
+            .raw_metadata
+            .entry(contributor.role.metadata_key().to_string())
+            .or_default()
+            .push(contributor.name.trim().to_string());
+        self.raw.contributors.push(contributor);
+    }
+}
+
 /// 代表一个包含歌词和其来源元数据的完整搜索结果。
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct LyricsAndMetadata {
@@ -102,6 +437,31 @@ pub struct LyricsAndMetadata {
     pub source_track: SearchResult,
 }
 
+/// 歌词内容的质量评估。
+///
+/// 与描述元数据吻合程度的 [`MatchType`] 不同，它只关心歌词本身有多完整。
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct LyricsQuality {
+    /// 歌词行数（不含空行）。
+    pub line_count: usize,
+    /// 逐字计时的行所占的比例（0.0 ~ 1.0）。
+    pub word_timed_coverage: f64,
+    /// 带有翻译的行所占的比例（0.0 ~ 1.0）。
+    pub translation_coverage: f64,
+    /// 带有罗马音的行所占的比例（0.0 ~ 1.0）。
+    pub romanization_coverage: f64,
+    /// 是否包含背景人声。
+    pub has_background_vocals: bool,
+    /// 演唱者的数量。
+    pub agent_count: usize,
+    /// 每分钟的歌词行数，歌曲时长未知时为 `None`。
+    pub lines_per_minute: Option<f64>,
+    /// 看起来像元数据（作词、作曲等）的行所占的比例（0.0 ~ 1.0）。
+    pub metadata_noise_ratio: f64,
+    /// 综合得分（0.0 ~ 100.0）。
+    pub score: f64,
+}
+
 /// 代表一次完整的搜索操作的最终结果。
 /// 包含最佳歌词匹配和所有搜索候选项。
 #[derive(Debug, Clone, Default)]
@@ -110,6 +470,8 @@ pub struct ComprehensiveSearchResult {
     pub primary_lyric_result: LyricsAndMetadata,
     /// 初始搜索返回的所有候选项，按匹配度从高到低排序。
     pub all_search_candidates: Vec<SearchResult>,
+    /// 主要歌词的质量评估。
+    pub primary_quality: Option<LyricsQuality>,
 }
 
 /// 歌曲的语言
@@ -31,6 +31,7 @@ async fn main() -> Result<()> {
         artists: Some(&["汪苏泷", "BY2"]),
         album: Some("万有引力"),
         duration: Some(235_000),
+        version_hints: None,
     };
     info!(
         "准备搜索歌曲: '{}' - '{}'",
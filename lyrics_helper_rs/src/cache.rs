@@ -0,0 +1,653 @@
+//! 提供商响应的持久化缓存。
+//!
+//! [`ProviderCache`] 把 `search_songs` 的结果和 `FullLyricsResult`（原始歌词和解析结果）
+//! 以 JSON 文件的形式保存在配置目录下，支持按类型设置有效期、按总大小淘汰最久未使用的条目，
+//! 以及只读取缓存、不访问网络的离线模式。
+//!
+//! [`CachedProvider`] 可以包装任意 `Provider`。也可以通过 `LyricsHelper::set_cache`
+//! 让所有提供商都使用同一个缓存。
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, SystemTime},
+};
+
+use async_trait::async_trait;
+use md5::{Digest, Md5};
+use parking_lot::Mutex;
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    config::CachedConfig,
+    error::{LyricsHelperError, Result},
+    http::HttpClient,
+    providers::Provider,
+};
+
+use lyrics_helper_core::{
+    CoverSize, FullLyricsResult, ParsedSourceData, SearchResult, SongReference, Track,
+    model::generic,
+};
+
+const CACHE_DIR_NAME: &str = "provider_cache";
+const SEARCH_DIR: &str = "search";
+const LYRICS_DIR: &str = "lyrics";
+
+/// 缓存的配置。
+#[derive(Debug, Clone)]
+pub struct CacheOptions {
+    /// 搜索结果的有效期。
+    pub search_ttl: Duration,
+    /// 歌词的有效期。
+    pub lyrics_ttl: Duration,
+    /// 缓存目录的最大总大小（字节），超出后会删除最久未使用的条目。
+    pub max_size_bytes: u64,
+    /// 是否只使用缓存。开启后缓存未命中时不会访问网络。
+    pub offline_only: bool,
+}
+
+impl Default for CacheOptions {
+    fn default() -> Self {
+        Self {
+            search_ttl: Duration::from_hours(24),
+            lyrics_ttl: Duration::from_hours(30 * 24),
+            max_size_bytes: 200 * 1024 * 1024,
+            offline_only: false,
+        }
+    }
+}
+
+/// 保存在磁盘上的提供商响应缓存。
+///
+/// 文件读写都在阻塞线程池中进行，不会阻塞异步运行时。
+pub struct ProviderCache {
+    dir: PathBuf,
+    options: CacheOptions,
+    offline_only: AtomicBool,
+    /// 缓存目录的总大小（字节）。第一次需要时扫描目录得到，之后随写入和删除增减，
+    /// 为 `None` 时表示需要重新扫描。
+    ///
+    /// 写入和淘汰都持有这把锁，避免多个写入同时触发淘汰。
+    size: Arc<Mutex<Option<u64>>>,
+}
+
+impl ProviderCache {
+    /// 在配置目录下的 `provider_cache` 中创建缓存。
+    pub fn new(options: CacheOptions) -> Result<Self> {
+        let dir = crate::config::get_config_file_path(CACHE_DIR_NAME)?;
+        Ok(Self::with_dir(dir, options))
+    }
+
+    /// 在指定目录中创建缓存。
+    pub fn with_dir(dir: impl Into<PathBuf>, options: CacheOptions) -> Self {
+        Self {
+            dir: dir.into(),
+            offline_only: AtomicBool::new(options.offline_only),
+            options,
+            size: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// 缓存所在的目录。
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 开启或关闭离线模式。
+    pub fn set_offline_only(&self, offline_only: bool) {
+        self.offline_only.store(offline_only, Ordering::Relaxed);
+    }
+
+    /// 是否处于离线模式。
+    #[must_use]
+    pub fn is_offline_only(&self) -> bool {
+        self.offline_only.load(Ordering::Relaxed)
+    }
+
+    /// 读取缓存的搜索结果。
+    pub async fn get_search(&self, provider: &str, track: &Track<'_>) -> Option<Vec<SearchResult>> {
+        self.read(
+            self.entry_path(provider, SEARCH_DIR, &track_key(track)),
+            self.options.search_ttl,
+        )
+        .await
+    }
+
+    /// 缓存搜索结果。
+    pub async fn put_search(&self, provider: &str, track: &Track<'_>, results: &[SearchResult]) {
+        self.write(
+            self.entry_path(provider, SEARCH_DIR, &track_key(track)),
+            &results,
+        )
+        .await;
+    }
+
+    /// 读取缓存的歌词。
+    pub async fn get_lyrics(&self, provider: &str, song_id: &str) -> Option<FullLyricsResult> {
+        self.read(
+            self.entry_path(provider, LYRICS_DIR, song_id),
+            self.options.lyrics_ttl,
+        )
+        .await
+    }
+
+    /// 缓存歌词。
+    pub async fn put_lyrics(&self, provider: &str, song_id: &str, lyrics: &FullLyricsResult) {
+        self.write(self.entry_path(provider, LYRICS_DIR, song_id), lyrics)
+            .await;
+    }
+
+    /// 删除某次搜索的缓存。
+    pub async fn invalidate_search(&self, provider: &str, track: &Track<'_>) -> Result<()> {
+        let path = self.entry_path(provider, SEARCH_DIR, &track_key(track));
+        let size = self.size.clone();
+        run_blocking(move || remove_entry(&path, &size)).await?
+    }
+
+    /// 删除某首歌词的缓存。
+    pub async fn invalidate_lyrics(&self, provider: &str, song_id: &str) -> Result<()> {
+        let path = self.entry_path(provider, LYRICS_DIR, song_id);
+        let size = self.size.clone();
+        run_blocking(move || remove_entry(&path, &size)).await?
+    }
+
+    /// 删除某个提供商的所有缓存。
+    pub async fn invalidate_provider(&self, provider: &str) -> Result<()> {
+        let path = self.dir.join(provider);
+        let size = self.size.clone();
+        run_blocking(move || remove_dir(&path, &size)).await?
+    }
+
+    /// 清空所有缓存。
+    pub async fn clear(&self) -> Result<()> {
+        let path = self.dir.clone();
+        let size = self.size.clone();
+        run_blocking(move || remove_dir(&path, &size)).await?
+    }
+
+    /// 缓存当前占用的总大小（字节）。
+    pub async fn size_bytes(&self) -> u64 {
+        let dir = self.dir.clone();
+        let size = self.size.clone();
+        run_blocking(move || *size.lock().get_or_insert_with(|| total_size(&dir)))
+            .await
+            .unwrap_or_default()
+    }
+
+    fn entry_path(&self, provider: &str, kind: &str, key: &str) -> PathBuf {
+        let hash = hex::encode(Md5::digest(key.as_bytes()));
+        self.dir
+            .join(provider)
+            .join(kind)
+            .join(format!("{hash}.json"))
+    }
+
+    async fn read<T: DeserializeOwned + Send + 'static>(
+        &self,
+        path: PathBuf,
+        ttl: Duration,
+    ) -> Option<T> {
+        // 离线模式下宁可使用过期的数据，也不要什么都没有
+        let keep_expired = self.is_offline_only();
+        let size = self.size.clone();
+        run_blocking(move || read_entry(&path, ttl, keep_expired, &size))
+            .await
+            .ok()
+            .flatten()
+    }
+
+    async fn write<T: Serialize>(&self, path: PathBuf, data: &T) {
+        let entry = CachedConfig {
+            data,
+            last_updated: chrono::Utc::now(),
+        };
+        let content = match serde_json::to_string(&entry) {
+            Ok(content) => content,
+            Err(e) => {
+                tracing::warn!("[Cache] 序列化缓存 {} 失败: {e}", path.display());
+                return;
+            }
+        };
+
+        let display_path = path.display().to_string();
+        let dir = self.dir.clone();
+        let size = self.size.clone();
+        let max_size_bytes = self.options.max_size_bytes;
+        let result = run_blocking(move || {
+            let mut size = size.lock();
+            // 先得到写入前的大小，写入的文件不会被重复计算
+            let total = size.get_or_insert_with(|| total_size(&dir));
+            let previous = fs::metadata(&path).map_or(0, |metadata| metadata.len());
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, content)?;
+            *total = total.saturating_sub(previous) + fs::metadata(&path)?.len();
+            if *total > max_size_bytes {
+                evict(&dir, total, max_size_bytes);
+            }
+            drop(size);
+            Ok(())
+        })
+        .await
+        .and_then(|result| result);
+        if let Err(e) = result {
+            tracing::warn!("[Cache] 写入缓存 {display_path} 失败: {e}");
+        }
+    }
+}
+
+/// 包装一个提供商，为它的搜索和歌词请求加上缓存。
+pub struct CachedProvider {
+    inner: Arc<dyn Provider + Send + Sync>,
+    cache: Arc<ProviderCache>,
+}
+
+impl CachedProvider {
+    /// 用指定的缓存包装一个提供商。
+    pub fn new(inner: Arc<dyn Provider + Send + Sync>, cache: Arc<ProviderCache>) -> Self {
+        Self { inner, cache }
+    }
+
+    /// 被包装的提供商。
+    #[must_use]
+    pub fn inner(&self) -> &Arc<dyn Provider + Send + Sync> {
+        &self.inner
+    }
+
+    fn offline_error(&self) -> LyricsHelperError {
+        LyricsHelperError::ProviderNotSupported(format!(
+            "{} 处于离线模式，只能使用缓存的搜索结果和歌词",
+            self.inner.name()
+        ))
+    }
+}
+
+#[async_trait]
+impl Provider for CachedProvider {
+    /// 缓存包装器需要一个已有的提供商，无法仅凭 HTTP 客户端创建。
+    async fn with_http_client(_http_client: Arc<dyn HttpClient>) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        Err(LyricsHelperError::ProviderNotSupported(
+            "CachedProvider 需要通过 CachedProvider::new 创建".into(),
+        ))
+    }
+
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    async fn search_songs(&self, track: &Track<'_>) -> Result<Vec<SearchResult>> {
+        let name = self.name();
+        if let Some(results) = self.cache.get_search(name, track).await {
+            tracing::debug!("[Cache] 命中 '{name}' 的搜索缓存。");
+            return Ok(results);
+        }
+        if self.cache.is_offline_only() {
+            return Ok(vec![]);
+        }
+
+        let results = self.inner.search_songs(track).await?;
+        // 没有结果可能只是暂时的，例如提供商限流或收录延迟，不写入缓存
+        if !results.is_empty() {
+            self.cache.put_search(name, track, &results).await;
+        }
+        Ok(results)
+    }
+
+    async fn find_by_reference(&self, reference: &SongReference) -> Result<Vec<SearchResult>> {
+        self.inner.find_by_reference(reference).await
+    }
+
+    async fn get_full_lyrics(&self, song_id: &str) -> Result<FullLyricsResult> {
+        let name = self.name();
+        if let Some(lyrics) = self.cache.get_lyrics(name, song_id).await {
+            tracing::debug!("[Cache] 命中 '{name}' 的歌词缓存: {song_id}");
+            return Ok(lyrics);
+        }
+        if self.cache.is_offline_only() {
+            return Err(LyricsHelperError::LyricNotFound);
+        }
+
+        let lyrics = self.inner.get_full_lyrics(song_id).await?;
+        self.cache.put_lyrics(name, song_id, &lyrics).await;
+        Ok(lyrics)
+    }
+
+    async fn get_lyrics(&self, song_id: &str) -> Result<ParsedSourceData> {
+        // 与 get_full_lyrics 共用同一条缓存，未命中时获取完整歌词并写入缓存
+        Ok(self.get_full_lyrics(song_id).await?.parsed)
+    }
+
+    async fn get_album_info(&self, album_id: &str) -> Result<generic::Album> {
+        if self.cache.is_offline_only() {
+            return Err(self.offline_error());
+        }
+        self.inner.get_album_info(album_id).await
+    }
+
+    async fn get_album_songs(
+        &self,
+        album_id: &str,
+        page: u32,
+        page_size: u32,
+    ) -> Result<Vec<generic::Song>> {
+        if self.cache.is_offline_only() {
+            return Err(self.offline_error());
+        }
+        self.inner.get_album_songs(album_id, page, page_size).await
+    }
+
+    async fn get_singer_songs(
+        &self,
+        singer_id: &str,
+        page: u32,
+        page_size: u32,
+    ) -> Result<Vec<generic::Song>> {
+        if self.cache.is_offline_only() {
+            return Err(self.offline_error());
+        }
+        self.inner
+            .get_singer_songs(singer_id, page, page_size)
+            .await
+    }
+
+    async fn get_playlist(&self, playlist_id: &str) -> Result<generic::Playlist> {
+        if self.cache.is_offline_only() {
+            return Err(self.offline_error());
+        }
+        self.inner.get_playlist(playlist_id).await
+    }
+
+    async fn get_song_info(&self, song_id: &str) -> Result<generic::Song> {
+        if self.cache.is_offline_only() {
+            return Err(self.offline_error());
+        }
+        self.inner.get_song_info(song_id).await
+    }
+
+    async fn get_album_cover_url(&self, album_id: &str, size: CoverSize) -> Result<String> {
+        if self.cache.is_offline_only() {
+            return Err(self.offline_error());
+        }
+        self.inner.get_album_cover_url(album_id, size).await
+    }
+}
+
+/// 由搜索条件生成缓存键。
+fn track_key(track: &Track<'_>) -> String {
+    serde_json::json!([track.title, track.artists, track.album, track.duration]).to_string()
+}
+
+/// 在阻塞线程池中执行文件操作。
+async fn run_blocking<R: Send + 'static>(f: impl FnOnce() -> R + Send + 'static) -> Result<R> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| LyricsHelperError::Internal(format!("缓存文件操作失败: {e}")))
+}
+
+/// 读取一个缓存条目，损坏或过期的条目会被删除。
+fn read_entry<T: DeserializeOwned>(
+    path: &Path,
+    ttl: Duration,
+    keep_expired: bool,
+    size: &Mutex<Option<u64>>,
+) -> Option<T> {
+    let content = fs::read_to_string(path).ok()?;
+    let entry: CachedConfig<T> = match serde_json::from_str(&content) {
+        Ok(entry) => entry,
+        Err(e) => {
+            tracing::warn!("[Cache] 缓存文件 {} 已损坏: {e}", path.display());
+            let _ = remove_entry(path, size);
+            return None;
+        }
+    };
+
+    let age = chrono::Utc::now() - entry.last_updated;
+    if age.to_std().unwrap_or_default() > ttl && !keep_expired {
+        let _ = remove_entry(path, size);
+        return None;
+    }
+
+    // 更新修改时间，淘汰时据此判断最近是否被使用过
+    if let Ok(file) = fs::File::options().append(true).open(path) {
+        let _ = file.set_modified(SystemTime::now());
+    }
+    Some(entry.data)
+}
+
+/// 总大小超出限制时，按最近使用时间从旧到新删除条目。
+///
+/// 淘汰时会重新扫描目录，顺便校正记录的总大小。
+fn evict(dir: &Path, total: &mut u64, max_size_bytes: u64) {
+    let mut files = cache_files(dir);
+    *total = files.iter().map(|(_, _, size)| size).sum();
+    if *total <= max_size_bytes {
+        return;
+    }
+
+    files.sort_by_key(|(_, modified, _)| *modified);
+    let mut removed = 0;
+    for (path, _, size) in files {
+        if *total <= max_size_bytes {
+            break;
+        }
+        if fs::remove_file(&path).is_ok() {
+            *total = total.saturating_sub(size);
+            removed += 1;
+        }
+    }
+    tracing::debug!("[Cache] 缓存超出大小限制，已淘汰 {removed} 个条目。");
+}
+
+/// 缓存目录中所有文件的总大小。
+fn total_size(dir: &Path) -> u64 {
+    cache_files(dir).iter().map(|(_, _, size)| size).sum()
+}
+
+/// 递归列出缓存目录中的所有文件及其修改时间和大小。
+fn cache_files(dir: &Path) -> Vec<(PathBuf, SystemTime, u64)> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                pending.push(entry.path());
+            } else {
+                let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                files.push((entry.path(), modified, metadata.len()));
+            }
+        }
+    }
+    files
+}
+
+/// 删除一个缓存条目，并从记录的总大小中减去它的大小。
+fn remove_entry(path: &Path, size: &Mutex<Option<u64>>) -> Result<()> {
+    let mut size = size.lock();
+    let len = fs::metadata(path).map_or(0, |metadata| metadata.len());
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+        _ => {}
+    }
+    if let Some(total) = size.as_mut() {
+        *total = total.saturating_sub(len);
+    }
+    drop(size);
+    Ok(())
+}
+
+/// 删除一个目录，下次需要时重新扫描总大小。
+fn remove_dir(path: &Path, size: &Mutex<Option<u64>>) -> Result<()> {
+    let mut size = size.lock();
+    let result = match fs::remove_dir_all(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    };
+    *size = None;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::{MockProvider, search_result};
+    use lyrics_helper_core::RawLyrics;
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            Self(std::env::temp_dir().join(format!("provider-cache-{}", uuid::Uuid::new_v4())))
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    /// 搜索 `TRACK` 时返回一条结果、歌词内容为歌曲 ID 的提供商。
+    fn counting_provider() -> Arc<MockProvider> {
+        Arc::new(
+            MockProvider::new("counting")
+                .with_results(vec![search_result("counting", "1", "Song A", "Artist A")])
+                .with_lyrics(|song_id| {
+                    Ok(FullLyricsResult {
+                        raw: RawLyrics {
+                            format: "lrc".to_string(),
+                            content: format!("[00:01.00]{song_id}"),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    })
+                }),
+        )
+    }
+
+    const TRACK: Track<'static> = Track {
+        title: Some("Song A"),
+        artists: Some(&["Artist A"]),
+        album: None,
+        duration: None,
+        version_hints: None,
+    };
+
+    #[tokio::test]
+    async fn test_cached_provider_hits_and_invalidation() {
+        let dir = TempDir::new();
+        let cache = Arc::new(ProviderCache::with_dir(&dir.0, CacheOptions::default()));
+        let inner = counting_provider();
+        let provider = CachedProvider::new(inner.clone(), cache.clone());
+
+        provider.search_songs(&TRACK).await.unwrap();
+        let results = provider.search_songs(&TRACK).await.unwrap();
+        assert_eq!(results[0].title, "Song A");
+        provider.get_full_lyrics("42").await.unwrap();
+        let lyrics = provider.get_full_lyrics("42").await.unwrap();
+        assert_eq!(lyrics.raw.content, "[00:01.00]42");
+        assert_eq!(inner.calls(), 2);
+
+        // get_lyrics 与 get_full_lyrics 使用同一条缓存
+        provider.get_lyrics("42").await.unwrap();
+        assert_eq!(inner.calls(), 2);
+        provider.get_lyrics("43").await.unwrap();
+        provider.get_full_lyrics("43").await.unwrap();
+        assert_eq!(inner.calls(), 3);
+
+        cache.invalidate_lyrics("counting", "42").await.unwrap();
+        provider.get_full_lyrics("42").await.unwrap();
+        assert_eq!(inner.calls(), 4);
+
+        cache.invalidate_provider("counting").await.unwrap();
+        provider.search_songs(&TRACK).await.unwrap();
+        assert_eq!(inner.calls(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_offline_mode_and_ttl() {
+        let dir = TempDir::new();
+        let options = CacheOptions {
+            search_ttl: Duration::ZERO,
+            ..Default::default()
+        };
+        let cache = Arc::new(ProviderCache::with_dir(&dir.0, options));
+        let inner = counting_provider();
+        let provider = CachedProvider::new(inner.clone(), cache.clone());
+
+        provider.search_songs(&TRACK).await.unwrap();
+        std::thread::sleep(Duration::from_millis(5));
+        // 已过期，离线模式下仍然返回旧数据
+        cache.set_offline_only(true);
+        assert_eq!(provider.search_songs(&TRACK).await.unwrap().len(), 1);
+        assert!(matches!(
+            provider.get_full_lyrics("7").await,
+            Err(LyricsHelperError::LyricNotFound)
+        ));
+        assert_eq!(inner.calls(), 1);
+
+        // 在线时过期的数据会被丢弃
+        cache.set_offline_only(false);
+        provider.search_songs(&TRACK).await.unwrap();
+        assert_eq!(inner.calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_empty_search_results_are_not_cached() {
+        let dir = TempDir::new();
+        let cache = Arc::new(ProviderCache::with_dir(&dir.0, CacheOptions::default()));
+        let inner = Arc::new(MockProvider::new("empty"));
+        let provider = CachedProvider::new(inner.clone(), cache.clone());
+
+        assert!(provider.search_songs(&TRACK).await.unwrap().is_empty());
+        assert!(provider.search_songs(&TRACK).await.unwrap().is_empty());
+        assert_eq!(inner.calls(), 2);
+        assert_eq!(cache.size_bytes().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_size_based_eviction() {
+        let dir = TempDir::new();
+        let options = CacheOptions {
+            max_size_bytes: 600,
+            ..Default::default()
+        };
+        let cache = ProviderCache::with_dir(&dir.0, options);
+        let lyrics = FullLyricsResult {
+            raw: RawLyrics {
+                content: "x".repeat(200),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        for id in ["1", "2", "3", "4"] {
+            cache.put_lyrics("counting", id, &lyrics).await;
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        let size = cache.size_bytes().await;
+        assert!(size <= 600);
+        assert_eq!(size, total_size(&dir.0), "记录的总大小应与磁盘一致");
+        assert!(cache.get_lyrics("counting", "4").await.is_some());
+        assert!(cache.get_lyrics("counting", "1").await.is_none());
+
+        cache.invalidate_lyrics("counting", "4").await.unwrap();
+        assert_eq!(cache.size_bytes().await, total_size(&dir.0));
+    }
+}
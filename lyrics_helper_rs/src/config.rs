@@ -1,11 +1,17 @@
 //! 负责处理应用的持久化配置。
 
 use chrono::{DateTime, Utc};
+use lyrics_helper_core::AliasTable;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use tracing::info;
 
+const HTTP_CLIENT_CONFIG_FILE: &str = "http_client_config.json";
+const ALIAS_TABLE_FILE: &str = "artist_aliases.json";
+
 /// 获取应用配置目录下指定文件的完整路径。
 ///
 /// # 参数
@@ -24,20 +30,92 @@ pub fn get_config_file_path(filename: &str) -> Result<PathBuf, std::io::Error> {
     }
 }
 
-pub fn load_amll_config() -> Result<AmllConfig, Box<dyn std::error::Error>> {
-    let config_path = get_config_file_path("amll_config.json")?;
-    match fs::read_to_string(config_path) {
-        Ok(content) => {
-            let config: AmllConfig = serde_json::from_str(&content)?;
-            info!("已加载 AMLL 镜像配置。");
-            Ok(config)
+/// 读取 AMLL TTML DB 的镜像配置，文件不存在时使用默认源。
+///
+/// 文件读写在阻塞线程池中进行，不会阻塞异步运行时。
+pub async fn load_amll_config() -> Result<AmllConfig, Box<dyn std::error::Error + Send + Sync>> {
+    tokio::task::spawn_blocking(|| {
+        let config_path = get_config_file_path("amll_config.json")?;
+        match fs::read_to_string(config_path) {
+            Ok(content) => {
+                let config: AmllConfig = serde_json::from_str(&content)?;
+                info!("已加载 AMLL 镜像配置。");
+                Ok(config)
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                info!("未找到 AMLL 配置文件，使用默认源。");
+                Ok(AmllConfig::default())
+            }
+            Err(e) => Err(e.into()),
         }
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-            info!("未找到 AMLL 配置文件，使用默认源。");
-            Ok(AmllConfig::default())
+    })
+    .await?
+}
+
+/// 读取持久化的 HTTP 客户端配置，文件不存在时返回默认配置。
+///
+/// 文件读写在阻塞线程池中进行，不会阻塞异步运行时。
+pub async fn load_http_client_config()
+-> Result<HttpClientConfig, Box<dyn std::error::Error + Send + Sync>> {
+    tokio::task::spawn_blocking(|| {
+        let config_path = get_config_file_path(HTTP_CLIENT_CONFIG_FILE)?;
+        match fs::read_to_string(config_path) {
+            Ok(content) => {
+                let config: HttpClientConfig = serde_json::from_str(&content)?;
+                info!("已加载 HTTP 客户端配置。");
+                Ok(config)
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(HttpClientConfig::default()),
+            Err(e) => Err(e.into()),
         }
-        Err(e) => Err(e.into()),
-    }
+    })
+    .await?
+}
+
+/// 将 HTTP 客户端配置保存到配置目录。
+pub async fn save_http_client_config(
+    config: &HttpClientConfig,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let content = serde_json::to_string_pretty(config)?;
+    tokio::task::spawn_blocking(move || {
+        let config_path = get_config_file_path(HTTP_CLIENT_CONFIG_FILE)?;
+        fs::write(config_path, content)?;
+        Ok(())
+    })
+    .await?
+}
+
+/// 读取用户维护的别名表，文件不存在时返回空表。
+///
+/// 文件内容是一个二维数组，每个子数组是一组互为别名的名称，例如 `[["周杰伦", "Jay Chou"]]`。
+/// 文件读写在阻塞线程池中进行，不会阻塞异步运行时。
+pub async fn load_alias_table() -> Result<AliasTable, Box<dyn std::error::Error + Send + Sync>> {
+    tokio::task::spawn_blocking(|| {
+        let config_path = get_config_file_path(ALIAS_TABLE_FILE)?;
+        match fs::read_to_string(config_path) {
+            Ok(content) => {
+                let table: AliasTable = serde_json::from_str(&content)?;
+                info!("已加载 {} 组别名。", table.groups().len());
+                Ok(table)
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(AliasTable::default()),
+            Err(e) => Err(e.into()),
+        }
+    })
+    .await?
+}
+
+/// 将别名表保存到配置目录。
+pub async fn save_alias_table(
+    table: &AliasTable,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let content = serde_json::to_string_pretty(table)?;
+    tokio::task::spawn_blocking(move || {
+        let config_path = get_config_file_path(ALIAS_TABLE_FILE)?;
+        fs::write(config_path, content)?;
+        Ok(())
+    })
+    .await?
 }
 
 pub fn load_cached_config<T: for<'de> Deserialize<'de>>(
@@ -80,7 +158,7 @@ pub fn write_to_cache(
     Ok(())
 }
 
-fn get_cache_file_path(filename: &str) -> Result<PathBuf, std::io::Error> {
+pub(crate) fn get_cache_file_path(filename: &str) -> Result<PathBuf, std::io::Error> {
     dirs::cache_dir().map_or_else(
         || {
             Err(std::io::Error::new(
@@ -120,6 +198,13 @@ pub enum AmllMirror {
         /// 示例：`https://your.mirror.com/path/to/raw-lyrics/{song_id}`
         lyrics_url_template: String,
     },
+    /// 本地快照，完全不访问网络。
+    LocalPath {
+        /// amll-ttml-db 仓库的本地克隆目录，或下载的 `.tar.gz`/`.tgz`/`.tar`/`.zip` 归档。
+        ///
+        /// 示例：`/home/user/amll-ttml-db` 或 `/home/user/Downloads/amll-ttml-db-main.zip`
+        path: PathBuf,
+    },
 }
 
 /// AMLL TTML Database 的配置项。
@@ -130,6 +215,68 @@ pub struct AmllConfig {
     pub mirror: AmllMirror,
 }
 
+/// 内置 HTTP 客户端的网络配置。
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct HttpClientConfig {
+    /// 所有提供商默认使用的代理，支持 `http://`、`https://` 和 `socks5://`。
+    pub proxy: Option<String>,
+    /// 按提供商名称覆盖代理，例如 `"qq"`。值为空字符串时该提供商直连。
+    pub provider_proxies: HashMap<String, String>,
+    /// 整个请求的超时时间（秒）。
+    pub timeout_secs: u64,
+    /// 建立连接的超时时间（秒）。
+    pub connect_timeout_secs: Option<u64>,
+    /// 读取响应的超时时间（秒）。
+    pub read_timeout_secs: Option<u64>,
+    /// 覆盖浏览器模拟时使用的 User-Agent。
+    pub user_agent: Option<String>,
+    /// 每个请求都会携带的额外请求头。
+    pub default_headers: HashMap<String, String>,
+    /// 额外信任的根证书，PEM 格式的文件路径。
+    pub root_certificates: Vec<PathBuf>,
+    /// 将域名解析到固定的地址，跳过系统 DNS。
+    pub dns_overrides: HashMap<String, Vec<SocketAddr>>,
+}
+
+impl Default for HttpClientConfig {
+    fn default() -> Self {
+        Self {
+            proxy: None,
+            provider_proxies: HashMap::new(),
+            timeout_secs: 10,
+            connect_timeout_secs: None,
+            read_timeout_secs: None,
+            user_agent: None,
+            default_headers: HashMap::new(),
+            root_certificates: Vec::new(),
+            dns_overrides: HashMap::new(),
+        }
+    }
+}
+
+impl HttpClientConfig {
+    /// 返回指定提供商实际使用的代理。
+    #[must_use]
+    pub fn proxy_for(&self, provider: &str) -> Option<&str> {
+        match self.provider_proxies.get(provider) {
+            Some(proxy) if proxy.is_empty() => None,
+            Some(proxy) => Some(proxy),
+            None => self.proxy.as_deref(),
+        }
+    }
+
+    /// 返回指定提供商的配置，其中 `proxy` 已被替换为该提供商实际使用的代理。
+    #[must_use]
+    pub fn for_provider(&self, provider: &str) -> Self {
+        Self {
+            proxy: self.proxy_for(provider).map(str::to_string),
+            provider_proxies: HashMap::new(),
+            ..self.clone()
+        }
+    }
+}
+
 /// 通用的、带时间戳的缓存配置结构。
 #[derive(Serialize, Deserialize, Debug)]
 pub struct CachedConfig<T> {
@@ -138,3 +285,25 @@ pub struct CachedConfig<T> {
     /// 最后更新的时间戳。
     pub last_updated: DateTime<Utc>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_http_client_config_provider_proxy() {
+        let config: HttpClientConfig = serde_json::from_str(
+            r#"{"proxy":"socks5://127.0.0.1:1080","provider_proxies":{"qq":"http://10.0.0.1:8080","kugou":""}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.timeout_secs, 10);
+        assert_eq!(config.proxy_for("qq"), Some("http://10.0.0.1:8080"));
+        assert_eq!(config.proxy_for("kugou"), None);
+        assert_eq!(config.proxy_for("netease"), Some("socks5://127.0.0.1:1080"));
+
+        let qq = config.for_provider("qq");
+        assert_eq!(qq.proxy.as_deref(), Some("http://10.0.0.1:8080"));
+        assert!(qq.provider_proxies.is_empty());
+    }
+}
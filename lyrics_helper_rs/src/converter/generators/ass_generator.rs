@@ -25,6 +25,7 @@ pub fn generate_ass(
         metadata_store,
         agents,
         is_line_timed,
+        options.emit_song_part_comments,
     )?;
 
     Ok(ass_content)
@@ -94,6 +95,7 @@ fn write_ass_events(
     metadata_store: &MetadataStore,
     agents: &AgentStore,
     is_line_timed: bool,
+    emit_song_part_comments: bool,
 ) -> Result<(), ConvertError> {
     writeln!(output, "[Events]")?;
     writeln!(
@@ -122,7 +124,20 @@ fn write_ass_events(
         }
     }
 
+    let mut previous_song_part: Option<&str> = None;
     for line in lines {
+        if emit_song_part_comments {
+            let song_part = line.song_part.as_deref().filter(|p| !p.is_empty());
+            if let Some(part) = song_part
+                && song_part != previous_song_part
+            {
+                // 使用独立的 section 样式，解析时不会被当作元数据或歌词
+                let time = format_ass_time(line.start_ms);
+                writeln!(output, "Comment: 0,{time},{time},section,,0,0,0,,{part}")?;
+            }
+            previous_song_part = song_part;
+        }
+
         write_events_for_line(output, line, is_line_timed)?;
     }
 
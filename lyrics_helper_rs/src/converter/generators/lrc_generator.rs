@@ -20,7 +20,18 @@ pub fn generate_lrc(
         writeln!(lrc_output, "{}", lrc_header.trim_end_matches('\n'))?;
     }
 
+    let mut previous_song_part: Option<&str> = None;
     for (i, line) in lines.iter().enumerate() {
+        if options.emit_song_part_comments {
+            let song_part = line.song_part.as_deref().filter(|p| !p.is_empty());
+            if let Some(part) = song_part
+                && song_part != previous_song_part
+            {
+                writeln!(lrc_output, "[#:{part}]")?;
+            }
+            previous_song_part = song_part;
+        }
+
         let main_annotated_track = line
             .tracks
             .iter()
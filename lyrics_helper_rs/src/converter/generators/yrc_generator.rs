@@ -24,8 +24,10 @@ pub fn generate_yrc(
             CanonicalMetadataKey::Title => "曲名",
             CanonicalMetadataKey::Artist => "歌手",
             CanonicalMetadataKey::Album => "专辑",
-            CanonicalMetadataKey::Songwriter => "作词",
-            CanonicalMetadataKey::Custom(custom_key) if custom_key == "composer" => "作曲",
+            CanonicalMetadataKey::Songwriter | CanonicalMetadataKey::Lyricist => "作词",
+            CanonicalMetadataKey::Composer => "作曲",
+            CanonicalMetadataKey::Arranger => "编曲",
+            CanonicalMetadataKey::Producer => "制作人",
             // 对于其他不适合显示的键（如 Offset, Language），返回空字符串来忽略它们
             _ => "",
         };
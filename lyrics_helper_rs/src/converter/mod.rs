@@ -15,7 +15,8 @@ use tracing::{debug, warn};
 use lyrics_helper_core::{
     AuxiliaryLineMatchingStrategy, ContentType, ConversionInput, ConversionOptions,
     ConversionResult, ConversionTask, ConvertError, FullConversionResult, InputFile, LyricFormat,
-    LyricLine, LyricTrack, MetadataStore, ParsedSourceData, TrackMetadataKey,
+    LyricLine, LyricTrack, MetadataStore, MetadataStripperOptions, ParsedSourceData,
+    TrackMetadataKey,
 };
 use ttml_processor::{generate_ttml, parse_ttml};
 
@@ -112,8 +113,9 @@ pub fn generate_from_parsed<S: BuildHasher>(
 ) -> Result<FullConversionResult, ConvertError> {
     ChineseConversionProcessor::process(&mut source_data.lines, &options.chinese_conversion);
 
-    processors::metadata_stripper::strip_descriptive_metadata_lines(
+    strip_metadata_lines_and_promote_credits(
         &mut source_data.lines,
+        &mut source_data.raw_metadata,
         &options.metadata_stripper,
     );
 
@@ -247,8 +249,9 @@ pub fn parse_and_merge(
 
     ChineseConversionProcessor::process(&mut main_new_lines, &options.chinese_conversion);
 
-    processors::metadata_stripper::strip_descriptive_metadata_lines(
+    strip_metadata_lines_and_promote_credits(
         &mut main_new_lines,
+        &mut main_parsed_source.raw_metadata,
         &options.metadata_stripper,
     );
 
@@ -257,6 +260,18 @@ pub fn parse_and_merge(
     Ok(main_parsed_source)
 }
 
+/// 移除元数据行，并把从中解析出的制作人员信息写入原始元数据。
+fn strip_metadata_lines_and_promote_credits(
+    lines: &mut Vec<LyricLine>,
+    raw_metadata: &mut HashMap<String, Vec<String>>,
+    options: &MetadataStripperOptions,
+) {
+    let credits = processors::metadata_stripper::strip_and_extract_credits(lines, options);
+    for (key, value) in credits {
+        raw_metadata.entry(key.to_string()).or_default().push(value);
+    }
+}
+
 /// 合并主歌词行与翻译、罗马音数据，将翻译和罗马音轨道按时间戳插入到主歌词行中。
 pub fn merge_tracks(
     main_lines: &mut [LyricLine],
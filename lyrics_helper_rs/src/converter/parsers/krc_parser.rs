@@ -39,6 +39,18 @@ struct RawSyllable {
     duration_ms: u64,
 }
 
+/// KRC 头部中携带歌曲信息的标签，以及它们对应的规范化元数据键。
+///
+/// `hash` 是酷狗的歌曲 Hash，`total` 是歌曲时长（毫秒）。
+const KRC_ID_TAGS: [(&str, &str); 2] = [("[hash:", "kugouHash"), ("[total:", "duration")];
+
+fn parse_krc_id_tag(line: &str) -> Option<(&'static str, String)> {
+    KRC_ID_TAGS.iter().find_map(|(prefix, key)| {
+        let value = line.strip_prefix(prefix)?.strip_suffix(']')?.trim();
+        (!value.is_empty()).then(|| (*key, value.to_string()))
+    })
+}
+
 /// 解析 KRC 格式内容到 `ParsedSourceData` 结构。
 pub fn parse_krc(content: &str) -> Result<ParsedSourceData, ConvertError> {
     let mut lines: Vec<LyricLine> = Vec::new();
@@ -51,11 +63,14 @@ pub fn parse_krc(content: &str) -> Result<ParsedSourceData, ConvertError> {
     for (i, line_str) in content.lines().enumerate() {
         let trimmed_line = line_str.trim();
 
+        if let Some((key, value)) = parse_krc_id_tag(trimmed_line) {
+            raw_metadata.entry(key.to_string()).or_default().push(value);
+            continue;
+        }
+
         if trimmed_line.is_empty()
             || trimmed_line.starts_with("[language:")
             || trimmed_line.starts_with("[id:")
-            || trimmed_line.starts_with("[hash:")
-            || trimmed_line.starts_with("[total:")
             || trimmed_line.starts_with("[qq:")
             || trimmed_line.starts_with("[offset:")
             || trimmed_line.starts_with("[sign:")
@@ -375,4 +390,19 @@ mod tests {
             "最后一个音节的开始时间应匹配"
         );
     }
+    #[test]
+    fn test_krc_maps_hash_and_total_to_metadata() {
+        let content = "[ti:歌名]\n[hash:5D7A1B2C3D4E5F60718293A4B5C6D7E8]\n[total:254000]\n[0,1000]<0,500,0>歌<500,500,0>词";
+        let parsed = parse_krc(content).unwrap();
+
+        assert_eq!(
+            parsed.raw_metadata.get("kugouHash"),
+            Some(&vec!["5D7A1B2C3D4E5F60718293A4B5C6D7E8".to_string()])
+        );
+        assert_eq!(
+            parsed.raw_metadata.get("duration"),
+            Some(&vec!["254000".to_string()])
+        );
+        assert_eq!(parsed.lines.len(), 1);
+    }
 }
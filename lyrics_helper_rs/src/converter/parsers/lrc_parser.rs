@@ -353,4 +353,35 @@ mod tests {
         assert_eq!(get_track_text(&track.romanizations[0]), "こんにちは");
         assert_eq!(get_track_text(&track.translations[0]), "你好世界");
     }
+
+    #[test]
+    fn test_id_tags_map_to_canonical_keys() {
+        let content = "[ncmMusicId:1234567]\n[ncmAlbumId:7654321]\n[qqAlbumId:0001AlbumMid]\n[kugouHash:ABCDEF]\n[00:20.00]Hello world";
+        let parsed_data = parse_lrc(content, &LrcParsingOptions::default()).unwrap();
+        let metadata = lyrics_helper_core::MetadataStore::from(&parsed_data);
+
+        for (key, value) in [
+            (
+                lyrics_helper_core::CanonicalMetadataKey::NcmMusicId,
+                "1234567",
+            ),
+            (
+                lyrics_helper_core::CanonicalMetadataKey::NcmAlbumId,
+                "7654321",
+            ),
+            (
+                lyrics_helper_core::CanonicalMetadataKey::QqAlbumId,
+                "0001AlbumMid",
+            ),
+            (
+                lyrics_helper_core::CanonicalMetadataKey::KugouHash,
+                "ABCDEF",
+            ),
+        ] {
+            assert_eq!(
+                metadata.get_single_value(&key).map(String::as_str),
+                Some(value)
+            );
+        }
+    }
 }
@@ -0,0 +1,413 @@
+//! 背景人声提取器。
+//!
+//! 许多 LRC/QRC 歌词会把背景人声用括号内联在主歌词中，例如：
+//!
+//! 主歌词 (背景人声)
+//! 主歌词 (背景) 主歌词
+//!
+//! 该处理器会把括号内的部分连同其音节时间一起移动到 `ContentType::Background`
+//! 轨道中，并对翻译和罗马音中对应的括号部分做同样的拆分。
+
+use lyrics_helper_core::{AnnotatedTrack, ContentType, LyricLine, LyricSyllable, LyricTrack, Word};
+use tracing::debug;
+
+/// 支持的左括号（半角和全角）。
+const OPEN_BRACKETS: [char; 2] = ['(', '（'];
+/// 支持的右括号（半角和全角）。
+const CLOSE_BRACKETS: [char; 2] = [')', '）'];
+
+/// 音节被括号切开后的一个片段。
+struct SyllablePiece {
+    text: String,
+    is_background: bool,
+    ends_with_space: bool,
+    /// 片段中非括号字符的数量，用于按比例分配时间。
+    weight: usize,
+}
+
+/// 提取所有行中以括号内联的背景人声。
+///
+/// 已经包含背景人声轨道的行会被跳过。整行都在括号内的行也会被保留，
+/// 因为把它整个移走会留下一个空的主歌词行。
+pub fn extract_background_vocals(lines: &mut [LyricLine]) {
+    let mut extracted_count = 0;
+
+    for line in lines.iter_mut() {
+        if line.background_track().is_some() {
+            continue;
+        }
+
+        let Some(main_index) = line
+            .tracks
+            .iter()
+            .position(|t| t.content_type == ContentType::Main)
+        else {
+            continue;
+        };
+
+        let main_annotated_track = &mut line.tracks[main_index];
+        let Some((main_content, bg_content)) = split_track(&main_annotated_track.content) else {
+            continue;
+        };
+        main_annotated_track.content = main_content;
+
+        let bg_translations = split_auxiliary_tracks(&mut main_annotated_track.translations);
+        let bg_romanizations = split_auxiliary_tracks(&mut main_annotated_track.romanizations);
+
+        line.tracks.insert(
+            main_index + 1,
+            AnnotatedTrack {
+                content_type: ContentType::Background,
+                content: bg_content,
+                translations: bg_translations,
+                romanizations: bg_romanizations,
+            },
+        );
+        extracted_count += 1;
+    }
+
+    if extracted_count > 0 {
+        debug!("[BackgroundVocalExtractor] 从 {extracted_count} 行中提取了背景人声。");
+    }
+}
+
+/// 拆分翻译或罗马音轨道中的括号部分，返回应归属于背景人声的轨道。
+///
+/// 没有括号的辅助轨道保持原样，只留在主歌词上。
+fn split_auxiliary_tracks(tracks: &mut [LyricTrack]) -> Vec<LyricTrack> {
+    let mut bg_tracks = Vec::new();
+    for track in tracks.iter_mut() {
+        if let Some((main_part, bg_part)) = split_track(track) {
+            *track = main_part;
+            bg_tracks.push(bg_part);
+        }
+    }
+    bg_tracks
+}
+
+/// 将一个轨道按括号拆分为主歌词部分和背景人声部分。
+///
+/// 在以下情况返回 `None`：
+/// - 轨道中没有括号内容；
+/// - 括号不配对；
+/// - 拆分后主歌词部分为空（整行都在括号内）。
+fn split_track(track: &LyricTrack) -> Option<(LyricTrack, LyricTrack)> {
+    if !track
+        .syllables()
+        .any(|s| s.text.contains(OPEN_BRACKETS) || s.text.contains(CLOSE_BRACKETS))
+    {
+        return None;
+    }
+
+    let is_timed = track.is_timed();
+    let mut depth: usize = 0;
+    let mut main_words = Vec::new();
+    let mut bg_words = Vec::new();
+
+    for word in &track.words {
+        let mut main_syllables = Vec::new();
+        let mut bg_syllables = Vec::new();
+
+        for syllable in &word.syllables {
+            let pieces = split_syllable_text(syllable, &mut depth)?;
+            for (piece, (start_ms, end_ms)) in distribute_timing(syllable, pieces, is_timed) {
+                let new_syllable = LyricSyllable {
+                    text: piece.text,
+                    start_ms,
+                    end_ms,
+                    duration_ms: None,
+                    ends_with_space: piece.ends_with_space,
+                };
+                if piece.is_background {
+                    bg_syllables.push(new_syllable);
+                } else {
+                    main_syllables.push(new_syllable);
+                }
+            }
+        }
+
+        // 只有整个词都留在同一侧时，振假名才仍然有意义
+        let is_split = !main_syllables.is_empty() && !bg_syllables.is_empty();
+        let furigana = if is_split {
+            None
+        } else {
+            word.furigana.clone()
+        };
+
+        if !main_syllables.is_empty() {
+            main_words.push(Word {
+                syllables: main_syllables,
+                furigana: furigana.clone(),
+            });
+        }
+        if !bg_syllables.is_empty() {
+            bg_words.push(Word {
+                syllables: bg_syllables,
+                furigana,
+            });
+        }
+    }
+
+    if depth != 0 || main_words.is_empty() || bg_words.is_empty() {
+        return None;
+    }
+
+    let mut main_track = LyricTrack {
+        words: main_words,
+        metadata: track.metadata.clone(),
+    };
+    let mut bg_track = LyricTrack {
+        words: bg_words,
+        metadata: track.metadata.clone(),
+    };
+    clear_trailing_space(&mut main_track);
+    clear_trailing_space(&mut bg_track);
+
+    Some((main_track, bg_track))
+}
+
+/// 按括号切分单个音节的文本。
+///
+/// `depth` 是跨音节共享的括号嵌套深度。遇到不配对的右括号时返回 `None`。
+fn split_syllable_text(syllable: &LyricSyllable, depth: &mut usize) -> Option<Vec<SyllablePiece>> {
+    let mut pieces = Vec::new();
+    let mut buffer = String::new();
+    let mut buffer_is_background = *depth > 0;
+
+    for ch in syllable.text.chars() {
+        if OPEN_BRACKETS.contains(&ch) {
+            if *depth == 0 {
+                flush_piece(&mut buffer, buffer_is_background, &mut pieces);
+                buffer_is_background = true;
+            }
+            *depth += 1;
+        } else if CLOSE_BRACKETS.contains(&ch) {
+            if *depth == 0 {
+                return None;
+            }
+            *depth -= 1;
+            if *depth == 0 {
+                flush_piece(&mut buffer, buffer_is_background, &mut pieces);
+                buffer_is_background = false;
+            }
+        } else {
+            buffer.push(ch);
+        }
+    }
+    flush_piece(&mut buffer, buffer_is_background, &mut pieces);
+
+    if syllable.ends_with_space
+        && let Some(last) = pieces.last_mut()
+    {
+        last.ends_with_space = true;
+    }
+
+    Some(pieces)
+}
+
+/// 将缓冲区中的文本作为一个片段写入 `pieces`，并清空缓冲区。
+///
+/// 首尾空白不会进入片段文本，而是转换为前一个片段或当前片段的 `ends_with_space`。
+fn flush_piece(buffer: &mut String, is_background: bool, pieces: &mut Vec<SyllablePiece>) {
+    let has_leading_space = buffer.starts_with(char::is_whitespace);
+    let has_trailing_space = buffer.ends_with(char::is_whitespace);
+    let text = buffer.trim();
+
+    if has_leading_space && let Some(last) = pieces.last_mut() {
+        last.ends_with_space = true;
+    }
+
+    if !text.is_empty() {
+        pieces.push(SyllablePiece {
+            text: text.to_string(),
+            is_background,
+            ends_with_space: has_trailing_space,
+            weight: text.chars().count(),
+        });
+    }
+    buffer.clear();
+}
+
+/// 为切分后的片段分配时间。
+///
+/// 逐字轨道按字符数比例切分原音节的时长；逐行轨道的每个片段都沿用原音节的时间。
+fn distribute_timing(
+    syllable: &LyricSyllable,
+    pieces: Vec<SyllablePiece>,
+    is_timed: bool,
+) -> Vec<(SyllablePiece, (u64, u64))> {
+    let piece_count = pieces.len();
+    let total_weight: u64 = pieces.iter().map(|p| p.weight as u64).sum();
+    let duration = syllable.duration();
+    let mut current_ms = syllable.start_ms;
+    let mut consumed_weight = 0;
+
+    pieces
+        .into_iter()
+        .enumerate()
+        .map(|(i, piece)| {
+            let timing = if !is_timed || piece_count == 1 || total_weight == 0 {
+                (syllable.start_ms, syllable.end_ms)
+            } else if i == piece_count - 1 {
+                (current_ms, syllable.end_ms)
+            } else {
+                consumed_weight += piece.weight as u64;
+                let end_ms = syllable.start_ms + duration * consumed_weight / total_weight;
+                let timing = (current_ms, end_ms);
+                current_ms = end_ms;
+                timing
+            };
+            (piece, timing)
+        })
+        .collect()
+}
+
+/// 确保轨道的最后一个音节没有尾随空格。
+fn clear_trailing_space(track: &mut LyricTrack) {
+    if let Some(last) = track.syllables_mut().last() {
+        last.ends_with_space = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use lyrics_helper_core::TrackMetadataKey;
+
+    fn syllable(text: &str, start_ms: u64, end_ms: u64, ends_with_space: bool) -> LyricSyllable {
+        LyricSyllable {
+            text: text.to_string(),
+            start_ms,
+            end_ms,
+            ends_with_space,
+            ..Default::default()
+        }
+    }
+
+    fn line_with_syllables(syllables: Vec<LyricSyllable>) -> LyricLine {
+        let start_ms = syllables.first().map_or(0, |s| s.start_ms);
+        let end_ms = syllables.last().map_or(0, |s| s.end_ms);
+        LyricLine {
+            tracks: vec![AnnotatedTrack {
+                content_type: ContentType::Main,
+                content: LyricTrack {
+                    words: vec![Word {
+                        syllables,
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            start_ms,
+            end_ms,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_extract_trailing_word_timed() {
+        let mut lines = vec![line_with_syllables(vec![
+            syllable("Hello", 0, 500, true),
+            syllable("(oh", 600, 800, true),
+            syllable("yeah)", 800, 1000, false),
+        ])];
+
+        extract_background_vocals(&mut lines);
+
+        let line = &lines[0];
+        assert_eq!(line.main_text().as_deref(), Some("Hello"));
+        assert_eq!(line.background_text().as_deref(), Some("oh yeah"));
+
+        let bg_syllables: Vec<_> = line
+            .background_track()
+            .unwrap()
+            .content
+            .syllables()
+            .collect();
+        assert_eq!(bg_syllables[0].start_ms, 600);
+        assert_eq!(bg_syllables[1].end_ms, 1000);
+        assert!(
+            !line
+                .main_track()
+                .unwrap()
+                .content
+                .syllables()
+                .last()
+                .unwrap()
+                .ends_with_space,
+            "主歌词末尾不应保留空格"
+        );
+    }
+
+    #[test]
+    fn test_extract_embedded_full_width_syllable_split() {
+        let mut lines = vec![line_with_syllables(vec![
+            syllable("我", 0, 100, false),
+            syllable("爱（你）", 100, 400, false),
+            syllable("啊", 400, 500, false),
+        ])];
+
+        extract_background_vocals(&mut lines);
+
+        let line = &lines[0];
+        assert_eq!(line.main_text().as_deref(), Some("我爱啊"));
+        assert_eq!(line.background_text().as_deref(), Some("你"));
+
+        let bg_syllable = line
+            .background_track()
+            .unwrap()
+            .content
+            .syllables()
+            .next()
+            .unwrap();
+        assert_eq!((bg_syllable.start_ms, bg_syllable.end_ms), (250, 400));
+    }
+
+    #[test]
+    fn test_extract_line_timed_with_translation() {
+        let mut line = LyricLine::new(1000, 5000);
+        line.add_content_track(ContentType::Main, "主歌词 (背景人声)");
+        line.add_translation(ContentType::Main, "main (backing)", Some("en"));
+        let mut lines = vec![line];
+
+        extract_background_vocals(&mut lines);
+
+        let line = &lines[0];
+        assert_eq!(line.main_text().as_deref(), Some("主歌词"));
+        assert_eq!(line.background_text().as_deref(), Some("背景人声"));
+
+        let bg_track = line.background_track().unwrap();
+        let bg_syllable = bg_track.content.syllables().next().unwrap();
+        assert_eq!((bg_syllable.start_ms, bg_syllable.end_ms), (1000, 5000));
+
+        assert_eq!(
+            line.main_track().unwrap().translations[0].text(),
+            "main".to_string()
+        );
+        assert_eq!(bg_track.translations.len(), 1);
+        assert_eq!(bg_track.translations[0].text(), "backing");
+        assert_eq!(
+            bg_track.translations[0]
+                .metadata
+                .get(&TrackMetadataKey::Language)
+                .map(String::as_str),
+            Some("en")
+        );
+    }
+
+    #[test]
+    fn test_skip_whole_line_and_unbalanced() {
+        let mut whole = LyricLine::new(0, 1000);
+        whole.add_content_track(ContentType::Main, "(全部都是背景)");
+        let mut unbalanced = LyricLine::new(1000, 2000);
+        unbalanced.add_content_track(ContentType::Main, "缺少右括号 (背景");
+        let mut lines = vec![whole, unbalanced];
+        let original = lines.clone();
+
+        extract_background_vocals(&mut lines);
+
+        assert_eq!(lines, original);
+    }
+}
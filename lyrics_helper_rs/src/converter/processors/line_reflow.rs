@@ -0,0 +1,741 @@
+//! 歌词行拆分与合并。
+//!
+//! 部分来源会把一整段歌词放在同一行中（例如某些 YRC 歌词），另一些来源则在短语中间断行。
+//! 该处理器会按最大字符数和最大时长，在标点、音节间隔或空格处拆分过长的行，
+//! 并把时长过短的相邻片段合并起来。拆分和合并时会同步处理翻译和罗马音轨道。
+
+use std::ops::Range;
+
+use lyrics_helper_core::{
+    AnnotatedTrack, ContentType, LineReflowOptions, LyricLine, LyricSyllable, LyricTrack, Word,
+};
+use tracing::debug;
+
+/// 可以作为拆分点的标点（拆分发生在标点之后）。
+const SPLIT_PUNCTUATION: [char; 16] = [
+    ',', '.', '!', '?', ';', ':', '~', '，', '。', '！', '？', '；', '：', '、', '…', '～',
+];
+
+/// 在逐行轨道的文本中，拆分点离目标位置的距离可以因为落在标点上而放宽的比例。
+const PUNCTUATION_PREFERENCE: f64 = 0.15;
+
+/// 拆分过长的歌词行，并合并过短的相邻行。
+pub fn reflow_lines(lines: &mut Vec<LyricLine>, options: &LineReflowOptions) {
+    let original_count = lines.len();
+
+    let split_lines: Vec<LyricLine> = lines
+        .drain(..)
+        .flat_map(|line| split_line(line, options))
+        .collect();
+    let split_count = split_lines.len();
+
+    *lines = merge_short_lines(split_lines, options);
+
+    debug!(
+        "[LineReflow] 行数变化: {original_count} -> {split_count}（拆分后）-> {}（合并后）",
+        lines.len()
+    );
+}
+
+/// 判断字符是否属于不使用空格分词的文字（中日韩文字及其标点）。
+const fn is_cjk(ch: char) -> bool {
+    matches!(ch,
+        '\u{3000}'..='\u{303F}'
+        | '\u{3040}'..='\u{30FF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{AC00}'..='\u{D7AF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{FF00}'..='\u{FFEF}'
+    )
+}
+
+/// 统计不含空白的字符数。
+fn visible_char_count(text: &str) -> usize {
+    text.chars().filter(|c| !c.is_whitespace()).count()
+}
+
+fn units_char_count(units: &[Word]) -> usize {
+    units
+        .iter()
+        .flat_map(|w| &w.syllables)
+        .map(|s| visible_char_count(&s.text))
+        .sum()
+}
+
+fn units_time_range(units: &[Word]) -> (u64, u64) {
+    let mut syllables = units.iter().flat_map(|w| &w.syllables);
+    let first = syllables.next().map_or((0, 0), |s| (s.start_ms, s.end_ms));
+    syllables.fold(first, |(start, end), s| {
+        (start.min(s.start_ms), end.max(s.end_ms))
+    })
+}
+
+// =============================================================================
+// 拆分
+// =============================================================================
+
+/// 把文本切分为可以在其间断开的片段。
+///
+/// 片段在空白、拆分标点之后以及每个中日韩字符处结束。
+/// 返回的每一项为 `(片段文本, 之后是否有空格)`。
+fn tokenize_text(text: &str) -> Vec<(String, bool)> {
+    let mut tokens: Vec<(String, bool)> = Vec::new();
+    let mut current = String::new();
+
+    let flush = |current: &mut String, tokens: &mut Vec<(String, bool)>| {
+        if !current.is_empty() {
+            tokens.push((std::mem::take(current), false));
+        }
+    };
+
+    for ch in text.chars() {
+        if ch.is_whitespace() {
+            flush(&mut current, &mut tokens);
+            if let Some(last) = tokens.last_mut() {
+                last.1 = true;
+            }
+        } else if SPLIT_PUNCTUATION.contains(&ch) {
+            // 标点始终跟随前一个片段
+            match tokens.last_mut() {
+                Some(last) if current.is_empty() && !last.1 => last.0.push(ch),
+                _ => current.push(ch),
+            }
+            flush(&mut current, &mut tokens);
+        } else if is_cjk(ch) {
+            flush(&mut current, &mut tokens);
+            current.push(ch);
+            flush(&mut current, &mut tokens);
+        } else {
+            current.push(ch);
+        }
+    }
+    flush(&mut current, &mut tokens);
+
+    if let Some(last) = tokens.last_mut() {
+        last.1 = false;
+    }
+    tokens
+}
+
+/// 把逐行轨道的文本切分为片段，并按字符数比例为每个片段分配时间。
+fn tokenize_untimed_track(track: &LyricTrack, start_ms: u64, end_ms: u64) -> Vec<Word> {
+    let tokens = tokenize_text(&track.text());
+    let total_weight: u64 = tokens
+        .iter()
+        .map(|(text, _)| visible_char_count(text) as u64)
+        .sum();
+    let duration = end_ms.saturating_sub(start_ms);
+
+    let mut consumed_weight = 0;
+    let mut current_ms = start_ms;
+    let token_count = tokens.len();
+    tokens
+        .into_iter()
+        .enumerate()
+        .map(|(i, (text, ends_with_space))| {
+            consumed_weight += visible_char_count(&text) as u64;
+            let token_end_ms = if i == token_count - 1 || total_weight == 0 {
+                end_ms
+            } else {
+                start_ms + duration * consumed_weight / total_weight
+            };
+            let syllable = LyricSyllable {
+                text,
+                start_ms: current_ms,
+                end_ms: token_end_ms,
+                ends_with_space,
+                ..Default::default()
+            };
+            current_ms = token_end_ms;
+            Word {
+                syllables: vec![syllable],
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+/// 评估在两个相邻词之间拆分的合适程度，数值越大越合适。
+///
+/// 返回 `None` 表示不允许在此处拆分（例如会把一个西文单词切开）。
+fn boundary_priority(prev: &Word, next: &Word, options: &LineReflowOptions) -> Option<u8> {
+    let last = prev.syllables.last()?;
+    let first = next.syllables.first()?;
+
+    if last.text.ends_with(SPLIT_PUNCTUATION) {
+        Some(3)
+    } else if options.boundary_gap_ms > 0
+        && first.start_ms.saturating_sub(last.end_ms) >= options.boundary_gap_ms
+    {
+        Some(2)
+    } else if last.ends_with_space {
+        Some(1)
+    } else if last.text.chars().last().is_some_and(is_cjk)
+        || first.text.chars().next().is_some_and(is_cjk)
+    {
+        Some(0)
+    } else {
+        None
+    }
+}
+
+fn fits_limits(units: &[Word], options: &LineReflowOptions) -> bool {
+    let (start_ms, end_ms) = units_time_range(units);
+    (options.max_chars == 0 || units_char_count(units) <= options.max_chars)
+        && (options.max_duration_ms == 0
+            || end_ms.saturating_sub(start_ms) <= options.max_duration_ms)
+}
+
+/// 为超出限制的范围选择最佳拆分点。
+///
+/// 优先选择合适程度最高的拆分点，其次选择两侧字符数最接近的位置。
+/// 如果存在不会留下过短片段（少于总字符数四分之一）的拆分点，则只在这些拆分点中选择。
+fn best_cut(units: &[Word], range: Range<usize>, options: &LineReflowOptions) -> Option<usize> {
+    let total_chars = units_char_count(&units[range.clone()]);
+
+    let candidates: Vec<(usize, u8, usize, usize)> = (range.start + 1..range.end)
+        .filter_map(|cut| {
+            let priority = boundary_priority(&units[cut - 1], &units[cut], options)?;
+            let left_chars = units_char_count(&units[range.start..cut]);
+            let right_chars = total_chars - left_chars;
+            Some((
+                cut,
+                priority,
+                left_chars.abs_diff(right_chars),
+                left_chars.min(right_chars),
+            ))
+        })
+        .collect();
+
+    let balanced: Vec<_> = candidates
+        .iter()
+        .filter(|(_, _, _, shorter)| shorter * 4 >= total_chars)
+        .collect();
+    let pool = if balanced.is_empty() {
+        candidates.iter().collect()
+    } else {
+        balanced
+    };
+
+    pool.into_iter()
+        .max_by_key(|(_, priority, imbalance, _)| (*priority, std::cmp::Reverse(*imbalance)))
+        .map(|(cut, ..)| *cut)
+}
+
+/// 递归地拆分范围，直到每一段都满足限制或无法再拆分。
+fn split_units(
+    units: &[Word],
+    range: Range<usize>,
+    options: &LineReflowOptions,
+) -> Vec<Range<usize>> {
+    if range.len() < 2 || fits_limits(&units[range.clone()], options) {
+        return vec![range];
+    }
+    let Some(cut) = best_cut(units, range.clone(), options) else {
+        return vec![range];
+    };
+
+    let mut result = split_units(units, range.start..cut, options);
+    result.extend(split_units(units, cut..range.end, options));
+    result
+}
+
+fn split_line(line: LyricLine, options: &LineReflowOptions) -> Vec<LyricLine> {
+    if options.max_chars == 0 && options.max_duration_ms == 0 {
+        return vec![line];
+    }
+    let Some(main_index) = line
+        .tracks
+        .iter()
+        .position(|t| t.content_type == ContentType::Main)
+    else {
+        return vec![line];
+    };
+
+    let main_content = &line.tracks[main_index].content;
+    let is_timed = main_content.is_timed();
+    let units = if is_timed {
+        main_content.words.clone()
+    } else {
+        tokenize_untimed_track(main_content, line.start_ms, line.end_ms)
+    };
+
+    let ranges = split_units(&units, 0..units.len(), options);
+    if ranges.len() < 2 {
+        return vec![line];
+    }
+
+    build_split_lines(&line, main_index, &units, &ranges, is_timed)
+}
+
+/// 根据拆分结果构建新的歌词行。
+fn build_split_lines(
+    line: &LyricLine,
+    main_index: usize,
+    units: &[Word],
+    ranges: &[Range<usize>],
+    is_timed: bool,
+) -> Vec<LyricLine> {
+    let piece_count = ranges.len();
+    let piece_times: Vec<(u64, u64)> = ranges
+        .iter()
+        .enumerate()
+        .map(|(i, range)| {
+            let (start_ms, end_ms) = units_time_range(&units[range.clone()]);
+            let start_ms = if i == 0 { line.start_ms } else { start_ms };
+            let end_ms = if i == piece_count - 1 {
+                line.end_ms.max(end_ms)
+            } else {
+                end_ms
+            };
+            (start_ms, end_ms)
+        })
+        .collect();
+
+    let main_track = &line.tracks[main_index];
+    let total_chars = units_char_count(units).max(1);
+    let mut cut_ratios = Vec::with_capacity(piece_count - 1);
+    let mut consumed_chars = 0;
+    for range in &ranges[..piece_count - 1] {
+        consumed_chars += units_char_count(&units[range.clone()]);
+        cut_ratios.push(consumed_chars as f64 / total_chars as f64);
+    }
+
+    let translations: Vec<Vec<Option<LyricTrack>>> = main_track
+        .translations
+        .iter()
+        .map(|t| split_auxiliary_track(t, &piece_times, &cut_ratios))
+        .collect();
+    let romanizations: Vec<Vec<Option<LyricTrack>>> = main_track
+        .romanizations
+        .iter()
+        .map(|t| split_auxiliary_track(t, &piece_times, &cut_ratios))
+        .collect();
+
+    // 其它内容轨道（如背景人声）整体归入时间重叠最多的那一行
+    let other_track_targets: Vec<usize> = line
+        .tracks
+        .iter()
+        .map(|track| {
+            let (start_ms, end_ms) = track
+                .content
+                .time_range()
+                .unwrap_or((line.start_ms, line.end_ms));
+            (0..piece_count)
+                .max_by_key(|&i| {
+                    let (piece_start, piece_end) = piece_times[i];
+                    (
+                        end_ms
+                            .min(piece_end)
+                            .saturating_sub(start_ms.max(piece_start)),
+                        std::cmp::Reverse(i),
+                    )
+                })
+                .unwrap_or(0)
+        })
+        .collect();
+
+    ranges
+        .iter()
+        .enumerate()
+        .map(|(piece_index, range)| {
+            let (start_ms, end_ms) = piece_times[piece_index];
+            let mut content = LyricTrack {
+                words: units[range.clone()].to_vec(),
+                metadata: main_track.content.metadata.clone(),
+            };
+            clear_trailing_space(&mut content);
+            if !is_timed {
+                content = line_timed_track(content.text(), start_ms, end_ms, &content);
+            }
+
+            let mut tracks = Vec::new();
+            for (track_index, track) in line.tracks.iter().enumerate() {
+                if track_index == main_index {
+                    tracks.push(AnnotatedTrack {
+                        content_type: track.content_type,
+                        content: content.clone(),
+                        translations: translations
+                            .iter()
+                            .filter_map(|pieces| pieces[piece_index].clone())
+                            .collect(),
+                        romanizations: romanizations
+                            .iter()
+                            .filter_map(|pieces| pieces[piece_index].clone())
+                            .collect(),
+                    });
+                } else if other_track_targets[track_index] == piece_index {
+                    tracks.push(track.clone());
+                }
+            }
+
+            LyricLine {
+                tracks,
+                start_ms,
+                end_ms,
+                agent: line.agent.clone(),
+                song_part: line.song_part.clone(),
+                itunes_key: if piece_index == 0 {
+                    line.itunes_key.clone()
+                } else {
+                    None
+                },
+            }
+        })
+        .collect()
+}
+
+/// 按拆分结果切分翻译或罗马音轨道。
+///
+/// 逐字轨道按每个词的开始时间归入对应的行；逐行轨道则按主歌词的字符比例切分文本，
+/// 并尽量在标点处断开。返回的列表与拆分出的行一一对应，`None` 表示该行没有对应内容。
+fn split_auxiliary_track(
+    track: &LyricTrack,
+    piece_times: &[(u64, u64)],
+    cut_ratios: &[f64],
+) -> Vec<Option<LyricTrack>> {
+    if track.is_timed() {
+        let mut pieces: Vec<Vec<Word>> = vec![Vec::new(); piece_times.len()];
+        for word in &track.words {
+            let word_start = word.syllables.first().map_or(0, |s| s.start_ms);
+            let index = piece_times
+                .iter()
+                .rposition(|(start, _)| *start <= word_start)
+                .unwrap_or(0);
+            pieces[index].push(word.clone());
+        }
+        return pieces
+            .into_iter()
+            .map(|words| {
+                (!words.is_empty()).then(|| {
+                    let mut piece = LyricTrack {
+                        words,
+                        metadata: track.metadata.clone(),
+                    };
+                    clear_trailing_space(&mut piece);
+                    piece
+                })
+            })
+            .collect();
+    }
+
+    let tokens = tokenize_text(&track.text());
+    let cuts = choose_text_cuts(&tokens, cut_ratios);
+
+    let mut boundaries = vec![0];
+    boundaries.extend(cuts);
+    boundaries.push(tokens.len());
+
+    boundaries
+        .windows(2)
+        .zip(piece_times)
+        .map(|(bounds, (start_ms, end_ms))| {
+            let text = join_tokens(&tokens[bounds[0]..bounds[1]]);
+            (!text.is_empty()).then(|| line_timed_track(text, *start_ms, *end_ms, track))
+        })
+        .collect()
+}
+
+/// 为逐行文本选择与主歌词比例最接近的拆分位置，落在标点后的位置会被优先选择。
+fn choose_text_cuts(tokens: &[(String, bool)], cut_ratios: &[f64]) -> Vec<usize> {
+    let mut prefix = vec![0usize];
+    for (text, _) in tokens {
+        prefix.push(prefix.last().copied().unwrap_or(0) + visible_char_count(text));
+    }
+    let total = prefix.last().copied().unwrap_or(0).max(1) as f64;
+
+    let mut cuts = Vec::with_capacity(cut_ratios.len());
+    let mut previous_cut = 0;
+    for ratio in cut_ratios {
+        let best = (previous_cut..=tokens.len()).min_by(|&a, &b| {
+            let score = |cut: usize| {
+                let distance = (prefix[cut] as f64 / total - ratio).abs();
+                let ends_with_punctuation =
+                    cut > 0 && tokens[cut - 1].0.ends_with(SPLIT_PUNCTUATION);
+                if ends_with_punctuation {
+                    distance - PUNCTUATION_PREFERENCE
+                } else {
+                    distance
+                }
+            };
+            score(a).total_cmp(&score(b))
+        });
+        previous_cut = best.unwrap_or(previous_cut);
+        cuts.push(previous_cut);
+    }
+    cuts
+}
+
+fn join_tokens(tokens: &[(String, bool)]) -> String {
+    let mut text = String::new();
+    for (token, ends_with_space) in tokens {
+        text.push_str(token);
+        if *ends_with_space {
+            text.push(' ');
+        }
+    }
+    text.trim_end().to_string()
+}
+
+/// 构建只有一个音节的逐行轨道，沿用 `template` 的元数据。
+fn line_timed_track(text: String, start_ms: u64, end_ms: u64, template: &LyricTrack) -> LyricTrack {
+    LyricTrack {
+        words: vec![Word {
+            syllables: vec![LyricSyllable {
+                text,
+                start_ms,
+                end_ms,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+        metadata: template.metadata.clone(),
+    }
+}
+
+/// 确保轨道的最后一个音节没有尾随空格。
+fn clear_trailing_space(track: &mut LyricTrack) {
+    if let Some(last) = track.syllables_mut().last() {
+        last.ends_with_space = false;
+    }
+}
+
+// =============================================================================
+// 合并
+// =============================================================================
+
+fn merge_short_lines(lines: Vec<LyricLine>, options: &LineReflowOptions) -> Vec<LyricLine> {
+    if options.min_duration_ms == 0 {
+        return lines;
+    }
+
+    let is_short =
+        |line: &LyricLine| line.end_ms.saturating_sub(line.start_ms) < options.min_duration_ms;
+
+    let mut result: Vec<LyricLine> = Vec::with_capacity(lines.len());
+    for line in lines {
+        if let Some(prev) = result.last_mut()
+            && (is_short(prev) || is_short(&line))
+            && can_merge(prev, &line, options)
+        {
+            merge_into(prev, line);
+        } else {
+            result.push(line);
+        }
+    }
+    result
+}
+
+/// 两行只有在演唱者、段落和轨道结构都一致，且合并后不超出限制时才能合并。
+fn can_merge(prev: &LyricLine, next: &LyricLine, options: &LineReflowOptions) -> bool {
+    let same_structure = prev.tracks.len() == next.tracks.len()
+        && prev.tracks.iter().zip(&next.tracks).all(|(a, b)| {
+            a.content_type == b.content_type
+                && a.translations.len() == b.translations.len()
+                && a.romanizations.len() == b.romanizations.len()
+        });
+    if !same_structure || prev.agent != next.agent || prev.song_part != next.song_part {
+        return false;
+    }
+
+    let gap = next.start_ms.saturating_sub(prev.end_ms);
+    let merged_chars = visible_char_count(&prev.main_text().unwrap_or_default())
+        + visible_char_count(&next.main_text().unwrap_or_default());
+    let merged_duration = next.end_ms.max(prev.end_ms).saturating_sub(prev.start_ms);
+
+    gap <= options.max_merge_gap_ms
+        && (options.max_chars == 0 || merged_chars <= options.max_chars)
+        && (options.max_duration_ms == 0 || merged_duration <= options.max_duration_ms)
+}
+
+fn merge_into(prev: &mut LyricLine, next: LyricLine) {
+    for (target, source) in prev.tracks.iter_mut().zip(next.tracks) {
+        merge_track(&mut target.content, source.content);
+        for (t, s) in target.translations.iter_mut().zip(source.translations) {
+            merge_track(t, s);
+        }
+        for (t, s) in target.romanizations.iter_mut().zip(source.romanizations) {
+            merge_track(t, s);
+        }
+    }
+    prev.end_ms = prev.end_ms.max(next.end_ms);
+}
+
+/// 把 `source` 追加到 `target` 之后。
+///
+/// 两个轨道都是逐行轨道时合并为单个音节，以免被误判为逐字轨道。
+fn merge_track(target: &mut LyricTrack, source: LyricTrack) {
+    let target_text = target.text();
+    let source_text = source.text();
+    let needs_space = target_text.chars().last().is_some_and(|c| !is_cjk(c))
+        && source_text.chars().next().is_some_and(|c| !is_cjk(c));
+
+    if target.is_timed() || source.is_timed() {
+        if needs_space && let Some(last) = target.syllables_mut().last() {
+            last.ends_with_space = true;
+        }
+        target.words.extend(source.words);
+        return;
+    }
+
+    let (start_ms, end_ms) = match (target.time_range(), source.time_range()) {
+        (Some((a_start, a_end)), Some((b_start, b_end))) => {
+            (a_start.min(b_start), a_end.max(b_end))
+        }
+        (Some(range), None) | (None, Some(range)) => range,
+        (None, None) => (0, 0),
+    };
+    let separator = if needs_space { " " } else { "" };
+    let text = format!("{target_text}{separator}{source_text}");
+    *target = line_timed_track(text, start_ms, end_ms, target);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timed_line(words: &[(&str, u64, u64, bool)]) -> LyricLine {
+        let syllables: Vec<Word> = words
+            .iter()
+            .map(|(text, start_ms, end_ms, ends_with_space)| Word {
+                syllables: vec![LyricSyllable {
+                    text: (*text).to_string(),
+                    start_ms: *start_ms,
+                    end_ms: *end_ms,
+                    ends_with_space: *ends_with_space,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            })
+            .collect();
+        let (start_ms, end_ms) = units_time_range(&syllables);
+        LyricLine {
+            tracks: vec![AnnotatedTrack {
+                content_type: ContentType::Main,
+                content: LyricTrack {
+                    words: syllables,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            start_ms,
+            end_ms,
+            ..Default::default()
+        }
+    }
+
+    fn main_texts(lines: &[LyricLine]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|l| l.main_text().unwrap_or_default())
+            .collect()
+    }
+
+    #[test]
+    fn test_split_word_timed_line_at_gap() {
+        let mut line = timed_line(&[
+            ("我", 0, 300, false),
+            ("们", 300, 600, false),
+            ("一", 600, 900, false),
+            ("起", 900, 1200, false),
+            ("走", 2000, 2300, false),
+            ("过", 2300, 2600, false),
+            ("漫", 2600, 2900, false),
+            ("长", 2900, 3200, false),
+        ]);
+        line.add_translation(
+            ContentType::Main,
+            "We walk together, through the long",
+            None,
+        );
+        let mut lines = vec![line];
+
+        let options = LineReflowOptions {
+            max_chars: 6,
+            min_duration_ms: 0,
+            ..Default::default()
+        };
+        reflow_lines(&mut lines, &options);
+
+        assert_eq!(main_texts(&lines), vec!["我们一起", "走过漫长"]);
+        assert_eq!((lines[0].start_ms, lines[0].end_ms), (0, 1200));
+        assert_eq!((lines[1].start_ms, lines[1].end_ms), (2000, 3200));
+
+        let translation = |line: &LyricLine| line.main_track().unwrap().translations[0].text();
+        assert_eq!(translation(&lines[0]), "We walk together,");
+        assert_eq!(translation(&lines[1]), "through the long");
+    }
+
+    #[test]
+    fn test_split_line_timed_line_at_punctuation() {
+        let mut line = LyricLine::new(10_000, 20_000);
+        line.add_content_track(
+            ContentType::Main,
+            "Hello darkness my old friend, I've come to talk with you again",
+        );
+        line.add_translation(
+            ContentType::Main,
+            "你好黑暗我的老朋友，我又来和你交谈了",
+            None,
+        );
+        let mut lines = vec![line];
+
+        let options = LineReflowOptions {
+            max_chars: 30,
+            ..Default::default()
+        };
+        reflow_lines(&mut lines, &options);
+
+        assert_eq!(
+            main_texts(&lines),
+            vec![
+                "Hello darkness my old friend,",
+                "I've come to talk with you again"
+            ]
+        );
+        assert_eq!(lines[0].start_ms, 10_000);
+        assert_eq!(lines[0].end_ms, lines[1].start_ms);
+        assert_eq!(lines[1].end_ms, 20_000);
+        assert!(!lines[0].main_track().unwrap().content.is_timed());
+
+        let translation = |line: &LyricLine| line.main_track().unwrap().translations[0].text();
+        assert_eq!(translation(&lines[0]), "你好黑暗我的老朋友，");
+        assert_eq!(translation(&lines[1]), "我又来和你交谈了");
+    }
+
+    #[test]
+    fn test_does_not_split_inside_latin_word() {
+        let mut lines = vec![timed_line(&[
+            ("beau", 0, 3000, false),
+            ("ti", 3000, 6000, false),
+            ("ful", 6000, 12_000, false),
+        ])];
+        reflow_lines(&mut lines, &LineReflowOptions::default());
+        assert_eq!(main_texts(&lines), vec!["beautiful"]);
+    }
+
+    #[test]
+    fn test_merge_short_fragments() {
+        let line = |text: &str, start_ms: u64, end_ms: u64| {
+            let mut line = LyricLine::new(start_ms, end_ms);
+            line.add_content_track(ContentType::Main, text);
+            line.add_translation(ContentType::Main, text.to_uppercase(), None);
+            line
+        };
+        let mut lines = vec![
+            line("Oh", 0, 400),
+            line("baby", 450, 900),
+            line("I love you", 1000, 3000),
+            line("Yeah", 8000, 8500),
+        ];
+
+        reflow_lines(&mut lines, &LineReflowOptions::default());
+
+        assert_eq!(main_texts(&lines), vec!["Oh baby I love you", "Yeah"]);
+        assert_eq!((lines[0].start_ms, lines[0].end_ms), (0, 3000));
+        let merged = &lines[0].main_track().unwrap();
+        assert!(!merged.content.is_timed());
+        assert_eq!(merged.translations[0].text(), "OH BABY I LOVE YOU");
+    }
+}
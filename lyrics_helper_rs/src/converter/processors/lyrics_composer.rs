@@ -0,0 +1,311 @@
+//! 多来源歌词合成器。
+//!
+//! 不同提供商的歌词各有所长：有的逐字计时最准，有的社区翻译最好，有的带有演唱者和背景人声。
+//! 合成器从一个来源取主歌词，再按行的时间和文本把其它来源的翻译、罗马音对齐并合并进来，
+//! 每条轨道都会在 `TrackMetadataKey::Custom("source")` 中记录其来源。
+
+use lyrics_helper_core::{
+    AnnotatedTrack, AuxiliaryLineMatchingStrategy, ContentType, LyricLine, LyricTrack,
+    LyricsCompositionOptions, ParsedSourceData, TrackMetadataKey,
+};
+use tracing::debug;
+
+use crate::converter::merge_tracks;
+
+/// 记录轨道来源的元数据键。
+pub const SOURCE_METADATA_KEY: &str = "source";
+
+/// 读取轨道的来源提供商。
+#[must_use]
+pub fn track_source(track: &LyricTrack) -> Option<&str> {
+    track
+        .metadata
+        .get(&TrackMetadataKey::Custom(SOURCE_METADATA_KEY.to_string()))
+        .map(String::as_str)
+}
+
+/// 将多个来源的歌词合成为一份。
+///
+/// 每个来源以 `ParsedSourceData::source_name` 标识。主歌词、翻译和罗马音分别取自
+/// 各自偏好列表中排名最高、且确实包含该轨道的来源。
+///
+/// # 返回
+/// 合成后的歌词；所有来源都没有主歌词时返回 `None`。
+#[must_use]
+pub fn compose_lyrics(
+    sources: &[ParsedSourceData],
+    options: &LyricsCompositionOptions,
+) -> Option<ParsedSourceData> {
+    let main_index = pick_source(sources, &options.main_preference, |source| {
+        source.lines.iter().any(|line| line.main_track().is_some())
+    })?;
+    let translation_index = pick_source(sources, &options.translation_preference, |source| {
+        has_annotations(source, |track| &track.translations)
+    });
+    let romanization_index = pick_source(sources, &options.romanization_preference, |source| {
+        has_annotations(source, |track| &track.romanizations)
+    });
+    debug!(
+        "[LyricsComposer] 主歌词来自 {:?}，翻译来自 {:?}，罗马音来自 {:?}",
+        sources[main_index].source_name,
+        translation_index.map(|i| &sources[i].source_name),
+        romanization_index.map(|i| &sources[i].source_name),
+    );
+
+    let mut composed = sources[main_index].clone();
+    let source_name = composed.source_name.clone();
+    let keep_translations = translation_index.is_none_or(|i| i == main_index);
+    let keep_romanizations = romanization_index.is_none_or(|i| i == main_index);
+
+    for track in composed.lines.iter_mut().flat_map(|line| &mut line.tracks) {
+        tag_source(&mut track.content, &source_name);
+        if track.content_type != ContentType::Main {
+            continue;
+        }
+        if keep_translations {
+            track
+                .translations
+                .iter_mut()
+                .for_each(|t| tag_source(t, &source_name));
+        } else {
+            track.translations.clear();
+        }
+        if keep_romanizations {
+            track
+                .romanizations
+                .iter_mut()
+                .for_each(|t| tag_source(t, &source_name));
+        } else {
+            track.romanizations.clear();
+        }
+    }
+
+    let auxiliary =
+        |index: Option<usize>, keep: bool, select: fn(&AnnotatedTrack) -> &Vec<LyricTrack>| {
+            index
+                .filter(|_| !keep)
+                .map(|i| {
+                    let lines = auxiliary_lines(&sources[i], &composed.lines, select, options);
+                    vec![(lines, ParsedSourceData::default(), None)]
+                })
+                .unwrap_or_default()
+        };
+    let translations = auxiliary(translation_index, keep_translations, |t| &t.translations);
+    let romanizations = auxiliary(romanization_index, keep_romanizations, |t| &t.romanizations);
+
+    merge_tracks(
+        &mut composed.lines,
+        &translations,
+        &romanizations,
+        sorted_sync(options.matching_strategy),
+    );
+
+    Some(composed)
+}
+
+/// 按偏好列表挑选包含所需轨道的来源。
+fn pick_source(
+    sources: &[ParsedSourceData],
+    preference: &[String],
+    has_track: impl Fn(&ParsedSourceData) -> bool,
+) -> Option<usize> {
+    let rank = |source: &ParsedSourceData| {
+        preference
+            .iter()
+            .position(|name| *name == source.source_name)
+            .unwrap_or(preference.len())
+    };
+    sources
+        .iter()
+        .enumerate()
+        .filter(|(_, source)| has_track(source))
+        .min_by_key(|(index, source)| (rank(source), *index))
+        .map(|(index, _)| index)
+}
+
+fn has_annotations(
+    source: &ParsedSourceData,
+    select: impl Fn(&AnnotatedTrack) -> &Vec<LyricTrack>,
+) -> bool {
+    source
+        .lines
+        .iter()
+        .filter_map(LyricLine::main_track)
+        .any(|track| select(track).iter().any(|t| !t.text().trim().is_empty()))
+}
+
+/// 把来源中每个主歌词行的翻译或罗马音拆成独立的辅助行，并尽量对齐到主歌词的时间。
+///
+/// 对齐时按顺序在主歌词中寻找文本相同、且时间差不超过 `text_alignment_window_ms` 的行，
+/// 找到后使用该行的时间；找不到时保留原时间，交由 [`merge_tracks`] 按容差匹配。
+fn auxiliary_lines(
+    source: &ParsedSourceData,
+    main_lines: &[LyricLine],
+    select: fn(&AnnotatedTrack) -> &Vec<LyricTrack>,
+    options: &LyricsCompositionOptions,
+) -> Vec<LyricLine> {
+    let main_texts: Vec<String> = main_lines
+        .iter()
+        .map(|line| normalize_for_alignment(&line.main_text().unwrap_or_default()))
+        .collect();
+    let mut cursor = 0;
+    let mut aux_lines = Vec::new();
+
+    for line in &source.lines {
+        let Some(track) = line.main_track() else {
+            continue;
+        };
+        let annotations = select(track);
+        if annotations.is_empty() {
+            continue;
+        }
+
+        let text = normalize_for_alignment(&track.content.text());
+        let aligned = (!text.is_empty())
+            .then(|| {
+                main_lines[cursor..]
+                    .iter()
+                    .zip(&main_texts[cursor..])
+                    .take_while(|(main, _)| {
+                        main.start_ms <= line.start_ms + options.text_alignment_window_ms
+                    })
+                    .position(|(main, main_text)| {
+                        *main_text == text
+                            && main.start_ms.abs_diff(line.start_ms)
+                                <= options.text_alignment_window_ms
+                    })
+            })
+            .flatten()
+            .map(|offset| {
+                let index = cursor + offset;
+                cursor = index + 1;
+                &main_lines[index]
+            });
+        let (start_ms, end_ms) = aligned.map_or((line.start_ms, line.end_ms), |main| {
+            (main.start_ms, main.end_ms)
+        });
+
+        for annotation in annotations {
+            let mut content = annotation.clone();
+            tag_source(&mut content, &source.source_name);
+            aux_lines.push(LyricLine {
+                tracks: vec![AnnotatedTrack {
+                    content,
+                    ..Default::default()
+                }],
+                start_ms,
+                end_ms,
+                ..Default::default()
+            });
+        }
+    }
+    aux_lines
+}
+
+fn tag_source(track: &mut LyricTrack, source_name: &str) {
+    track
+        .metadata
+        .entry(TrackMetadataKey::Custom(SOURCE_METADATA_KEY.to_string()))
+        .or_insert_with(|| source_name.to_string());
+}
+
+/// [`merge_tracks`] 只支持 `SortedSync`，其它策略按相同容差转换。
+const fn sorted_sync(strategy: AuxiliaryLineMatchingStrategy) -> AuxiliaryLineMatchingStrategy {
+    match strategy {
+        AuxiliaryLineMatchingStrategy::Exact => {
+            AuxiliaryLineMatchingStrategy::SortedSync { tolerance_ms: 0 }
+        }
+        AuxiliaryLineMatchingStrategy::Tolerance { tolerance_ms }
+        | AuxiliaryLineMatchingStrategy::SortedSync { tolerance_ms } => {
+            AuxiliaryLineMatchingStrategy::SortedSync { tolerance_ms }
+        }
+    }
+}
+
+fn normalize_for_alignment(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use lyrics_helper_core::{LyricSyllable, Word};
+
+    use super::*;
+
+    fn line(start_ms: u64, text: &str, translation: Option<&str>) -> LyricLine {
+        let mut track = AnnotatedTrack {
+            content: LyricTrack {
+                words: vec![Word {
+                    syllables: vec![LyricSyllable {
+                        text: text.to_string(),
+                        start_ms,
+                        end_ms: start_ms + 2000,
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        if let Some(translation) = translation {
+            track.add_translation(translation, "zh-Hans");
+        }
+        LyricLine {
+            tracks: vec![track],
+            start_ms,
+            end_ms: start_ms + 2000,
+            ..Default::default()
+        }
+    }
+
+    fn source(name: &str, lines: Vec<LyricLine>) -> ParsedSourceData {
+        ParsedSourceData {
+            lines,
+            source_name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_compose_main_and_translation_from_different_sources() {
+        let qq = source(
+            "qq",
+            vec![
+                line(1000, "Hello", Some("QQ 的翻译")),
+                line(4000, "World", None),
+            ],
+        );
+        // 网易云的时间轴整体晚了 500ms，需要按文本对齐
+        let netease = source(
+            "netease",
+            vec![
+                line(1500, "hello", Some("你好")),
+                line(4500, "world!", Some("世界")),
+            ],
+        );
+        let options = LyricsCompositionOptions {
+            main_preference: vec!["qq".to_string()],
+            translation_preference: vec!["netease".to_string()],
+            ..Default::default()
+        };
+
+        let composed = compose_lyrics(&[netease, qq], &options).unwrap();
+
+        assert_eq!(composed.source_name, "qq");
+        assert_eq!(composed.lines.len(), 2);
+        for (line, expected) in composed.lines.iter().zip(["你好", "世界"]) {
+            let track = line.main_track().unwrap();
+            assert_eq!(track_source(&track.content), Some("qq"));
+            assert_eq!(track.translations.len(), 1);
+            assert_eq!(track.translations[0].text(), expected);
+            assert_eq!(track_source(&track.translations[0]), Some("netease"));
+        }
+        assert_eq!(composed.lines[0].end_ms, 3000);
+
+        assert!(compose_lyrics(&[], &options).is_none());
+    }
+}
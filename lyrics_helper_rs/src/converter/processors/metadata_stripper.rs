@@ -14,6 +14,9 @@
 //! 出品：...
 //! 真正的歌词行 1
 //! 真正的歌词行 2
+//!
+//! 启用 `MetadataStripperFlags::EXTRACT_CREDITS` 后，被移除的制作人员行
+//! （如“作词：X”、“Arranged by: Y”）会被解析为结构化元数据，而不是直接丢弃。
 
 use std::borrow::Cow;
 
@@ -21,7 +24,7 @@ use regex::{Regex, RegexBuilder};
 use tracing::{debug, trace, warn};
 
 use crate::converter::LyricLine;
-use lyrics_helper_core::{MetadataStripperFlags, MetadataStripperOptions};
+use lyrics_helper_core::{CanonicalMetadataKey, MetadataStripperFlags, MetadataStripperOptions};
 
 mod default_rules {
     use std::sync::OnceLock;
@@ -53,6 +56,90 @@ mod default_rules {
     }
 }
 
+/// 制作人员标签（小写）到规范化元数据键的映射。
+const CREDIT_LABELS: &[(&str, CanonicalMetadataKey)] = &[
+    ("作词", CanonicalMetadataKey::Lyricist),
+    ("作詞", CanonicalMetadataKey::Lyricist),
+    ("词", CanonicalMetadataKey::Lyricist),
+    ("詞", CanonicalMetadataKey::Lyricist),
+    ("填词", CanonicalMetadataKey::Lyricist),
+    ("lyrics", CanonicalMetadataKey::Lyricist),
+    ("lyrics by", CanonicalMetadataKey::Lyricist),
+    ("lyricist", CanonicalMetadataKey::Lyricist),
+    ("作曲", CanonicalMetadataKey::Composer),
+    ("曲", CanonicalMetadataKey::Composer),
+    ("composer", CanonicalMetadataKey::Composer),
+    ("composed by", CanonicalMetadataKey::Composer),
+    ("music by", CanonicalMetadataKey::Composer),
+    ("词曲", CanonicalMetadataKey::Songwriter),
+    ("作词作曲", CanonicalMetadataKey::Songwriter),
+    ("written by", CanonicalMetadataKey::Songwriter),
+    ("words and music by", CanonicalMetadataKey::Songwriter),
+    ("编曲", CanonicalMetadataKey::Arranger),
+    ("編曲", CanonicalMetadataKey::Arranger),
+    ("arranger", CanonicalMetadataKey::Arranger),
+    ("arranged by", CanonicalMetadataKey::Arranger),
+    ("arrangement", CanonicalMetadataKey::Arranger),
+    ("制作人", CanonicalMetadataKey::Producer),
+    ("producer", CanonicalMetadataKey::Producer),
+    ("produced by", CanonicalMetadataKey::Producer),
+    ("record producer", CanonicalMetadataKey::Producer),
+    ("music producer", CanonicalMetadataKey::Producer),
+    ("混音", CanonicalMetadataKey::MixingEngineer),
+    ("混音师", CanonicalMetadataKey::MixingEngineer),
+    ("混音工程师", CanonicalMetadataKey::MixingEngineer),
+    ("mixing engineer", CanonicalMetadataKey::MixingEngineer),
+    ("mixed by", CanonicalMetadataKey::MixingEngineer),
+    ("母带", CanonicalMetadataKey::MasteringEngineer),
+    ("母带工程师", CanonicalMetadataKey::MasteringEngineer),
+    ("母带处理工程师", CanonicalMetadataKey::MasteringEngineer),
+    (
+        "mastering engineer",
+        CanonicalMetadataKey::MasteringEngineer,
+    ),
+    ("mastered by", CanonicalMetadataKey::MasteringEngineer),
+];
+
+fn lookup_credit_label(label: &str) -> Option<CanonicalMetadataKey> {
+    let label = label.trim();
+    CREDIT_LABELS
+        .iter()
+        .find(|(name, _)| *name == label)
+        .map(|(_, key)| key.clone())
+}
+
+/// 将标签解析为制作人员元数据键。
+///
+/// 除了完整匹配外，还会分别尝试中英双语标签（如“编曲Arranged By”）的中文和英文部分。
+fn credit_key_for_label(label: &str) -> Option<CanonicalMetadataKey> {
+    let label = label.trim().to_lowercase();
+    if let Some(key) = lookup_credit_label(&label) {
+        return Some(key);
+    }
+
+    let ascii_start = label.find(|c: char| c.is_ascii_alphabetic())?;
+    lookup_credit_label(&label[..ascii_start])
+        .or_else(|| lookup_credit_label(&label[ascii_start..]))
+}
+
+/// 尝试将一行文本解析为制作人员信息，一行可能包含多个以分隔符分开的名字。
+fn parse_credit_line(line_text: &str) -> Vec<(CanonicalMetadataKey, String)> {
+    let text = clean_text_for_check(line_text);
+    let Some((label, value)) = text.split_once([':', '：']) else {
+        return Vec::new();
+    };
+    let Some(key) = credit_key_for_label(label) else {
+        return Vec::new();
+    };
+
+    value
+        .split(['/', '、', ',', '，'])
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| (key.clone(), name.to_string()))
+        .collect()
+}
+
 fn get_text(line: &LyricLine) -> String {
     line.main_text().unwrap_or_default()
 }
@@ -229,9 +316,23 @@ pub fn strip_descriptive_metadata_lines(
     lines: &mut Vec<LyricLine>,
     options: &MetadataStripperOptions,
 ) {
+    let _ = strip_and_extract_credits(lines, options);
+}
+
+/// 从 `LyricLine` 列表中移除元数据行，并返回从被移除的行中解析出的制作人员信息。
+///
+/// 只有在设置了 `MetadataStripperFlags::EXTRACT_CREDITS` 时才会解析制作人员信息，
+/// 否则返回空列表。返回的元数据按原始行顺序排列。
+#[must_use]
+pub fn strip_and_extract_credits(
+    lines: &mut Vec<LyricLine>,
+    options: &MetadataStripperOptions,
+) -> Vec<(CanonicalMetadataKey, String)> {
+    let mut credits = Vec::new();
+
     if !options.flags.contains(MetadataStripperFlags::ENABLED) {
         trace!("[MetadataStripper] 功能被禁用，跳过处理。");
-        return;
+        return credits;
     }
 
     let options_to_use: Cow<MetadataStripperOptions> =
@@ -247,7 +348,7 @@ pub fn strip_descriptive_metadata_lines(
     let rules = StrippingRules::new(&options_to_use);
 
     if lines.is_empty() || !rules.has_rules() {
-        return;
+        return credits;
     }
 
     let original_count = lines.len();
@@ -260,10 +361,24 @@ pub fn strip_descriptive_metadata_lines(
     let last_lyric_exclusive_index =
         find_last_lyric_line_exclusive_index(lines, first_lyric_index, &rules, footer_limit);
 
+    let extract_credits = options
+        .flags
+        .contains(MetadataStripperFlags::EXTRACT_CREDITS);
+    let mut collect_credits = |removed: &[LyricLine]| {
+        if extract_credits {
+            for line in removed {
+                credits.extend(parse_credit_line(&get_text(line)));
+            }
+        }
+    };
+
     if first_lyric_index < last_lyric_exclusive_index {
+        collect_credits(&lines[..first_lyric_index]);
+        collect_credits(&lines[last_lyric_exclusive_index..]);
         lines.drain(last_lyric_exclusive_index..);
         lines.drain(..first_lyric_index);
     } else if first_lyric_index > 0 || last_lyric_exclusive_index < original_count {
+        collect_credits(lines);
         lines.clear();
     }
 
@@ -274,6 +389,15 @@ pub fn strip_descriptive_metadata_lines(
             lines.len()
         );
     }
+
+    if !credits.is_empty() {
+        debug!(
+            "[MetadataStripper] 提取了 {} 条制作人员信息。",
+            credits.len()
+        );
+    }
+
+    credits
 }
 
 #[cfg(test)]
@@ -479,6 +603,60 @@ mod tests {
 
         assert_eq!(lines_to_texts(&lines), vec!["123", "Artist: B"]);
     }
+    #[test]
+    fn test_extract_credits_from_stripped_lines() {
+        let texts = vec![
+            "作词：A/B",
+            "作曲：C",
+            "编曲Arranged By：D",
+            "Mixed by: E",
+            "出品：F",
+            "Lyric 1",
+            "Lyric 2",
+        ];
+        let mut lines = create_test_lines(&texts);
+        let options = MetadataStripperOptions {
+            flags: MetadataStripperFlags::ENABLED | MetadataStripperFlags::EXTRACT_CREDITS,
+            keywords: vec![
+                "作词".to_string(),
+                "作曲".to_string(),
+                "编曲Arranged By".to_string(),
+                "Mixed by".to_string(),
+                "出品".to_string(),
+            ],
+            ..Default::default()
+        };
+
+        let credits = strip_and_extract_credits(&mut lines, &options);
+
+        assert_eq!(lines_to_texts(&lines), vec!["Lyric 1", "Lyric 2"]);
+        assert_eq!(
+            credits,
+            vec![
+                (CanonicalMetadataKey::Lyricist, "A".to_string()),
+                (CanonicalMetadataKey::Lyricist, "B".to_string()),
+                (CanonicalMetadataKey::Composer, "C".to_string()),
+                (CanonicalMetadataKey::Arranger, "D".to_string()),
+                (CanonicalMetadataKey::MixingEngineer, "E".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_credits_not_extracted_without_flag() {
+        let mut lines = create_test_lines(&["作词：A", "Lyric 1"]);
+        let options = MetadataStripperOptions {
+            flags: MetadataStripperFlags::ENABLED,
+            keywords: vec!["作词".to_string()],
+            ..Default::default()
+        };
+
+        let credits = strip_and_extract_credits(&mut lines, &options);
+
+        assert_eq!(lines_to_texts(&lines), vec!["Lyric 1"]);
+        assert!(credits.is_empty());
+    }
+
     #[test]
     fn test_default_config_parsing() {
         let keywords = default_rules::keywords();
@@ -1,7 +1,11 @@
 //! 歌词处理器模块
 
 pub mod agent_recognizer;
+pub mod background_vocal_extractor;
 pub mod batch_processor;
 pub mod chinese_conversion_processor;
+pub mod line_reflow;
+pub mod lyrics_composer;
 pub mod metadata_stripper;
+pub mod song_structure;
 pub mod syllable_smoothing;
@@ -0,0 +1,352 @@
+//! 歌曲结构识别器。
+//!
+//! 根据歌词行的文本重复情况和行间的时间间隔，推断每一行所属的歌曲段落
+//! （主歌、预副歌、副歌、桥段、尾声），并写入 `LyricLine::song_part`。
+//! TTML 生成器会据此输出带 `itunes:song-part` 的 `<div>`。
+
+use std::ops::Range;
+
+use lyrics_helper_core::{LyricLine, SongStructureOptions};
+use tracing::debug;
+
+/// 主歌。
+pub const SONG_PART_VERSE: &str = "Verse";
+/// 预副歌。
+pub const SONG_PART_PRE_CHORUS: &str = "PreChorus";
+/// 副歌。
+pub const SONG_PART_CHORUS: &str = "Chorus";
+/// 桥段。
+pub const SONG_PART_BRIDGE: &str = "Bridge";
+/// 尾声。
+pub const SONG_PART_OUTRO: &str = "Outro";
+
+/// 一个连续的歌词段落。
+struct Section {
+    range: Range<usize>,
+    /// 段落中是否以重复行为主。
+    repeated: bool,
+    /// 所属的段落分组，内容相似的段落属于同一分组。
+    group: usize,
+}
+
+/// 识别歌曲结构，并为每一行设置 `song_part`。
+///
+/// 如果任意一行已经带有 `song_part` 且未开启 `overwrite_existing`，则不做任何修改。
+/// 如果无法识别出副歌，同样保持原样，以免写入没有意义的标记。
+pub fn detect_song_structure(lines: &mut [LyricLine], options: &SongStructureOptions) {
+    if lines.is_empty() {
+        return;
+    }
+    if !options.overwrite_existing && lines.iter().any(|l| l.song_part.is_some()) {
+        debug!("[SongStructure] 歌词已包含段落标记，跳过识别。");
+        return;
+    }
+
+    let texts: Vec<String> = lines
+        .iter()
+        .map(|l| normalize_for_comparison(&l.main_text().unwrap_or_default()))
+        .collect();
+    let is_similar = |a: usize, b: usize| {
+        !texts[a].is_empty()
+            && !texts[b].is_empty()
+            && strsim::normalized_levenshtein(&texts[a], &texts[b])
+                >= options.line_similarity_threshold
+    };
+
+    let repeated: Vec<bool> = (0..lines.len())
+        .map(|i| (0..lines.len()).any(|j| j != i && is_similar(i, j)))
+        .collect();
+
+    let mut sections = split_sections(lines, &repeated, options);
+    assign_groups(
+        &mut sections,
+        &is_similar,
+        options.section_similarity_threshold,
+    );
+
+    let Some(labels) = label_sections(&sections) else {
+        debug!("[SongStructure] 未能识别出副歌，跳过。");
+        return;
+    };
+
+    for (section, label) in sections.iter().zip(labels) {
+        for line in &mut lines[section.range.clone()] {
+            line.song_part = Some(label.to_string());
+        }
+    }
+    debug!("[SongStructure] 共识别出 {} 个段落。", sections.len());
+}
+
+/// 只保留字母和数字并转为小写，忽略标点、空白和大小写的差异。
+fn normalize_for_comparison(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// 按时间间隔和“重复/非重复”的变化把歌词切分为段落，并合并过短的段落。
+fn split_sections(
+    lines: &[LyricLine],
+    repeated: &[bool],
+    options: &SongStructureOptions,
+) -> Vec<Section> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    let mut start = 0;
+    for i in 1..lines.len() {
+        let gap = lines[i].start_ms.saturating_sub(lines[i - 1].end_ms);
+        if gap >= options.section_gap_ms || repeated[i] != repeated[i - 1] {
+            ranges.push(start..i);
+            start = i;
+        }
+    }
+    ranges.push(start..lines.len());
+
+    let mut merged: Vec<Range<usize>> = Vec::new();
+    for range in ranges {
+        match merged.last_mut() {
+            Some(prev) if range.len() < options.min_section_lines => prev.end = range.end,
+            _ => merged.push(range),
+        }
+    }
+
+    merged
+        .into_iter()
+        .map(|range| {
+            let repeated_count = repeated[range.clone()].iter().filter(|r| **r).count();
+            Section {
+                repeated: repeated_count * 2 > range.len(),
+                range,
+                group: 0,
+            }
+        })
+        .collect()
+}
+
+/// 把内容相似的重复段落归入同一分组，其余段落各自成组。
+fn assign_groups(
+    sections: &mut [Section],
+    is_similar: &impl Fn(usize, usize) -> bool,
+    threshold: f64,
+) {
+    let mut representatives: Vec<Range<usize>> = Vec::new();
+    for section in sections.iter_mut() {
+        let existing = section
+            .repeated
+            .then(|| {
+                representatives.iter().position(|rep| {
+                    section_similarity(rep, &section.range, is_similar) >= threshold
+                })
+            })
+            .flatten();
+
+        section.group = existing.unwrap_or_else(|| {
+            representatives.push(section.range.clone());
+            representatives.len() - 1
+        });
+    }
+}
+
+/// 计算较短段落中能在另一段落找到相似行的比例。
+fn section_similarity(
+    a: &Range<usize>,
+    b: &Range<usize>,
+    is_similar: &impl Fn(usize, usize) -> bool,
+) -> f64 {
+    let (shorter, longer) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    if shorter.is_empty() {
+        return 0.0;
+    }
+    let matched = shorter
+        .clone()
+        .filter(|&i| longer.clone().any(|j| is_similar(i, j)))
+        .count();
+    matched as f64 / shorter.len() as f64
+}
+
+/// 为每个段落确定标签。没有副歌时返回 `None`。
+fn label_sections(sections: &[Section]) -> Option<Vec<&'static str>> {
+    let group_count = sections.iter().map(|s| s.group).max()? + 1;
+    let mut occurrences = vec![0usize; group_count];
+    let mut total_lines = vec![0usize; group_count];
+    for section in sections {
+        occurrences[section.group] += 1;
+        total_lines[section.group] += section.range.len();
+    }
+
+    // 每次出现都紧接在同一个重复分组之前的分组是引子性质的段落（如预副歌），
+    // 不应被选为副歌
+    let repeated_groups: Vec<usize> = (0..group_count).filter(|&g| occurrences[g] >= 2).collect();
+    let always_precedes = |g: usize, target: usize| {
+        sections.iter().enumerate().all(|(i, s)| {
+            s.group != g || sections.get(i + 1).is_some_and(|next| next.group == target)
+        })
+    };
+    let is_lead_in = |g: usize| {
+        repeated_groups
+            .iter()
+            .any(|&target| target != g && always_precedes(g, target))
+    };
+
+    let largest = |candidates: Vec<usize>| {
+        candidates
+            .into_iter()
+            .max_by_key(|&g| (total_lines[g], std::cmp::Reverse(g)))
+    };
+    let chorus_group = largest(
+        repeated_groups
+            .iter()
+            .copied()
+            .filter(|&g| !is_lead_in(g))
+            .collect(),
+    )
+    .or_else(|| largest(repeated_groups.clone()))?;
+
+    let pre_chorus_groups: Vec<usize> = repeated_groups
+        .iter()
+        .copied()
+        .filter(|&g| g != chorus_group && always_precedes(g, chorus_group))
+        .collect();
+
+    let chorus_indices: Vec<usize> = sections
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| s.group == chorus_group)
+        .map(|(i, _)| i)
+        .collect();
+
+    let labels = sections
+        .iter()
+        .enumerate()
+        .map(|(i, section)| {
+            if section.group == chorus_group {
+                return SONG_PART_CHORUS;
+            }
+            if pre_chorus_groups.contains(&section.group) {
+                return SONG_PART_PRE_CHORUS;
+            }
+            if occurrences[section.group] >= 2 {
+                return SONG_PART_VERSE;
+            }
+            let choruses_before = chorus_indices.iter().filter(|&&c| c < i).count();
+            let choruses_after = chorus_indices.len() - choruses_before;
+            match (choruses_before, choruses_after) {
+                (1.., 0) => SONG_PART_OUTRO,
+                (2.., 1..) => SONG_PART_BRIDGE,
+                _ => SONG_PART_VERSE,
+            }
+        })
+        .collect();
+
+    Some(labels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lyrics_helper_core::ContentType;
+
+    /// 以空字符串表示一段较长的间奏。
+    fn build_lines(texts: &[&str]) -> Vec<LyricLine> {
+        let mut time = 0;
+        let mut lines = Vec::new();
+        for text in texts {
+            if text.is_empty() {
+                time += 5000;
+                continue;
+            }
+            let mut line = LyricLine::new(time, time + 2000);
+            line.add_content_track(ContentType::Main, *text);
+            lines.push(line);
+            time += 2000;
+        }
+        lines
+    }
+
+    fn parts(lines: &[LyricLine]) -> Vec<&str> {
+        lines
+            .iter()
+            .map(|l| l.song_part.as_deref().unwrap_or("-"))
+            .collect()
+    }
+
+    #[test]
+    fn test_detects_verse_chorus_bridge_outro() {
+        let mut lines = build_lines(&[
+            "Walking down the empty road",
+            "Nothing left for me to hold",
+            "",
+            "We are the light tonight",
+            "Shining ever so bright",
+            "",
+            "Morning comes and I am gone",
+            "Every story has its song",
+            "",
+            "We are the light tonight",
+            "Shining ever so bright",
+            "",
+            "Falling through a silent sky",
+            "Never asking reasons why",
+            "",
+            "We are the light tonight",
+            "Shining ever so bright!",
+            "",
+            "Goodbye my friend",
+            "This is the end",
+        ]);
+
+        detect_song_structure(&mut lines, &SongStructureOptions::default());
+
+        assert_eq!(
+            parts(&lines),
+            vec![
+                "Verse", "Verse", "Chorus", "Chorus", "Verse", "Verse", "Chorus", "Chorus",
+                "Bridge", "Bridge", "Chorus", "Chorus", "Outro", "Outro",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detects_pre_chorus() {
+        let mut lines = build_lines(&[
+            "First verse line one",
+            "First verse line two",
+            "",
+            "Here it comes again",
+            "Hold on to the end",
+            "",
+            "Sing it loud sing it clear",
+            "Everybody is here",
+            "",
+            "Second verse goes like this",
+            "Something that we miss",
+            "",
+            "Here it comes again",
+            "Hold on to the end",
+            "",
+            "Sing it loud sing it clear",
+            "Everybody is here",
+        ]);
+
+        detect_song_structure(&mut lines, &SongStructureOptions::default());
+
+        assert_eq!(
+            &parts(&lines)[2..6],
+            ["PreChorus", "PreChorus", "Chorus", "Chorus"]
+        );
+        assert_eq!(&parts(&lines)[6..8], ["Verse", "Verse"]);
+    }
+
+    #[test]
+    fn test_keeps_existing_parts_and_skips_without_chorus() {
+        let mut lines = build_lines(&["One line", "Another line", "Yet another"]);
+        detect_song_structure(&mut lines, &SongStructureOptions::default());
+        assert!(lines.iter().all(|l| l.song_part.is_none()));
+
+        let mut lines = build_lines(&["La la la", "Hey", "", "La la la", "Hey"]);
+        lines[0].song_part = Some("Intro".to_string());
+        detect_song_structure(&mut lines, &SongStructureOptions::default());
+        assert_eq!(lines[0].song_part.as_deref(), Some("Intro"));
+        assert!(lines[1].song_part.is_none());
+    }
+}
@@ -0,0 +1,484 @@
+//! 批量下载模块。
+//!
+//! 分页获取专辑、歌手或歌单中的所有歌曲，以有限的并发获取歌词，转换为目标格式后按文件名模板写入。
+//! 已存在的文件会被跳过，因此中断的任务重新运行即可从中断处继续。每首歌曲的结果都记录在
+//! [`CollectionDownloadReport`] 中，并以 JSON 格式写入输出目录下的 [`REPORT_FILE_NAME`]。
+//! 报告在每首歌曲完成后都会更新，尚未处理的歌曲记为 [`SongDownloadStatus::Cancelled`]。
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use futures::{StreamExt, stream};
+use lyrics_helper_core::{
+    CanonicalMetadataKey, CollectionDownloadOptions, CollectionKind, CollectionReference,
+    ParsedSourceData, model::generic,
+};
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    LyricsHelper, SearchPolicy,
+    error::{LyricsHelperError, Result},
+    fetch_lyrics,
+    providers::{self, Provider},
+};
+
+/// 下载报告的文件名，位于输出目录下。
+pub const REPORT_FILE_NAME: &str = "download_report.json";
+
+/// 单首歌曲的下载结果。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", content = "detail", rename_all = "kebab-case")]
+pub enum SongDownloadStatus {
+    /// 歌词已下载并写入文件。
+    Downloaded,
+    /// 文件已存在，未重新下载。
+    Skipped,
+    /// 该歌曲没有歌词。
+    NoLyrics,
+    /// 获取、转换或写入失败，附带错误信息。
+    Failed(String),
+    /// 任务被取消，该歌曲未被处理。
+    Cancelled,
+}
+
+/// 单首歌曲的下载报告。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SongDownloadReport {
+    /// 歌曲在集合中的序号，从 1 开始。
+    pub index: usize,
+    /// 歌曲在提供商平台的 ID。
+    pub song_id: String,
+    /// 歌曲名。
+    pub title: String,
+    /// 演唱者。
+    pub artists: Vec<String>,
+    /// 歌词文件名，相对于输出目录。
+    pub file_name: String,
+    /// 下载结果。
+    pub status: SongDownloadStatus,
+}
+
+/// 一次批量下载的报告。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectionDownloadReport {
+    /// 下载的集合。
+    pub collection: CollectionReference,
+    /// 使用的提供商名称。
+    pub provider: String,
+    /// 每首歌曲的结果，按在集合中的顺序排列。
+    pub songs: Vec<SongDownloadReport>,
+}
+
+impl CollectionDownloadReport {
+    /// 所有歌曲是否都已处理完毕，即没有失败或被取消的歌曲。
+    ///
+    /// 不完整时可以用相同的选项重新运行任务，已写入的文件会被跳过。
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.songs.iter().all(|song| {
+            !matches!(
+                song.status,
+                SongDownloadStatus::Failed(_) | SongDownloadStatus::Cancelled
+            )
+        })
+    }
+
+    /// 返回指定结果的歌曲数量。
+    #[must_use]
+    pub fn count(&self, predicate: impl Fn(&SongDownloadStatus) -> bool) -> usize {
+        self.songs
+            .iter()
+            .filter(|song| predicate(&song.status))
+            .count()
+    }
+}
+
+/// 下载集合中所有歌曲的歌词。
+pub(crate) async fn download_collection(
+    provider: Arc<dyn Provider + Send + Sync>,
+    collection: CollectionReference,
+    options: CollectionDownloadOptions,
+    policy: SearchPolicy,
+    cancellation_token: Option<CancellationToken>,
+) -> Result<CollectionDownloadReport> {
+    let songs = list_songs(provider.as_ref(), &collection, &options).await?;
+    tracing::info!(
+        "[Download] {:?} 共有 {} 首歌曲，开始下载到 {}",
+        collection,
+        songs.len(),
+        options.output_dir.display()
+    );
+    let output_dir = options.output_dir.clone();
+    tokio::task::spawn_blocking(move || fs::create_dir_all(output_dir))
+        .await
+        .map_err(|e| LyricsHelperError::Internal(format!("创建输出目录失败: {e}")))??;
+
+    let file_names = assign_file_names(&songs, provider.name(), &options);
+    let mut report = CollectionDownloadReport {
+        collection,
+        provider: provider.name().to_string(),
+        songs: songs
+            .iter()
+            .zip(&file_names)
+            .enumerate()
+            .map(|(i, (song, file_name))| SongDownloadReport {
+                index: i + 1,
+                song_id: song_id(song).to_string(),
+                title: song.name.clone(),
+                artists: song.artists.iter().map(|a| a.name.clone()).collect(),
+                file_name: file_name.clone(),
+                status: SongDownloadStatus::Cancelled,
+            })
+            .collect(),
+    };
+    write_report(&options.output_dir, &report).await?;
+
+    let options = Arc::new(options);
+    let policy = Arc::new(policy);
+    let jobs = songs
+        .into_iter()
+        .zip(file_names)
+        .enumerate()
+        .map(|(i, (song, file_name))| {
+            let provider = provider.clone();
+            let options = options.clone();
+            let policy = policy.clone();
+            let token = cancellation_token.clone();
+            async move {
+                let status = download_song(
+                    &provider,
+                    &song,
+                    &file_name,
+                    &options,
+                    &policy,
+                    token.as_ref(),
+                )
+                .await;
+                (i, status)
+            }
+        });
+    let mut statuses = stream::iter(jobs).buffer_unordered(options.concurrency.max(1));
+    while let Some((i, status)) = statuses.next().await {
+        report.songs[i].status = status;
+        // 每完成一首就更新报告，进程中途退出时也能知道哪些歌曲已处理
+        write_report(&options.output_dir, &report).await?;
+    }
+    Ok(report)
+}
+
+/// 分页获取集合中的所有歌曲。
+async fn list_songs(
+    provider: &(dyn Provider + Send + Sync),
+    collection: &CollectionReference,
+    options: &CollectionDownloadOptions,
+) -> Result<Vec<generic::Song>> {
+    let limit = options.max_songs.unwrap_or(usize::MAX);
+    let mut songs = match collection.kind {
+        CollectionKind::Playlist => provider
+            .get_playlist(&collection.id)
+            .await?
+            .songs
+            .unwrap_or_default(),
+        CollectionKind::Album | CollectionKind::Artist => {
+            let page_size = options.page_size.max(1);
+            let mut songs = Vec::new();
+            let mut seen = HashSet::new();
+            for page in 1.. {
+                let batch = if collection.kind == CollectionKind::Album {
+                    provider
+                        .get_album_songs(&collection.id, page, page_size)
+                        .await?
+                } else {
+                    provider
+                        .get_singer_songs(&collection.id, page, page_size)
+                        .await?
+                };
+                let batch_len = batch.len();
+                let before = songs.len();
+                songs.extend(
+                    batch
+                        .into_iter()
+                        .filter(|song| seen.insert(song_id(song).to_string())),
+                );
+                // 不支持分页的接口会重复返回同一批歌曲
+                if batch_len < page_size as usize || songs.len() == before || songs.len() >= limit {
+                    break;
+                }
+            }
+            songs
+        }
+    };
+    songs.truncate(limit);
+    Ok(songs)
+}
+
+async fn download_song(
+    provider: &Arc<dyn Provider + Send + Sync>,
+    song: &generic::Song,
+    file_name: &str,
+    options: &CollectionDownloadOptions,
+    policy: &SearchPolicy,
+    cancellation_token: Option<&CancellationToken>,
+) -> SongDownloadStatus {
+    let path = options.output_dir.join(file_name);
+    if !options.overwrite {
+        let path = path.clone();
+        if tokio::task::spawn_blocking(move || path.exists())
+            .await
+            .unwrap_or(false)
+        {
+            return SongDownloadStatus::Skipped;
+        }
+    }
+    if cancellation_token.is_some_and(CancellationToken::is_cancelled) {
+        return SongDownloadStatus::Cancelled;
+    }
+
+    let result = async {
+        let lyrics = fetch_lyrics(provider, song_id(song), policy, cancellation_token).await?;
+        let mut parsed = lyrics.parsed;
+        providers::record_source_ids(
+            &mut parsed,
+            provider.name(),
+            song_id(song),
+            song.album_id.as_deref(),
+        );
+        fill_missing_metadata(&mut parsed, song);
+        let converted = LyricsHelper::generate_lyrics_from_parsed(
+            parsed,
+            options.target_format,
+            options.conversion.clone(),
+            None::<HashMap<String, Vec<String>>>,
+        )
+        .await?;
+        write_atomically(path, converted.output_lyrics).await
+    }
+    .await;
+
+    match result {
+        Ok(()) => SongDownloadStatus::Downloaded,
+        Err(LyricsHelperError::LyricNotFound) => SongDownloadStatus::NoLyrics,
+        Err(LyricsHelperError::Cancelled) => SongDownloadStatus::Cancelled,
+        Err(e) => {
+            tracing::warn!("[Download] 下载 '{}' 的歌词失败: {}", song.name, e);
+            SongDownloadStatus::Failed(e.to_string())
+        }
+    }
+}
+
+/// 歌词中缺少标题、艺术家或专辑时，使用歌曲列表中的信息补全。
+fn fill_missing_metadata(parsed: &mut ParsedSourceData, song: &generic::Song) {
+    let artists: Vec<String> = song.artists.iter().map(|a| a.name.clone()).collect();
+    let candidates = [
+        (CanonicalMetadataKey::Title, vec![song.name.clone()]),
+        (CanonicalMetadataKey::Artist, artists),
+        (
+            CanonicalMetadataKey::Album,
+            song.album.clone().into_iter().collect(),
+        ),
+    ];
+    for (key, values) in candidates {
+        let values: Vec<String> = values.into_iter().filter(|v| !v.is_empty()).collect();
+        let present = parsed
+            .raw_metadata
+            .keys()
+            .any(|raw_key| raw_key.parse::<CanonicalMetadataKey>().ok().as_ref() == Some(&key));
+        if !present && !values.is_empty() {
+            parsed.raw_metadata.insert(key.to_string(), values);
+        }
+    }
+}
+
+/// 把报告写入输出目录下的 [`REPORT_FILE_NAME`]。
+async fn write_report(output_dir: &Path, report: &CollectionDownloadReport) -> Result<()> {
+    let content = serde_json::to_string_pretty(report)?;
+    write_atomically(output_dir.join(REPORT_FILE_NAME), content).await
+}
+
+/// 先写入临时文件再重命名，避免中断时留下不完整的文件被当作已下载。
+///
+/// 文件在阻塞线程池中写入，不会阻塞异步运行时。
+async fn write_atomically(path: PathBuf, content: String) -> Result<()> {
+    tokio::task::spawn_blocking(move || {
+        let temp_path = path.with_extension("part");
+        fs::write(&temp_path, content)?;
+        fs::rename(&temp_path, &path)?;
+        Ok(())
+    })
+    .await
+    .map_err(|e| LyricsHelperError::Internal(format!("写入文件失败: {e}")))?
+}
+
+fn song_id(song: &generic::Song) -> &str {
+    if song.provider_id.is_empty() {
+        &song.id
+    } else {
+        &song.provider_id
+    }
+}
+
+/// 按模板为每首歌曲生成文件名，重名时在末尾附加歌曲 ID。
+fn assign_file_names(
+    songs: &[generic::Song],
+    provider_name: &str,
+    options: &CollectionDownloadOptions,
+) -> Vec<String> {
+    let width = songs.len().to_string().len().max(2);
+    let extension = options.target_format.to_extension_str();
+    let mut used = HashSet::new();
+
+    songs
+        .iter()
+        .enumerate()
+        .map(|(i, song)| {
+            let artist_names: Vec<&str> = song.artists.iter().map(|a| a.name.as_str()).collect();
+            let stem = options
+                .file_name_template
+                .replace("{index}", &format!("{:0width$}", i + 1))
+                .replace("{title}", &song.name)
+                .replace("{artists}", &artist_names.join(", "))
+                .replace("{album}", song.album.as_deref().unwrap_or_default())
+                .replace("{id}", song_id(song))
+                .replace("{provider}", provider_name);
+            let mut stem = sanitize_file_name(&stem);
+            if stem.is_empty() {
+                stem = sanitize_file_name(song_id(song));
+            }
+
+            let mut file_name = format!("{stem}.{extension}");
+            if !used.insert(file_name.to_lowercase()) {
+                file_name = format!("{stem} ({}).{extension}", sanitize_file_name(song_id(song)));
+                used.insert(file_name.to_lowercase());
+            }
+            file_name
+        })
+        .collect()
+}
+
+/// 替换文件名中不允许的字符，并去掉首尾的空格和点。
+fn sanitize_file_name(name: &str) -> String {
+    let replaced: String = name
+        .chars()
+        .map(|c| {
+            if c.is_control() || matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    replaced
+        .trim_matches(|c: char| c.is_whitespace() || c == '.')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use lyrics_helper_core::{Artist, FullLyricsResult, LyricFormat, LyricLine, ReferencePlatform};
+
+    use super::*;
+    use crate::providers::mock::MockProvider;
+
+    struct TempDir(PathBuf);
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn album_song(n: usize) -> generic::Song {
+        generic::Song {
+            id: format!("song{n}"),
+            provider_id: format!("song{n}"),
+            name: format!("Song/{n}"),
+            artists: vec![Artist {
+                id: String::new(),
+                name: "Artist".to_string(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    /// 一个有三首歌曲的专辑，第二首没有歌词。
+    fn album_provider() -> MockProvider {
+        MockProvider::new("mock")
+            .with_album_songs((1..=3).map(album_song).collect())
+            .with_lyrics(|song_id| {
+                if song_id == "song2" {
+                    return Err(LyricsHelperError::LyricNotFound);
+                }
+                let mut line = LyricLine::new(1000, 2000);
+                line.add_content_track(lyrics_helper_core::ContentType::Main, song_id);
+                Ok(FullLyricsResult {
+                    parsed: ParsedSourceData {
+                        lines: vec![line],
+                        is_line_timed_source: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+            })
+    }
+
+    #[tokio::test]
+    async fn test_download_album_and_resume() {
+        let temp = TempDir(std::env::temp_dir().join(format!("download-{}", uuid::Uuid::new_v4())));
+        let options = CollectionDownloadOptions {
+            output_dir: temp.0.clone(),
+            target_format: LyricFormat::Lrc,
+            page_size: 2,
+            ..Default::default()
+        };
+        let collection = CollectionReference {
+            platform: ReferencePlatform::Netease,
+            kind: CollectionKind::Album,
+            id: "1".to_string(),
+        };
+        let provider: Arc<dyn Provider + Send + Sync> = Arc::new(album_provider());
+
+        let report = download_collection(
+            provider.clone(),
+            collection.clone(),
+            options.clone(),
+            SearchPolicy::default(),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let statuses: Vec<_> = report.songs.iter().map(|s| s.status.clone()).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                SongDownloadStatus::Downloaded,
+                SongDownloadStatus::NoLyrics,
+                SongDownloadStatus::Downloaded,
+            ]
+        );
+        assert_eq!(report.songs[0].file_name, "01 - Artist - Song_1.lrc");
+        let content = fs::read_to_string(temp.0.join("03 - Artist - Song_3.lrc")).unwrap();
+        assert!(content.contains("song3"));
+        assert!(content.contains("[ti:Song/3]"));
+        assert!(report.is_complete());
+        let saved: CollectionDownloadReport =
+            serde_json::from_str(&fs::read_to_string(temp.0.join(REPORT_FILE_NAME)).unwrap())
+                .unwrap();
+        assert_eq!(saved, report);
+
+        // 再次运行时已下载的文件会被跳过
+        let resumed =
+            download_collection(provider, collection, options, SearchPolicy::default(), None)
+                .await
+                .unwrap();
+        assert_eq!(
+            resumed.count(|status| *status == SongDownloadStatus::Skipped),
+            2
+        );
+    }
+}
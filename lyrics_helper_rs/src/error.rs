@@ -79,6 +79,14 @@ pub enum LyricsHelperError {
     #[error("API 请求被限流: {0}")]
     RateLimited(String),
 
+    /// 操作超过了设定的时限
+    #[error("操作超时: {0}")]
+    Timeout(String),
+
+    /// 无法识别的歌曲链接或 ID
+    #[error("无法识别的歌曲引用: '{0}'")]
+    InvalidReference(String),
+
     /// 未初始化提供商功能
     #[error("提供商尚未初始化。请先调用 `load_providers()` 方法。")]
     ProvidersNotInitialized,
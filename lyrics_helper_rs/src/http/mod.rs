@@ -7,8 +7,12 @@ use serde::de::DeserializeOwned;
 
 use crate::error::{LyricsHelperError, Result};
 
+pub mod replay;
+pub mod retry;
 pub mod wreq_client;
 
+pub use self::replay::{MatchRules, RecordingHttpClient, ReplayHttpClient};
+pub use self::retry::{RateLimit, RetryHttpClient, RetryPolicy};
 pub use self::wreq_client::WreqClient;
 
 /// HTTP请求方法枚举
@@ -26,6 +30,20 @@ pub enum HttpMethod {
     Patch,
 }
 
+impl HttpMethod {
+    /// 返回大写的方法名，例如 `GET`。
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Get => "GET",
+            Self::Post => "POST",
+            Self::Put => "PUT",
+            Self::Delete => "DELETE",
+            Self::Patch => "PATCH",
+        }
+    }
+}
+
 /// 统一的HTTP响应数据结构
 #[derive(Debug, Clone)]
 pub struct HttpResponse {
@@ -0,0 +1,754 @@
+//! 录制与回放 HTTP 交互，用于在没有网络的环境中确定性地测试提供商。
+//!
+//! 先用 `RecordingHttpClient` 包装真实的客户端跑一遍，把交互保存为夹具文件：
+//!
+//! ```rust,ignore
+//! let recorder = Arc::new(RecordingHttpClient::new(Arc::new(WreqClient::new()?)));
+//! let qq = QQMusic::with_http_client(recorder.clone()).await?;
+//! qq.get_full_lyrics("002mZevo3wHvsc").await?;
+//! recorder.save("tests/test_data/fixtures/qq/get_lyrics.json")?;
+//! ```
+//!
+//! 之后用 `ReplayHttpClient` 读取夹具，离线回放：
+//!
+//! ```rust,ignore
+//! let replay = ReplayHttpClient::from_file("tests/test_data/fixtures/qq/get_lyrics.json")?
+//!     .with_match_rules(MatchRules::default().ignore_query_param("_"));
+//! let qq = QQMusic::with_http_client(Arc::new(replay)).await?;
+//! ```
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use base64::{Engine, engine::general_purpose::STANDARD};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{
+    error::{LyricsHelperError, Result},
+    http::{HttpClient, HttpMethod, HttpResponse},
+};
+
+/// 夹具中保存的请求体或响应体。能按 UTF-8 解码的内容以文本保存，便于阅读和修改。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FixtureBody {
+    /// UTF-8 文本。
+    Text(String),
+    /// Base64 编码的二进制数据。
+    Base64(String),
+}
+
+impl FixtureBody {
+    /// 根据字节内容选择合适的保存形式。
+    #[must_use]
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        std::str::from_utf8(bytes).map_or_else(
+            |_| Self::Base64(STANDARD.encode(bytes)),
+            |text| Self::Text(text.to_string()),
+        )
+    }
+
+    /// 还原为原始字节。
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        match self {
+            Self::Text(text) => Ok(text.as_bytes().to_vec()),
+            Self::Base64(encoded) => Ok(STANDARD.decode(encoded)?),
+        }
+    }
+}
+
+/// 一次完整的请求与响应。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpExchange {
+    /// 请求方法，例如 `GET`。
+    pub method: String,
+    /// 完整的请求 URL，包含查询参数。
+    pub url: String,
+    /// 请求头。
+    #[serde(default)]
+    pub request_headers: Vec<(String, String)>,
+    /// 请求体。
+    #[serde(default)]
+    pub request_body: Option<FixtureBody>,
+    /// 响应状态码。
+    pub status: u16,
+    /// 响应头。
+    #[serde(default)]
+    pub response_headers: Vec<(String, String)>,
+    /// 响应体。
+    pub response_body: FixtureBody,
+}
+
+impl HttpExchange {
+    /// 构造回放用的响应。
+    pub fn to_response(&self) -> Result<HttpResponse> {
+        Ok(HttpResponse {
+            status: self.status,
+            headers: self.response_headers.clone(),
+            body: self.response_body.to_bytes()?,
+        })
+    }
+}
+
+/// 夹具文件的内容。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HttpFixture {
+    /// 按发生顺序排列的交互。
+    #[serde(default)]
+    pub exchanges: Vec<HttpExchange>,
+    /// 录制结束时导出的 Cookies，格式与 `HttpClient::get_cookies` 相同。
+    #[serde(default)]
+    pub cookies: Option<String>,
+}
+
+impl HttpFixture {
+    /// 从 JSON 文件中读取夹具。
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// 将夹具保存为 JSON 文件，会自动创建父目录。
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// 回放时判断请求是否与夹具中的交互相同的规则。
+#[derive(Debug, Clone)]
+pub struct MatchRules {
+    /// 是否要求请求方法一致。
+    pub match_method: bool,
+    /// 是否要求请求体一致。
+    pub match_body: bool,
+    /// 比较 URL 时忽略的查询参数，例如时间戳和签名。
+    pub ignored_query_params: Vec<String>,
+    /// 比较 JSON 请求体时忽略的字段（任意层级），例如随机生成的搜索 ID。
+    pub ignored_body_fields: Vec<String>,
+    /// 需要一致的请求头（名称不区分大小写）。默认不比较任何请求头。
+    pub matched_headers: Vec<String>,
+}
+
+impl Default for MatchRules {
+    fn default() -> Self {
+        Self {
+            match_method: true,
+            match_body: true,
+            ignored_query_params: Vec::new(),
+            ignored_body_fields: Vec::new(),
+            matched_headers: Vec::new(),
+        }
+    }
+}
+
+impl MatchRules {
+    /// 比较 URL 时忽略指定的查询参数。
+    #[must_use]
+    pub fn ignore_query_param(mut self, name: impl Into<String>) -> Self {
+        self.ignored_query_params.push(name.into());
+        self
+    }
+
+    /// 比较 JSON 请求体时忽略指定的字段。
+    #[must_use]
+    pub fn ignore_body_field(mut self, name: impl Into<String>) -> Self {
+        self.ignored_body_fields.push(name.into());
+        self
+    }
+
+    /// 要求指定的请求头一致。
+    #[must_use]
+    pub fn match_header(mut self, name: impl Into<String>) -> Self {
+        self.matched_headers.push(name.into());
+        self
+    }
+
+    /// 设置是否比较请求体。请求体中含有随机数或时间戳时可以关闭。
+    #[must_use]
+    pub const fn with_body_matching(mut self, enabled: bool) -> Self {
+        self.match_body = enabled;
+        self
+    }
+
+    /// 去掉被忽略的查询参数，并对剩余参数排序。
+    fn normalize_url(&self, url: &str) -> String {
+        let Ok(mut parsed) = Url::parse(url) else {
+            return url.to_string();
+        };
+        let mut pairs: Vec<(String, String)> = parsed
+            .query_pairs()
+            .filter(|(key, _)| !self.ignored_query_params.iter().any(|p| p == key))
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect();
+        pairs.sort();
+
+        if pairs.is_empty() {
+            parsed.set_query(None);
+        } else {
+            parsed.query_pairs_mut().clear().extend_pairs(pairs);
+        }
+        parsed.to_string()
+    }
+
+    fn matches(&self, exchange: &HttpExchange, request: &RequestRecord) -> bool {
+        if self.match_method && !exchange.method.eq_ignore_ascii_case(&request.method) {
+            return false;
+        }
+        if self.normalize_url(&exchange.url) != self.normalize_url(&request.url) {
+            return false;
+        }
+        if self.match_body {
+            let recorded = exchange
+                .request_body
+                .as_ref()
+                .and_then(|body| body.to_bytes().ok());
+            if !self.bodies_match(recorded.as_deref(), request.body.as_deref()) {
+                return false;
+            }
+        }
+        self.matched_headers.iter().all(|name| {
+            header_value(&exchange.request_headers, name) == header_value(&request.headers, name)
+        })
+    }
+
+    fn bodies_match(&self, recorded: Option<&[u8]>, requested: Option<&[u8]>) -> bool {
+        if recorded == requested {
+            return true;
+        }
+        if self.ignored_body_fields.is_empty() {
+            return false;
+        }
+        let parse = |body: Option<&[u8]>| {
+            let mut value = serde_json::from_slice::<serde_json::Value>(body?).ok()?;
+            self.strip_ignored_fields(&mut value);
+            Some(value)
+        };
+        matches!((parse(recorded), parse(requested)), (Some(a), Some(b)) if a == b)
+    }
+
+    fn strip_ignored_fields(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(map) => {
+                map.retain(|key, _| !self.ignored_body_fields.contains(key));
+                for v in map.values_mut() {
+                    self.strip_ignored_fields(v);
+                }
+            }
+            serde_json::Value::Array(items) => {
+                for v in items {
+                    self.strip_ignored_fields(v);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn header_value<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+/// 一次请求的完整描述，录制和回放共用。
+#[derive(Debug, Clone)]
+struct RequestRecord {
+    method: String,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Option<Vec<u8>>,
+}
+
+impl RequestRecord {
+    fn new(method: HttpMethod, url: &str, headers: &[(&str, &str)], body: Option<Vec<u8>>) -> Self {
+        Self {
+            method: method.as_str().to_string(),
+            url: url.to_string(),
+            headers: headers
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect(),
+            body,
+        }
+    }
+
+    fn json(url: &str, json: &serde_json::Value) -> Result<Self> {
+        Ok(Self::new(
+            HttpMethod::Post,
+            url,
+            &[("Content-Type", "application/json")],
+            Some(serde_json::to_vec(json)?),
+        ))
+    }
+
+    fn form(url: &str, form: &[(&str, &str)]) -> Result<Self> {
+        let body = serde_urlencoded::to_string(form)
+            .map_err(|e| LyricsHelperError::Internal(format!("无法对表单进行编码: {e}")))?;
+        Ok(Self::new(
+            HttpMethod::Post,
+            url,
+            &[("Content-Type", "application/x-www-form-urlencoded")],
+            Some(body.into_bytes()),
+        ))
+    }
+}
+
+/// 将内部客户端的所有交互录制下来的装饰器。
+#[derive(Debug)]
+pub struct RecordingHttpClient {
+    inner: Arc<dyn HttpClient>,
+    fixture: Mutex<HttpFixture>,
+    path: Option<PathBuf>,
+}
+
+impl RecordingHttpClient {
+    /// 包装一个客户端。录制结果需要手动调用 `save` 保存。
+    pub fn new(inner: Arc<dyn HttpClient>) -> Self {
+        Self {
+            inner,
+            fixture: Mutex::new(HttpFixture::default()),
+            path: None,
+        }
+    }
+
+    /// 包装一个客户端，并在被丢弃时自动把录制结果写入 `path`。
+    pub fn to_file(inner: Arc<dyn HttpClient>, path: impl Into<PathBuf>) -> Self {
+        Self {
+            inner,
+            fixture: Mutex::new(HttpFixture::default()),
+            path: Some(path.into()),
+        }
+    }
+
+    /// 目前为止录制到的内容，包含内部客户端当前的 Cookies。
+    pub fn fixture(&self) -> HttpFixture {
+        let mut fixture = self.fixture.lock().clone();
+        fixture.cookies = self.inner.get_cookies().ok().filter(|c| !c.is_empty());
+        fixture
+    }
+
+    /// 将录制结果保存到文件。
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        self.fixture().save(path)
+    }
+
+    fn record(
+        &self,
+        request: RequestRecord,
+        response: Result<HttpResponse>,
+    ) -> Result<HttpResponse> {
+        let response = response?;
+        self.fixture.lock().exchanges.push(HttpExchange {
+            method: request.method,
+            url: request.url,
+            request_headers: request.headers,
+            request_body: request.body.as_deref().map(FixtureBody::from_bytes),
+            status: response.status,
+            response_headers: response.headers.clone(),
+            response_body: FixtureBody::from_bytes(&response.body),
+        });
+        Ok(response)
+    }
+}
+
+impl Drop for RecordingHttpClient {
+    fn drop(&mut self) {
+        if let Some(path) = &self.path
+            && let Err(e) = self.fixture().save(path)
+        {
+            tracing::error!("[Recording] 保存夹具 {} 失败: {e}", path.display());
+        }
+    }
+}
+
+#[async_trait]
+impl HttpClient for RecordingHttpClient {
+    async fn get(&self, url: &str) -> Result<HttpResponse> {
+        let response = self.inner.get(url).await;
+        self.record(
+            RequestRecord::new(HttpMethod::Get, url, &[], None),
+            response,
+        )
+    }
+
+    async fn post_json(&self, url: &str, json: &serde_json::Value) -> Result<HttpResponse> {
+        let response = self.inner.post_json(url, json).await;
+        self.record(RequestRecord::json(url, json)?, response)
+    }
+
+    async fn post_form(&self, url: &str, form: &[(&str, &str)]) -> Result<HttpResponse> {
+        let response = self.inner.post_form(url, form).await;
+        self.record(RequestRecord::form(url, form)?, response)
+    }
+
+    async fn request_with_headers(
+        &self,
+        method: HttpMethod,
+        url: &str,
+        headers: &[(&str, &str)],
+        body: Option<&[u8]>,
+    ) -> Result<HttpResponse> {
+        let response = self
+            .inner
+            .request_with_headers(method, url, headers, body)
+            .await;
+        self.record(
+            RequestRecord::new(method, url, headers, body.map(<[u8]>::to_vec)),
+            response,
+        )
+    }
+
+    fn get_cookies(&self) -> Result<String> {
+        self.inner.get_cookies()
+    }
+
+    fn set_cookies(&self, cookies_json: &str) -> Result<()> {
+        self.inner.set_cookies(cookies_json)
+    }
+}
+
+/// 从夹具中回放响应的客户端，不会访问网络。
+///
+/// 每个请求优先匹配尚未使用过的交互，因此同一个请求多次返回不同响应的场景也能按顺序回放；
+/// 所有匹配的交互都用过之后，会重复返回最后一个匹配的响应。
+#[derive(Debug)]
+pub struct ReplayHttpClient {
+    exchanges: Vec<HttpExchange>,
+    used: Mutex<Vec<bool>>,
+    rules: MatchRules,
+    cookies: Mutex<String>,
+}
+
+impl ReplayHttpClient {
+    /// 使用夹具创建回放客户端。
+    #[must_use]
+    pub fn new(fixture: HttpFixture) -> Self {
+        Self {
+            used: Mutex::new(vec![false; fixture.exchanges.len()]),
+            exchanges: fixture.exchanges,
+            rules: MatchRules::default(),
+            cookies: Mutex::new(fixture.cookies.unwrap_or_default()),
+        }
+    }
+
+    /// 从夹具文件创建回放客户端。
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self::new(HttpFixture::load(path)?))
+    }
+
+    /// 设置请求匹配规则。
+    #[must_use]
+    pub fn with_match_rules(mut self, rules: MatchRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// 尚未被回放过的交互数量。
+    pub fn remaining(&self) -> usize {
+        self.used.lock().iter().filter(|used| !**used).count()
+    }
+
+    fn replay(&self, request: &RequestRecord) -> Result<HttpResponse> {
+        let matching: Vec<usize> = self
+            .exchanges
+            .iter()
+            .enumerate()
+            .filter(|(_, exchange)| self.rules.matches(exchange, request))
+            .map(|(index, _)| index)
+            .collect();
+
+        let index = {
+            let mut used = self.used.lock();
+            let index = matching
+                .iter()
+                .copied()
+                .find(|&index| !used[index])
+                .or_else(|| matching.last().copied());
+            if let Some(index) = index {
+                used[index] = true;
+            }
+            drop(used);
+            index
+        };
+
+        index.map_or_else(
+            || {
+                Err(LyricsHelperError::Http(format!(
+                    "夹具中没有与请求匹配的记录: {} {}",
+                    request.method, request.url
+                )))
+            },
+            |index| self.exchanges[index].to_response(),
+        )
+    }
+}
+
+#[async_trait]
+impl HttpClient for ReplayHttpClient {
+    async fn get(&self, url: &str) -> Result<HttpResponse> {
+        self.replay(&RequestRecord::new(HttpMethod::Get, url, &[], None))
+    }
+
+    async fn post_json(&self, url: &str, json: &serde_json::Value) -> Result<HttpResponse> {
+        self.replay(&RequestRecord::json(url, json)?)
+    }
+
+    async fn post_form(&self, url: &str, form: &[(&str, &str)]) -> Result<HttpResponse> {
+        self.replay(&RequestRecord::form(url, form)?)
+    }
+
+    async fn request_with_headers(
+        &self,
+        method: HttpMethod,
+        url: &str,
+        headers: &[(&str, &str)],
+        body: Option<&[u8]>,
+    ) -> Result<HttpResponse> {
+        self.replay(&RequestRecord::new(
+            method,
+            url,
+            headers,
+            body.map(<[u8]>::to_vec),
+        ))
+    }
+
+    fn get_cookies(&self) -> Result<String> {
+        Ok(self.cookies.lock().clone())
+    }
+
+    fn set_cookies(&self, cookies_json: &str) -> Result<()> {
+        cookies_json.clone_into(&mut self.cookies.lock());
+        Ok(())
+    }
+}
+
+/// 提供商测试的夹具目录。
+#[cfg(test)]
+pub(crate) const FIXTURE_DIR: &str =
+    concat!(env!("CARGO_MANIFEST_DIR"), "/tests/test_data/fixtures");
+
+/// 设置此环境变量后，提供商测试会访问真实接口，并把交互重新录制到夹具中。
+#[cfg(test)]
+pub(crate) const RECORD_FIXTURES_ENV: &str = "LYRICS_HELPER_RECORD_FIXTURES";
+
+/// 为提供商测试创建 HTTP 客户端。
+///
+/// 默认从 `FIXTURE_DIR` 下的 `{name}.json` 回放；设置了 [`RECORD_FIXTURES_ENV`] 时改为访问网络，
+/// 并在客户端被丢弃时把录制结果写回同一个文件：
+///
+/// ```sh
+/// LYRICS_HELPER_RECORD_FIXTURES=1 cargo test -p lyrics_helper_rs providers::qq
+/// ```
+#[cfg(test)]
+pub(crate) fn fixture_client(name: &str, rules: MatchRules) -> Arc<dyn HttpClient> {
+    let path = Path::new(FIXTURE_DIR).join(format!("{name}.json"));
+    if std::env::var_os(RECORD_FIXTURES_ENV).is_some() {
+        let inner = crate::http::WreqClient::new().expect("创建 HTTP 客户端失败");
+        return Arc::new(RecordingHttpClient::to_file(Arc::new(inner), path));
+    }
+    let replay = ReplayHttpClient::from_file(&path).unwrap_or_else(|e| {
+        panic!(
+            "读取夹具 {} 失败: {e}。设置 {RECORD_FIXTURES_ENV}=1 重新录制",
+            path.display()
+        )
+    });
+    Arc::new(replay.with_match_rules(rules))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 把请求方法、URL 和请求体回显为响应体。
+    #[derive(Debug, Default)]
+    struct EchoClient {
+        cookies: Mutex<String>,
+    }
+
+    #[async_trait]
+    impl HttpClient for EchoClient {
+        async fn get(&self, url: &str) -> Result<HttpResponse> {
+            self.request_with_headers(HttpMethod::Get, url, &[], None)
+                .await
+        }
+        async fn post_json(&self, url: &str, json: &serde_json::Value) -> Result<HttpResponse> {
+            let body = serde_json::to_vec(json)?;
+            self.request_with_headers(HttpMethod::Post, url, &[], Some(&body))
+                .await
+        }
+        async fn post_form(&self, url: &str, form: &[(&str, &str)]) -> Result<HttpResponse> {
+            let body = serde_urlencoded::to_string(form).unwrap();
+            self.request_with_headers(HttpMethod::Post, url, &[], Some(body.as_bytes()))
+                .await
+        }
+        async fn request_with_headers(
+            &self,
+            method: HttpMethod,
+            url: &str,
+            _headers: &[(&str, &str)],
+            body: Option<&[u8]>,
+        ) -> Result<HttpResponse> {
+            let mut echo = format!("{} {url}", method.as_str()).into_bytes();
+            if let Some(body) = body {
+                echo.push(b'\n');
+                echo.extend_from_slice(body);
+            }
+            Ok(HttpResponse {
+                status: 200,
+                headers: vec![("content-type".into(), "text/plain".into())],
+                body: echo,
+            })
+        }
+        fn get_cookies(&self) -> Result<String> {
+            Ok(self.cookies.lock().clone())
+        }
+        fn set_cookies(&self, cookies_json: &str) -> Result<()> {
+            cookies_json.clone_into(&mut self.cookies.lock());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_then_replay_roundtrip() {
+        let path = std::env::temp_dir().join(format!("http-fixture-{}.json", uuid::Uuid::new_v4()));
+
+        let recorder = RecordingHttpClient::new(Arc::new(EchoClient::default()));
+        recorder
+            .post_form("https://example.com/login", &[("user", "a b")])
+            .await
+            .unwrap();
+        recorder
+            .post_json("https://example.com/api", &serde_json::json!({"id": 1}))
+            .await
+            .unwrap();
+        recorder
+            .request_with_headers(
+                HttpMethod::Put,
+                "https://example.com/bin",
+                &[("X-Token", "t")],
+                Some(&[0xff, 0x00]),
+            )
+            .await
+            .unwrap();
+        recorder.set_cookies("[{\"name\":\"uin\"}]").unwrap();
+        recorder.save(&path).unwrap();
+
+        let replay = ReplayHttpClient::from_file(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(replay.remaining(), 3);
+        assert_eq!(replay.get_cookies().unwrap(), "[{\"name\":\"uin\"}]");
+
+        let form = replay
+            .post_form("https://example.com/login", &[("user", "a b")])
+            .await
+            .unwrap();
+        assert_eq!(
+            form.text().unwrap(),
+            "POST https://example.com/login\nuser=a+b"
+        );
+
+        let json = replay
+            .post_json("https://example.com/api", &serde_json::json!({"id": 1}))
+            .await
+            .unwrap();
+        assert!(json.text().unwrap().ends_with("{\"id\":1}"));
+
+        let binary = replay
+            .request_with_headers(
+                HttpMethod::Put,
+                "https://example.com/bin",
+                &[],
+                Some(&[0xff, 0x00]),
+            )
+            .await
+            .unwrap();
+        assert!(binary.body.ends_with(&[0xff, 0x00]));
+        assert_eq!(replay.remaining(), 0);
+
+        let mismatch = replay
+            .post_json("https://example.com/api", &serde_json::json!({"id": 2}))
+            .await;
+        assert!(matches!(mismatch, Err(LyricsHelperError::Http(_))));
+    }
+
+    #[tokio::test]
+    async fn test_replay_match_rules() {
+        let exchange = |url: &str, body: &str| HttpExchange {
+            method: "GET".into(),
+            url: url.into(),
+            request_headers: vec![],
+            request_body: None,
+            status: 200,
+            response_headers: vec![],
+            response_body: FixtureBody::Text(body.into()),
+        };
+        let fixture = HttpFixture {
+            exchanges: vec![
+                exchange("https://example.com/s?q=a&_=1", "first"),
+                exchange("https://example.com/s?_=2&q=a", "second"),
+            ],
+            cookies: None,
+        };
+
+        let strict = ReplayHttpClient::new(fixture.clone());
+        assert!(strict.get("https://example.com/s?q=a&_=3").await.is_err());
+
+        let replay = ReplayHttpClient::new(fixture)
+            .with_match_rules(MatchRules::default().ignore_query_param("_"));
+        let url = "https://example.com/s?_=9&q=a";
+        assert_eq!(replay.get(url).await.unwrap().text().unwrap(), "first");
+        assert_eq!(replay.get(url).await.unwrap().text().unwrap(), "second");
+        assert_eq!(replay.get(url).await.unwrap().text().unwrap(), "second");
+        assert!(replay.post_form(url, &[]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_replay_ignores_body_fields() {
+        let recorder = RecordingHttpClient::new(Arc::new(EchoClient::default()));
+        let original = serde_json::json!({"req": {"searchid": "1", "query": "a"}});
+        recorder
+            .post_json("https://example.com/api", &original)
+            .await
+            .unwrap();
+        let fixture = recorder.fixture();
+
+        let requested = serde_json::json!({"req": {"query": "a", "searchid": "2"}});
+        let strict = ReplayHttpClient::new(fixture.clone());
+        assert!(
+            strict
+                .post_json("https://example.com/api", &requested)
+                .await
+                .is_err()
+        );
+
+        let replay = ReplayHttpClient::new(fixture)
+            .with_match_rules(MatchRules::default().ignore_body_field("searchid"));
+        assert!(
+            replay
+                .post_json("https://example.com/api", &requested)
+                .await
+                .is_ok()
+        );
+
+        let other = serde_json::json!({"req": {"query": "b", "searchid": "1"}});
+        assert!(
+            replay
+                .post_json("https://example.com/api", &other)
+                .await
+                .is_err()
+        );
+    }
+}
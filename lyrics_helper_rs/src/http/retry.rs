@@ -0,0 +1,479 @@
+//! 为任意 `HttpClient` 增加重试、退避、按主机限流和请求超时的装饰器。
+//!
+//! ```rust,ignore
+//! use std::{sync::Arc, time::Duration};
+//! use lyrics_helper_rs::http::{RateLimit, RetryHttpClient, RetryPolicy, WreqClient};
+//! use lyrics_helper_rs::providers::{Provider, qq::QQMusic};
+//!
+//! async {
+//!     let client = RetryHttpClient::new(Arc::new(WreqClient::new().unwrap()))
+//!         .with_retry_policy(RetryPolicy { max_retries: 5, ..Default::default() })
+//!         .with_rate_limit(RateLimit { requests_per_second: 4.0, burst: 2 })
+//!         .with_timeout(Duration::from_secs(8));
+//!     let qq = QQMusic::with_http_client(Arc::new(client)).await.unwrap();
+//! };
+//! ```
+
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use rand::Rng;
+use url::Url;
+
+use crate::{
+    error::{LyricsHelperError, Result},
+    http::{HttpClient, HttpMethod, HttpResponse},
+};
+
+/// 重试策略。
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// 首次请求失败后最多重试的次数。
+    pub max_retries: u32,
+    /// 第一次重试前的等待时间。
+    pub initial_backoff: Duration,
+    /// 等待时间的上限。
+    pub max_backoff: Duration,
+    /// 每次重试后等待时间的倍数。
+    pub multiplier: f64,
+    /// 随机抖动的比例，取值 0 到 1。等待时间会在 `±jitter` 的范围内随机浮动。
+    pub jitter: f64,
+    /// 需要重试的 HTTP 状态码。
+    pub retry_on_status: Vec<u16>,
+    /// 是否在网络错误（连接失败、超时等）时重试。
+    pub retry_on_network_error: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(300),
+            max_backoff: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: 0.2,
+            retry_on_status: vec![408, 429, 500, 502, 503, 504],
+            retry_on_network_error: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// 不进行任何重试的策略。
+    #[must_use]
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Default::default()
+        }
+    }
+
+    /// 判断一个响应状态码是否应当重试。
+    #[must_use]
+    pub fn should_retry_status(&self, status: u16) -> bool {
+        self.retry_on_status.contains(&status)
+    }
+
+    /// 判断一个错误是否应当重试。只有网络层面的错误才是暂时性的。
+    #[must_use]
+    pub const fn should_retry_error(&self, error: &LyricsHelperError) -> bool {
+        self.retry_on_network_error
+            && matches!(
+                error,
+                LyricsHelperError::Http(_)
+                    | LyricsHelperError::Io(_)
+                    | LyricsHelperError::RateLimited(_)
+                    | LyricsHelperError::Timeout(_)
+            )
+    }
+
+    /// 计算第 `attempt` 次重试（从 0 开始）前的等待时间，包含随机抖动。
+    #[must_use]
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let base = self.initial_backoff.as_secs_f64() * self.multiplier.powi(attempt as i32);
+        let capped = base.min(self.max_backoff.as_secs_f64());
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        let factor = if jitter > 0.0 {
+            rand::thread_rng().gen_range(1.0 - jitter..=1.0 + jitter)
+        } else {
+            1.0
+        };
+        Duration::from_secs_f64((capped * factor).max(0.0))
+    }
+}
+
+/// 按主机限流的令牌桶参数。
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimit {
+    /// 每秒补充的令牌数，即长期平均的每秒请求数。
+    pub requests_per_second: f64,
+    /// 桶的容量，即允许的突发请求数。
+    pub burst: u32,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// 为每个主机维护一个令牌桶。
+#[derive(Debug)]
+struct HostRateLimiter {
+    limit: RateLimit,
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+}
+
+impl HostRateLimiter {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 为指定主机预定一个令牌，返回需要等待的时间。
+    fn reserve(&self, host: &str) -> Duration {
+        let rate = self.limit.requests_per_second.max(f64::EPSILON);
+        let capacity = f64::from(self.limit.burst.max(1));
+        let now = Instant::now();
+
+        let tokens = {
+            let mut buckets = self.buckets.lock();
+            let bucket = buckets
+                .entry(host.to_string())
+                .or_insert_with(|| TokenBucket {
+                    tokens: capacity,
+                    last_refill: now,
+                });
+
+            let elapsed = now.saturating_duration_since(bucket.last_refill);
+            // 令牌可以透支，透支的部分就是需要等待的时间
+            bucket.tokens = elapsed
+                .as_secs_f64()
+                .mul_add(rate, bucket.tokens)
+                .min(capacity)
+                - 1.0;
+            bucket.last_refill = now;
+            let tokens = bucket.tokens;
+            drop(buckets);
+            tokens
+        };
+
+        if tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-tokens / rate)
+        }
+    }
+}
+
+/// 为内部的 `HttpClient` 增加重试、限流和超时的装饰器。
+#[derive(Debug, Clone)]
+pub struct RetryHttpClient {
+    inner: Arc<dyn HttpClient>,
+    policy: RetryPolicy,
+    rate_limiter: Option<Arc<HostRateLimiter>>,
+    timeout: Option<Duration>,
+}
+
+impl RetryHttpClient {
+    /// 使用默认的重试策略包装一个客户端，默认不限流、不设置额外的超时。
+    pub fn new(inner: Arc<dyn HttpClient>) -> Self {
+        Self {
+            inner,
+            policy: RetryPolicy::default(),
+            rate_limiter: None,
+            timeout: None,
+        }
+    }
+
+    /// 设置重试策略。
+    #[must_use]
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// 为每个主机启用令牌桶限流。
+    #[must_use]
+    pub fn with_rate_limit(mut self, limit: RateLimit) -> Self {
+        self.rate_limiter = Some(Arc::new(HostRateLimiter::new(limit)));
+        self
+    }
+
+    /// 设置每次尝试的超时时间。超时会被视为网络错误。
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// 被包装的客户端。
+    #[must_use]
+    pub fn inner(&self) -> &Arc<dyn HttpClient> {
+        &self.inner
+    }
+
+    /// 按照策略执行请求，直到成功、遇到不可重试的结果或用尽重试次数。
+    async fn execute<'a, F, Fut>(&'a self, url: &str, send: F) -> Result<HttpResponse>
+    where
+        F: Fn(&'a dyn HttpClient) -> Fut + Send,
+        Fut: Future<Output = Result<HttpResponse>> + Send + 'a,
+    {
+        let host = Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_string))
+            .unwrap_or_default();
+
+        let mut attempt = 0;
+        loop {
+            if let Some(limiter) = &self.rate_limiter {
+                let wait = limiter.reserve(&host);
+                if !wait.is_zero() {
+                    tracing::debug!("[Retry] 主机 {host} 触发限流，等待 {wait:?}。");
+                    tokio::time::sleep(wait).await;
+                }
+            }
+
+            let request = send(self.inner.as_ref());
+            let result = match self.timeout {
+                Some(timeout) => {
+                    tokio::time::timeout(timeout, request)
+                        .await
+                        .unwrap_or_else(|_| {
+                            Err(LyricsHelperError::Timeout(format!(
+                                "请求超过 {timeout:?}: {url}"
+                            )))
+                        })
+                }
+                None => request.await,
+            };
+
+            let wait = match &result {
+                Ok(response) if self.policy.should_retry_status(response.status) => {
+                    retry_after(response).unwrap_or_else(|| self.policy.backoff(attempt))
+                }
+                Err(e) if self.policy.should_retry_error(e) => self.policy.backoff(attempt),
+                _ => return result,
+            };
+
+            if attempt >= self.policy.max_retries {
+                return result;
+            }
+            attempt += 1;
+
+            match &result {
+                Ok(response) => tracing::warn!(
+                    "[Retry] {url} 返回状态码 {}，{wait:?} 后进行第 {attempt} 次重试。",
+                    response.status
+                ),
+                Err(e) => {
+                    tracing::warn!(
+                        "[Retry] 请求 {url} 失败: {e}，{wait:?} 后进行第 {attempt} 次重试。"
+                    );
+                }
+            }
+            tokio::time::sleep(wait.min(self.policy.max_backoff)).await;
+        }
+    }
+}
+
+/// 解析以秒为单位的 `Retry-After` 响应头。
+fn retry_after(response: &HttpResponse) -> Option<Duration> {
+    response
+        .headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("retry-after"))
+        .and_then(|(_, value)| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+#[async_trait]
+impl HttpClient for RetryHttpClient {
+    async fn get(&self, url: &str) -> Result<HttpResponse> {
+        self.execute(url, |client| client.get(url)).await
+    }
+
+    async fn post_json(&self, url: &str, json: &serde_json::Value) -> Result<HttpResponse> {
+        self.execute(url, |client| client.post_json(url, json))
+            .await
+    }
+
+    async fn post_form(&self, url: &str, form: &[(&str, &str)]) -> Result<HttpResponse> {
+        self.execute(url, |client| client.post_form(url, form))
+            .await
+    }
+
+    async fn request_with_headers(
+        &self,
+        method: HttpMethod,
+        url: &str,
+        headers: &[(&str, &str)],
+        body: Option<&[u8]>,
+    ) -> Result<HttpResponse> {
+        self.execute(url, |client| {
+            client.request_with_headers(method, url, headers, body)
+        })
+        .await
+    }
+
+    fn get_cookies(&self) -> Result<String> {
+        self.inner.get_cookies()
+    }
+
+    fn set_cookies(&self, cookies_json: &str) -> Result<()> {
+        self.inner.set_cookies(cookies_json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    /// 依次返回预设的结果，用尽后一直返回最后一个。
+    #[derive(Debug)]
+    struct ScriptedClient {
+        statuses: Vec<Option<u16>>,
+        delay: Duration,
+        calls: AtomicUsize,
+    }
+
+    impl ScriptedClient {
+        fn new(statuses: Vec<Option<u16>>) -> Self {
+            Self {
+                statuses,
+                delay: Duration::ZERO,
+                calls: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl HttpClient for ScriptedClient {
+        async fn get(&self, _url: &str) -> Result<HttpResponse> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            self.statuses[call.min(self.statuses.len() - 1)].map_or_else(
+                || Err(LyricsHelperError::Http("connection reset".into())),
+                |status| {
+                    Ok(HttpResponse {
+                        status,
+                        headers: vec![],
+                        body: vec![],
+                    })
+                },
+            )
+        }
+        async fn post_json(&self, url: &str, _json: &serde_json::Value) -> Result<HttpResponse> {
+            self.get(url).await
+        }
+        async fn post_form(&self, url: &str, _form: &[(&str, &str)]) -> Result<HttpResponse> {
+            self.get(url).await
+        }
+        async fn request_with_headers(
+            &self,
+            _method: HttpMethod,
+            url: &str,
+            _headers: &[(&str, &str)],
+            _body: Option<&[u8]>,
+        ) -> Result<HttpResponse> {
+            self.get(url).await
+        }
+        fn get_cookies(&self) -> Result<String> {
+            Ok(String::new())
+        }
+        fn set_cookies(&self, _cookies_json: &str) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn fast_policy(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(5),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_transient_failures() {
+        let inner = Arc::new(ScriptedClient::new(vec![Some(503), None, Some(200)]));
+        let client = RetryHttpClient::new(inner.clone()).with_retry_policy(fast_policy(3));
+
+        let response = client.get("https://example.com/a").await.unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_and_skips_non_retryable() {
+        let inner = Arc::new(ScriptedClient::new(vec![Some(500)]));
+        let client = RetryHttpClient::new(inner.clone()).with_retry_policy(fast_policy(2));
+        let response = client.get("https://example.com/a").await.unwrap();
+        assert_eq!(response.status, 500);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+
+        let inner = Arc::new(ScriptedClient::new(vec![Some(404)]));
+        let client = RetryHttpClient::new(inner.clone()).with_retry_policy(fast_policy(2));
+        assert_eq!(
+            client.get("https://example.com/a").await.unwrap().status,
+            404
+        );
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_attempt_timeout_is_retried_and_reported() {
+        let inner = Arc::new(ScriptedClient {
+            delay: Duration::from_secs(5),
+            ..ScriptedClient::new(vec![Some(200)])
+        });
+        let client = RetryHttpClient::new(inner.clone())
+            .with_retry_policy(fast_policy(1))
+            .with_timeout(Duration::from_millis(10));
+
+        let result = client.get("https://example.com/a").await;
+        assert!(matches!(result, Err(LyricsHelperError::Timeout(_))));
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_is_per_host() {
+        let inner = Arc::new(ScriptedClient::new(vec![Some(200)]));
+        let client = RetryHttpClient::new(inner).with_rate_limit(RateLimit {
+            requests_per_second: 20.0,
+            burst: 1,
+        });
+
+        let start = Instant::now();
+        client.get("https://a.example.com/").await.unwrap();
+        client.get("https://b.example.com/").await.unwrap();
+        assert!(start.elapsed() < Duration::from_millis(40));
+
+        client.get("https://a.example.com/").await.unwrap();
+        client.get("https://a.example.com/").await.unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(90));
+    }
+
+    #[test]
+    fn test_backoff_grows_and_is_capped() {
+        let policy = RetryPolicy {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(300),
+            jitter: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff(5), Duration::from_millis(300));
+    }
+}
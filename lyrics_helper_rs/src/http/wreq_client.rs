@@ -4,17 +4,18 @@ use async_trait::async_trait;
 use cookie_store::{CookieStore, RawCookie, serde::json};
 use parking_lot::Mutex;
 use serde_json;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use url::Url;
 use wreq::{
-    Uri,
+    Certificate, Proxy, Uri,
     cookie::Cookies,
-    header::{CONTENT_TYPE, HeaderMap, HeaderValue},
+    header::{CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue},
 };
 
 use wreq_util::Emulation;
 
 use crate::{
+    config::HttpClientConfig,
     error::{LyricsHelperError, Result},
     http::{HttpClient, HttpMethod, HttpResponse},
 };
@@ -96,13 +97,48 @@ impl Default for WreqClient {
 impl WreqClient {
     /// 创建一个新的 `WreqClient` 实例。
     pub fn new() -> Result<Self> {
+        Self::with_config(&HttpClientConfig::default())
+    }
+
+    /// 使用指定的网络配置创建 `WreqClient`。
+    ///
+    /// 只会使用 `config.proxy`，按提供商区分的代理需要先通过
+    /// [`HttpClientConfig::for_provider`] 解析。
+    pub fn with_config(config: &HttpClientConfig) -> Result<Self> {
         let cookie_store = Arc::new(SharedCookieStore::new());
 
-        let builder = wreq::Client::builder()
+        let mut builder = wreq::Client::builder()
             .emulation(Emulation::Chrome140)
-            .cookie_provider(Arc::clone(&cookie_store));
+            .cookie_provider(Arc::clone(&cookie_store))
+            .timeout(Duration::from_secs(config.timeout_secs));
 
-        let builder = builder.timeout(std::time::Duration::from_secs(10));
+        if let Some(secs) = config.connect_timeout_secs {
+            builder = builder.connect_timeout(Duration::from_secs(secs));
+        }
+        if let Some(secs) = config.read_timeout_secs {
+            builder = builder.read_timeout(Duration::from_secs(secs));
+        }
+        if let Some(proxy) = &config.proxy {
+            let proxy = Proxy::all(proxy.as_str())
+                .map_err(|e| LyricsHelperError::Http(format!("无效的代理地址 '{proxy}': {e}")))?;
+            builder = builder.proxy(proxy);
+        }
+        if let Some(user_agent) = &config.user_agent {
+            builder = builder.user_agent(user_agent.as_str());
+        }
+        if !config.default_headers.is_empty() {
+            builder = builder.default_headers(build_header_map(config)?);
+        }
+        for path in &config.root_certificates {
+            let pem = std::fs::read(path)?;
+            let certificate = Certificate::from_pem(&pem).map_err(|e| {
+                LyricsHelperError::Http(format!("无法加载根证书 {}: {e}", path.display()))
+            })?;
+            builder = builder.add_root_certificate(certificate);
+        }
+        for (domain, addrs) in &config.dns_overrides {
+            builder = builder.resolve_to_addrs(domain, addrs);
+        }
 
         let client = builder
             .build()
@@ -115,6 +151,18 @@ impl WreqClient {
     }
 }
 
+fn build_header_map(config: &HttpClientConfig) -> Result<HeaderMap> {
+    let mut headers = HeaderMap::new();
+    for (name, value) in &config.default_headers {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|e| LyricsHelperError::Http(format!("无效的请求头名称 '{name}': {e}")))?;
+        let value = HeaderValue::from_str(value)
+            .map_err(|e| LyricsHelperError::Http(format!("无效的请求头值 '{value}': {e}")))?;
+        headers.insert(name, value);
+    }
+    Ok(headers)
+}
+
 #[async_trait]
 impl HttpClient for WreqClient {
     fn get_cookies(&self) -> Result<String> {
@@ -25,6 +25,7 @@
 //!         artists: Some(&["小蓝背心"]),
 //!         album: None,
 //!         duration: None,
+//!         version_hints: None,
 //!     };
 //!     match helper.search_lyrics(&track_to_search, SearchMode::Ordered) {
 //!         Ok(future) => match future.await {
@@ -37,6 +38,28 @@
 //! };
 //! ```
 //!
+//! ## 注册自定义提供商
+//!
+//! 任何实现了 [`providers::Provider`] 的类型都可以通过构建器注册到 `LyricsHelper` 中，
+//! 并与内置提供商一起参与搜索。
+//!
+//! ```rust,ignore
+//! use std::sync::Arc;
+//! use lyrics_helper_rs::{LyricsHelper, ProviderName, SearchMode};
+//!
+//! async {
+//!     let helper = LyricsHelper::builder()
+//!         .register_provider(Arc::new(MyProvider::default()))
+//!         .provider_priority("my-provider", 10)
+//!         .disable_provider("kugou")
+//!         .build()
+//!         .await
+//!         .unwrap();
+//!
+//!     let mode = SearchMode::Subset(vec![ProviderName::custom("my-provider"), ProviderName::Netease]);
+//! };
+//! ```
+//!
 //! ## 格式转换
 //!
 //! ```rust
@@ -74,8 +97,10 @@
 //!     }
 //! }
 //! ```
+pub mod cache;
 pub mod config;
 pub mod converter;
+pub mod download;
 pub mod error;
 pub mod http;
 pub mod model;
@@ -87,21 +112,29 @@ use std::{
     hash::BuildHasher,
     pin::Pin,
     sync::Arc,
+    time::{Duration, Instant},
 };
 
+use crate::cache::{CachedProvider, ProviderCache};
+use crate::config::HttpClientConfig;
 use crate::http::{HttpClient, WreqClient};
 
-use futures::{Future, StreamExt, future, stream};
+use futures::{Future, Stream, StreamExt, future, stream, stream::FuturesUnordered};
 use lyrics_helper_core::{
-    ComprehensiveSearchResult, ConversionInput, ConversionOptions, CoverSize, FullConversionResult,
-    FullLyricsResult, LyricFormat, LyricsAndMetadata, ParsedSourceData, SearchResult, Track,
+    CollectionDownloadOptions, ComprehensiveSearchResult, ConversionInput, ConversionOptions,
+    CoverSize, FullConversionResult, FullLyricsResult, LyricFormat, LyricsAndMetadata,
+    LyricsCompositionOptions, LyricsQuality, MatchScoringConfig, MatchType, ParsedSourceData,
+    SearchResult, SongReference, Track, model::generic,
 };
 use serde::{Deserialize, Serialize};
 use tokio_util::sync::CancellationToken;
 
 pub use crate::error::{LyricsHelperError, Result};
 
-/// 支持的歌词提供商枚举
+/// 歌词提供商的标识。
+///
+/// 内置提供商使用固定的枚举值；通过 [`LyricsHelper::register_provider`] 注册的提供商
+/// 使用 `Custom`，其内容即为 `Provider::name()` 的返回值。
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ProviderName {
     /// QQ音乐
@@ -112,21 +145,42 @@ pub enum ProviderName {
     Kugou,
     /// AMLL TTML 数据库
     AmllTtmlDatabase,
+    /// 自定义提供商
+    Custom(String),
 }
 
 impl ProviderName {
     /// 获取提供商的字符串标识符
     #[must_use]
-    pub const fn as_str(&self) -> &'static str {
+    pub fn as_str(&self) -> &str {
         match self {
             Self::QQMusic => "qq",
             Self::Netease => "netease",
             Self::Kugou => "kugou",
             Self::AmllTtmlDatabase => "amll-ttml-database",
+            Self::Custom(name) => name,
         }
     }
 
-    /// 从字符串标识符创建 `ProviderName`
+    /// 创建一个自定义提供商的标识
+    #[must_use]
+    pub fn custom(name: impl Into<String>) -> Self {
+        Self::Custom(name.into())
+    }
+
+    /// 从字符串标识符创建 `ProviderName`，无法识别的标识符视为自定义提供商。
+    #[must_use]
+    pub fn from_name(name: &str) -> Self {
+        Self::try_from_str(name).unwrap_or_else(|| Self::Custom(name.to_string()))
+    }
+
+    /// 是否为内置提供商
+    #[must_use]
+    pub const fn is_builtin(&self) -> bool {
+        !matches!(self, Self::Custom(_))
+    }
+
+    /// 从字符串标识符创建内置提供商的 `ProviderName`
     #[must_use]
     pub fn try_from_str(s: &str) -> Option<Self> {
         match s {
@@ -138,7 +192,7 @@ impl ProviderName {
         }
     }
 
-    /// 获取所有支持的提供商
+    /// 获取所有内置的提供商
     #[must_use]
     pub fn all() -> Vec<Self> {
         vec![
@@ -151,12 +205,13 @@ impl ProviderName {
 
     /// 获取提供商的显示名称
     #[must_use]
-    pub const fn display_name(&self) -> &'static str {
+    pub fn display_name(&self) -> &str {
         match self {
             Self::QQMusic => "QQ音乐",
             Self::Netease => "网易云音乐",
             Self::Kugou => "酷狗音乐",
             Self::AmllTtmlDatabase => "AMLL TTML 数据库",
+            Self::Custom(name) => name,
         }
     }
 }
@@ -198,7 +253,7 @@ pub enum SearchMode {
     Parallel,
     /// 只搜索一个特定的提供商。
     ///
-    /// 参数是提供商的枚举值。
+    /// 参数是提供商的标识，可以是内置提供商或已注册的自定义提供商。
     Specific(ProviderName),
     /// 在指定的一个提供商子集中并行搜索。
     Subset(Vec<ProviderName>),
@@ -242,6 +297,190 @@ impl SearchMode {
     }
 }
 
+/// `search_track_stream` 在搜索过程中依次产生的事件。
+#[derive(Debug)]
+pub enum ProviderSearchEvent {
+    /// 某个提供商完成了搜索，`results` 已按匹配度排序，可能为空。
+    ProviderResults {
+        /// 提供商名称。
+        provider: String,
+        /// 该提供商的搜索结果。
+        results: Vec<SearchResult>,
+    },
+    /// 某个提供商的搜索失败。
+    ProviderFailed {
+        /// 提供商名称。
+        provider: String,
+        /// 失败的原因。
+        error: LyricsHelperError,
+    },
+    /// 合并目前所有提供商的结果后重新排序的快照，在每次收到新结果后产生。
+    Ranking(Vec<SearchResult>),
+    /// 所有提供商都已响应，附带最终的排序结果。这总是最后一个事件。
+    Finished(Vec<SearchResult>),
+}
+
+/// 一个随提供商响应逐步产生搜索事件的 Stream。
+pub type SearchTrackStream<'a> = Pin<Box<dyn Stream<Item = ProviderSearchEvent> + Send + 'a>>;
+
+/// 提供商搜索失败或超时时的处理方式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FailurePolicy {
+    /// 忽略失败的提供商，继续使用其它提供商的结果。
+    #[default]
+    BestEffort,
+    /// 任何一个提供商失败或超时，都立即结束搜索并返回该错误。
+    FailFast,
+}
+
+/// 控制歌词搜索中每个提供商的时限和失败处理方式。
+#[derive(Debug, Clone, Default)]
+pub struct SearchPolicy {
+    /// 每个提供商搜索的默认时限，`None` 表示不限制。
+    pub search_timeout: Option<Duration>,
+    /// 每次获取歌词的默认时限，`None` 表示不限制。
+    pub fetch_timeout: Option<Duration>,
+    /// 按提供商名称覆盖搜索时限。
+    pub provider_search_timeouts: HashMap<String, Duration>,
+    /// 按提供商名称覆盖获取歌词的时限。
+    pub provider_fetch_timeouts: HashMap<String, Duration>,
+    /// 提供商失败时的处理方式。
+    pub failure_policy: FailurePolicy,
+    /// 为搜索结果评分时使用的权重和阈值。
+    pub match_scoring: MatchScoringConfig,
+    /// 全面搜索时如何结合匹配度和歌词质量挑选歌词。
+    pub ranking: LyricsRankingPolicy,
+}
+
+impl SearchPolicy {
+    /// 指定提供商的搜索时限。
+    #[must_use]
+    pub fn search_timeout_for(&self, provider: &str) -> Option<Duration> {
+        self.provider_search_timeouts
+            .get(provider)
+            .copied()
+            .or(self.search_timeout)
+    }
+
+    /// 指定提供商获取歌词的时限。
+    #[must_use]
+    pub fn fetch_timeout_for(&self, provider: &str) -> Option<Duration> {
+        self.provider_fetch_timeouts
+            .get(provider)
+            .copied()
+            .or(self.fetch_timeout)
+    }
+
+    const fn is_fail_fast(&self) -> bool {
+        matches!(self.failure_policy, FailurePolicy::FailFast)
+    }
+}
+
+/// 全面搜索时挑选歌词的策略。
+///
+/// 匹配度最高的若干候选项都会被获取歌词并评估 [`LyricsQuality`]，
+/// 然后按 `match_weight * 匹配度 + quality_weight * 质量` 排序，
+/// 这样匹配度稍低但带有逐字时间轴和翻译的歌词可以胜过只有逐行时间轴的歌词。
+#[derive(Debug, Clone, PartialEq)]
+pub struct LyricsRankingPolicy {
+    /// 匹配度的权重。
+    pub match_weight: f64,
+    /// 歌词质量的权重，为 0 时只按匹配度挑选。
+    pub quality_weight: f64,
+    /// 最多比较多少个候选项的歌词，为 1 时等同于直接使用匹配度最高的候选项。
+    pub max_candidates: usize,
+    /// 匹配度低于此等级的候选项不参与比较，只在比较的候选项都没有歌词时按顺序尝试。
+    pub min_match_type: MatchType,
+}
+
+impl Default for LyricsRankingPolicy {
+    fn default() -> Self {
+        Self {
+            match_weight: 0.6,
+            quality_weight: 0.4,
+            max_candidates: 3,
+            min_match_type: MatchType::High,
+        }
+    }
+}
+
+impl LyricsRankingPolicy {
+    /// 结合匹配度和歌词质量的综合得分（0.0 ~ 1.0 之间按权重加权）。
+    #[must_use]
+    pub fn combined_score(&self, match_type: MatchType, quality: &LyricsQuality) -> f64 {
+        // `MatchType` 的判别值为 0 ~ 100，`None` 为 -1
+        let match_score = f64::from((match_type as i32).max(0)) / 100.0;
+        self.match_weight
+            .mul_add(match_score, self.quality_weight * quality.score / 100.0)
+    }
+}
+
+/// 单个提供商在一次搜索中的结果。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProviderSearchStatus {
+    /// 返回了指定数量的候选项。
+    Found(usize),
+    /// 正常响应，但没有任何候选项。
+    NoResults,
+    /// 超过了时限。
+    TimedOut,
+    /// 发生了错误，附带错误信息。
+    Failed(String),
+    /// 搜索被取消。
+    Cancelled,
+}
+
+impl ProviderSearchStatus {
+    fn from_result(result: &Result<Vec<SearchResult>>) -> Self {
+        match result {
+            Ok(results) if results.is_empty() => Self::NoResults,
+            Ok(results) => Self::Found(results.len()),
+            Err(LyricsHelperError::Timeout(_)) => Self::TimedOut,
+            Err(LyricsHelperError::Cancelled) => Self::Cancelled,
+            Err(e) => Self::Failed(e.to_string()),
+        }
+    }
+
+    /// 提供商是否不可用（超时或出错），而不是确实没有找到。
+    #[must_use]
+    pub const fn is_unavailable(&self) -> bool {
+        matches!(self, Self::TimedOut | Self::Failed(_))
+    }
+}
+
+/// 单个提供商的搜索报告。
+#[derive(Debug, Clone)]
+pub struct ProviderSearchReport {
+    /// 提供商名称。
+    pub provider: String,
+    /// 搜索结果的状态。
+    pub status: ProviderSearchStatus,
+    /// 搜索花费的时间。
+    pub elapsed: Duration,
+}
+
+/// 全面搜索的结果，以及每个提供商的搜索状态。
+#[derive(Debug, Clone, Default)]
+pub struct ComprehensiveSearchReport {
+    /// 找到的歌词，未找到时为 `None`。
+    pub result: Option<ComprehensiveSearchResult>,
+    /// 每个参与搜索的提供商的报告。
+    pub providers: Vec<ProviderSearchReport>,
+}
+
+impl ComprehensiveSearchReport {
+    /// 超时或出错的提供商。
+    pub fn unavailable_providers(&self) -> impl Iterator<Item = &ProviderSearchReport> {
+        self.providers.iter().filter(|r| r.status.is_unavailable())
+    }
+
+    /// 是否所有提供商都不可用。此时未找到歌词并不意味着歌词不存在。
+    #[must_use]
+    pub fn all_providers_unavailable(&self) -> bool {
+        !self.providers.is_empty() && self.providers.iter().all(|r| r.status.is_unavailable())
+    }
+}
+
 /// 一个代表歌词搜索结果的 Future。
 pub type SearchLyricsFuture<'a> =
     Pin<Box<dyn Future<Output = Result<Option<LyricsAndMetadata>>> + Send + 'a>>;
@@ -250,12 +489,35 @@ pub type SearchLyricsFuture<'a> =
 pub type SearchLyricsComprehensiveFuture<'a> =
     Pin<Box<dyn Future<Output = Result<Option<ComprehensiveSearchResult>>> + Send + 'a>>;
 
+/// 一个代表带有提供商报告的全面搜索结果的 Future。
+pub type SearchLyricsReportFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<ComprehensiveSearchReport>> + Send + 'a>>;
+
+/// 一个代表多来源歌词合成结果的 Future。
+pub type ComposeLyricsFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<Option<ParsedSourceData>>> + Send + 'a>>;
+
+/// 一个代表批量下载结果的 Future。
+pub type DownloadCollectionFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<download::CollectionDownloadReport>> + Send + 'a>>;
+
 /// 顶层歌词助手客户端，封装了所有提供商，为用户提供统一、简单的接口。
 ///
 /// 这是与本库交互的主要入口点。
 pub struct LyricsHelper {
     providers: Vec<Arc<dyn Provider + Send + Sync>>,
-    http_clients: HashMap<ProviderName, Arc<dyn HttpClient>>,
+    /// 以提供商名称为键的 HTTP 客户端，用于获取封面等额外请求。
+    http_clients: HashMap<String, Arc<dyn HttpClient>>,
+    /// 被禁用的提供商名称，不会参与任何搜索。
+    disabled_providers: HashSet<String>,
+    /// 提供商的优先级，数值越大越先被搜索，未设置时为 0。
+    provider_priorities: HashMap<String, i32>,
+    /// 所有提供商共用的响应缓存。
+    cache: Option<Arc<ProviderCache>>,
+    /// 创建内置提供商的 HTTP 客户端时使用的网络配置。
+    http_config: HttpClientConfig,
+    /// 搜索歌词时每个提供商的时限和失败处理方式。
+    search_policy: SearchPolicy,
 }
 
 impl Default for LyricsHelper {
@@ -274,30 +536,59 @@ impl LyricsHelper {
         Self {
             providers: Vec::new(),
             http_clients: HashMap::new(),
+            disabled_providers: HashSet::new(),
+            provider_priorities: HashMap::new(),
+            cache: None,
+            http_config: HttpClientConfig::default(),
+            search_policy: SearchPolicy::default(),
         }
     }
 
-    /// 初始化并加载所有歌词提供商。
+    /// 创建一个 `LyricsHelperBuilder`，用于注册自定义提供商并调整提供商的启用状态和优先级。
+    #[must_use]
+    pub fn builder() -> LyricsHelperBuilder {
+        LyricsHelperBuilder::default()
+    }
+
+    /// 初始化并加载所有内置歌词提供商。
     ///
     /// 这个方法会执行网络请求来准备提供商。
-    /// 在使用搜索功能之前，须先调用此方法。
+    /// 在使用搜索功能之前，须先调用此方法。已注册的自定义提供商会被保留。
     ///
     /// # 返回
     /// 如果所有提供商都成功或部分成功初始化，则返回 `Ok(())`。
     pub async fn load_providers(&mut self) -> Result<()> {
-        let provider_names = ProviderName::all();
-        let mut clients: HashMap<ProviderName, Arc<dyn HttpClient>> = HashMap::new();
+        self.load_builtin_providers(ProviderName::all()).await
+    }
+
+    /// 初始化并加载指定的内置提供商，替换之前加载的所有内置提供商。
+    async fn load_builtin_providers(&mut self, provider_names: Vec<ProviderName>) -> Result<()> {
+        let provider_names: Vec<ProviderName> = provider_names
+            .into_iter()
+            .filter(|name| {
+                if !name.is_builtin() {
+                    tracing::warn!(
+                        "[Main] '{}' 不是内置提供商，请使用 register_provider 注册。",
+                        name
+                    );
+                }
+                name.is_builtin()
+            })
+            .collect();
         let mut provider_initializers = Vec::new();
 
         for name in &provider_names {
-            clients.insert(name.clone(), Arc::new(WreqClient::new()?));
+            let config = self.http_config.for_provider(name.as_str());
+            self.http_clients.insert(
+                name.as_str().to_string(),
+                Arc::new(WreqClient::with_config(&config)?),
+            );
         }
-        self.http_clients = clients;
 
         for name in provider_names {
-            let client = self.http_clients.get(&name).unwrap().clone();
+            let client = self.http_clients.get(name.as_str()).unwrap().clone();
             let provider_future = async move {
-                let result: Result<Box<dyn Provider + Send + Sync>> = match name {
+                let result: Result<Box<dyn Provider + Send + Sync>> = match &name {
                     ProviderName::QQMusic => QQMusic::with_http_client(client)
                         .await
                         .map(|p| Box::new(p) as Box<_>),
@@ -310,6 +601,9 @@ impl LyricsHelper {
                     ProviderName::AmllTtmlDatabase => AmllTtmlDatabase::with_http_client(client)
                         .await
                         .map(|p| Box::new(p) as Box<_>),
+                    ProviderName::Custom(custom) => {
+                        Err(LyricsHelperError::ProviderNotSupported(custom.clone()))
+                    }
                 };
                 (name, result)
             };
@@ -321,7 +615,7 @@ impl LyricsHelper {
             .collect::<Vec<_>>()
             .await;
 
-        let providers = results
+        let mut providers: Vec<Arc<dyn Provider + Send + Sync>> = results
             .into_iter()
             .filter_map(|(name, result)| match result {
                 Ok(provider) => {
@@ -339,10 +633,173 @@ impl LyricsHelper {
             })
             .collect();
 
+        // 保留已注册的自定义提供商
+        providers.extend(
+            std::mem::take(&mut self.providers)
+                .into_iter()
+                .filter(|p| ProviderName::try_from_str(p.name()).is_none()),
+        );
         self.providers = providers;
         Ok(())
     }
 
+    /// 注册一个提供商。
+    ///
+    /// 如果已存在同名的提供商，它会被替换。
+    pub fn register_provider(&mut self, provider: Arc<dyn Provider + Send + Sync>) {
+        let name = provider.name();
+        if let Some(existing) = self.providers.iter_mut().find(|p| p.name() == name) {
+            tracing::warn!("[Main] 提供商 '{}' 已存在，将被替换。", name);
+            *existing = provider;
+        } else {
+            tracing::info!("[Main] 已注册提供商 '{}'。", name);
+            self.providers.push(provider);
+        }
+    }
+
+    /// 注册一个提供商，并指定获取封面等额外请求时使用的 HTTP 客户端。
+    pub fn register_provider_with_http_client(
+        &mut self,
+        provider: Arc<dyn Provider + Send + Sync>,
+        http_client: Arc<dyn HttpClient>,
+    ) {
+        self.http_clients
+            .insert(provider.name().to_string(), http_client);
+        self.register_provider(provider);
+    }
+
+    /// 移除指定名称的提供商，返回被移除的提供商。
+    pub fn unregister_provider(&mut self, name: &str) -> Option<Arc<dyn Provider + Send + Sync>> {
+        let index = self.providers.iter().position(|p| p.name() == name)?;
+        self.http_clients.remove(name);
+        Some(self.providers.remove(index))
+    }
+
+    /// 启用或禁用指定名称的提供商。
+    ///
+    /// 被禁用的提供商不会参与任何搜索，但仍可以通过 `get_full_lyrics` 按 ID 获取歌词。
+    pub fn set_provider_enabled(&mut self, name: &str, enabled: bool) {
+        if enabled {
+            self.disabled_providers.remove(name);
+        } else {
+            self.disabled_providers.insert(name.to_string());
+        }
+    }
+
+    /// 检查指定名称的提供商是否已注册且处于启用状态。
+    #[must_use]
+    pub fn is_provider_enabled(&self, name: &str) -> bool {
+        !self.disabled_providers.contains(name) && self.providers.iter().any(|p| p.name() == name)
+    }
+
+    /// 设置提供商的优先级。
+    ///
+    /// 数值越大越先被搜索，在 `SearchMode::Ordered` 模式下决定尝试的顺序，
+    /// 在并行模式下，匹配度相同的结果会优先选择优先级更高的提供商。
+    pub fn set_provider_priority(&mut self, name: &str, priority: i32) {
+        self.provider_priorities.insert(name.to_string(), priority);
+    }
+
+    /// 设置所有提供商共用的响应缓存，传入 `None` 以禁用缓存。
+    ///
+    /// 设置后，搜索和获取歌词都会先查询缓存。
+    pub fn set_cache(&mut self, cache: Option<Arc<ProviderCache>>) {
+        self.cache = cache;
+    }
+
+    /// 当前使用的响应缓存。
+    #[must_use]
+    pub const fn cache(&self) -> Option<&Arc<ProviderCache>> {
+        self.cache.as_ref()
+    }
+
+    /// 设置内置提供商的网络配置（代理、超时、证书等）。
+    ///
+    /// 只对之后调用 `load_providers()` 创建的客户端生效。
+    /// 可以通过 [`config::load_http_client_config`] 读取持久化的配置。
+    pub fn set_http_client_config(&mut self, config: HttpClientConfig) {
+        self.http_config = config;
+    }
+
+    /// 当前的网络配置。
+    #[must_use]
+    pub const fn http_client_config(&self) -> &HttpClientConfig {
+        &self.http_config
+    }
+
+    /// 读取配置目录中持久化的网络配置，替换当前的配置。
+    ///
+    /// 与 [`Self::set_http_client_config`] 一样，只对之后调用 `load_providers()` 创建的客户端生效。
+    /// 参见 [`config::load_http_client_config`]。
+    pub async fn load_saved_http_client_config(&mut self) -> Result<()> {
+        self.http_config = config::load_http_client_config()
+            .await
+            .map_err(|e| LyricsHelperError::Internal(format!("读取网络配置失败: {e}")))?;
+        Ok(())
+    }
+
+    /// 设置搜索歌词时每个提供商的时限和失败处理方式。
+    pub fn set_search_policy(&mut self, policy: SearchPolicy) {
+        self.search_policy = policy;
+    }
+
+    /// 当前的搜索策略。
+    #[must_use]
+    pub const fn search_policy(&self) -> &SearchPolicy {
+        &self.search_policy
+    }
+
+    /// 读取配置目录中用户维护的别名表，并合并到搜索策略的评分配置中。
+    ///
+    /// 别名表用于跨文字比较标题和艺术家，例如 `米津玄師` 与 `Kenshi Yonezu`。
+    /// 参见 [`config::load_alias_table`]。
+    pub async fn load_saved_aliases(&mut self) -> Result<()> {
+        let table = config::load_alias_table()
+            .await
+            .map_err(|e| LyricsHelperError::Internal(format!("读取别名表失败: {e}")))?;
+        let aliases = &mut self.search_policy.match_scoring.aliases;
+        for group in table.groups() {
+            aliases.add_group(group.iter().cloned());
+        }
+        Ok(())
+    }
+
+    /// 启用缓存时用 `CachedProvider` 包装提供商。
+    fn with_cache(
+        &self,
+        provider: Arc<dyn Provider + Send + Sync>,
+    ) -> Arc<dyn Provider + Send + Sync> {
+        match &self.cache {
+            Some(cache) => Arc::new(CachedProvider::new(provider, cache.clone())),
+            None => provider,
+        }
+    }
+
+    /// 按搜索顺序返回所有已启用的提供商名称。
+    #[must_use]
+    pub fn provider_names(&self) -> Vec<&'static str> {
+        self.active_providers().iter().map(|p| p.name()).collect()
+    }
+
+    /// 返回所有已启用的提供商，按优先级从高到低排序，优先级相同时保持注册顺序。
+    fn active_providers(&self) -> Vec<Arc<dyn Provider + Send + Sync>> {
+        let mut active: Vec<_> = self
+            .providers
+            .iter()
+            .filter(|p| !self.disabled_providers.contains(p.name()))
+            .map(|p| self.with_cache(p.clone()))
+            .collect();
+        active.sort_by_key(|p| {
+            std::cmp::Reverse(
+                self.provider_priorities
+                    .get(p.name())
+                    .copied()
+                    .unwrap_or_default(),
+            )
+        });
+        active
+    }
+
     /// 在所有支持的音乐平台中并发地搜索歌曲。
     ///
     /// # 参数
@@ -355,40 +812,94 @@ impl LyricsHelper {
         &self,
         track_meta: &Track<'a>,
     ) -> Pin<Box<dyn Future<Output = Result<Vec<SearchResult>>> + Send + 'a>> {
-        if self.providers.is_empty() {
-            return Box::pin(async { Err(LyricsHelperError::ProvidersNotInitialized) });
-        }
-
-        let providers = self.providers.clone();
-        let track_meta = track_meta.clone();
+        let events = match self.search_track_stream(track_meta) {
+            Ok(events) => events,
+            Err(e) => return Box::pin(async { Err(e) }),
+        };
 
         Box::pin(async move {
-            let search_futures = providers
-                .iter()
-                .map(|provider| search::search_track(provider.as_ref(), &track_meta, true));
+            let mut events = events;
+            while let Some(event) = events.next().await {
+                if let ProviderSearchEvent::Finished(results) = event {
+                    return Ok(results);
+                }
+            }
+            Ok(Vec::new())
+        })
+    }
 
-            let all_results: Vec<SearchResult> = future::join_all(search_futures)
-                .await
-                .into_iter()
-                .filter_map(Result::ok)
-                .flatten()
-                .collect();
+    /// 在所有支持的音乐平台中并发地搜索歌曲，并在每个提供商响应时立即产生事件。
+    ///
+    /// 与 `search_track` 不同，较慢的提供商不会推迟其它提供商的结果。每收到一个提供商的结果，
+    /// 都会产生一个 [`ProviderSearchEvent::Ranking`]，其中是合并后重新排序的全部结果，
+    /// 适合用来逐步刷新界面。
+    ///
+    /// ```rust,ignore
+    /// let mut events = helper.search_track_stream(&track)?;
+    /// while let Some(event) = events.next().await {
+    ///     match event {
+    ///         ProviderSearchEvent::Ranking(results) => refresh_view(&results),
+    ///         ProviderSearchEvent::ProviderFailed { provider, error } => warn(&provider, &error),
+    ///         _ => {}
+    ///     }
+    /// }
+    /// ```
+    pub fn search_track_stream<'a>(&self, track_meta: &Track<'a>) -> Result<SearchTrackStream<'a>> {
+        if self.providers.is_empty() {
+            return Err(LyricsHelperError::ProvidersNotInitialized);
+        }
 
-            let mut sorted_results = all_results;
-            sorted_results.sort_by(|a, b| b.match_type.cmp(&a.match_type));
+        let providers = self.active_providers();
+        if providers.is_empty() {
+            return Ok(Box::pin(stream::iter([ProviderSearchEvent::Finished(
+                Vec::new(),
+            )])));
+        }
 
-            let mut unique_results = Vec::new();
-            let mut seen_keys = HashSet::new();
+        let pending: FuturesUnordered<_> = providers
+            .into_iter()
+            .enumerate()
+            .map(|(index, provider)| {
+                let track_meta = track_meta.clone();
+                let timeout = self.search_policy.search_timeout_for(provider.name());
+                let scoring = self.search_policy.match_scoring.clone();
+                async move {
+                    let (result, _) =
+                        search_provider(&provider, &track_meta, &scoring, timeout, None).await;
+                    (index, provider.name().to_string(), result)
+                }
+            })
+            .collect();
 
-            for result in sorted_results {
-                let key = (result.provider_name.clone(), result.provider_id.clone());
-                if seen_keys.insert(key) {
-                    unique_results.push(result);
+        let events = stream::unfold(
+            (pending, Vec::new()),
+            |(mut pending, mut collected)| async move {
+                let (index, provider, result) = pending.next().await?;
+                let mut events = Vec::with_capacity(3);
+                match result {
+                    Ok(results) => {
+                        collected.extend(results.iter().cloned().map(|r| (index, r)));
+                        events.push(ProviderSearchEvent::ProviderResults { provider, results });
+                        events.push(ProviderSearchEvent::Ranking(merge_ranked_results(
+                            &collected,
+                        )));
+                    }
+                    Err(error) => {
+                        tracing::warn!("[Main] 提供商 '{}' 搜索失败: {}", provider, error);
+                        events.push(ProviderSearchEvent::ProviderFailed { provider, error });
+                    }
                 }
-            }
+                if pending.is_empty() {
+                    events.push(ProviderSearchEvent::Finished(merge_ranked_results(
+                        &collected,
+                    )));
+                }
+                Some((stream::iter(events), (pending, collected)))
+            },
+        )
+        .flatten();
 
-            Ok(unique_results)
-        })
+        Ok(Box::pin(events))
     }
 
     /// 根据提供商名称和歌曲 ID 获取歌词。
@@ -406,22 +917,46 @@ impl LyricsHelper {
         provider_name: &'a str,
         song_id: &'a str,
     ) -> Result<Pin<Box<dyn Future<Output = Result<FullLyricsResult>> + Send + 'a>>> {
+        let provider = self.cached_provider(provider_name)?;
+        let song_id = song_id.to_string();
+
+        Ok(Box::pin(
+            async move { provider.get_full_lyrics(&song_id).await },
+        ))
+    }
+
+    /// 根据提供商名称和歌曲 ID 获取解析后的歌词。
+    ///
+    /// 与 `get_full_lyrics` 使用同一个缓存，只返回其中已解析的部分。
+    ///
+    /// # 参数
+    /// * `provider_name` - 提供商的唯一名称, 例如 "qq" 或 "netease"。
+    /// * `song_id` - 在该提供商平台上的歌曲ID。
+    ///
+    /// # 返回
+    /// `Result<ParsedSourceData>` - 成功时返回已解析和合并好的歌词数据。
+    pub fn get_lyrics<'a>(
+        &self,
+        provider_name: &'a str,
+        song_id: &'a str,
+    ) -> Result<Pin<Box<dyn Future<Output = Result<ParsedSourceData>> + Send + 'a>>> {
+        let provider = self.cached_provider(provider_name)?;
+        let song_id = song_id.to_string();
+
+        Ok(Box::pin(async move { provider.get_lyrics(&song_id).await }))
+    }
+
+    /// 按名称查找提供商，启用缓存时返回包装后的提供商。
+    fn cached_provider(&self, provider_name: &str) -> Result<Arc<dyn Provider + Send + Sync>> {
         if self.providers.is_empty() {
             return Err(LyricsHelperError::ProvidersNotInitialized);
         }
 
-        let provider = self
-            .providers
+        self.providers
             .iter()
             .find(|p| p.name() == provider_name)
-            .cloned() // 克隆 Arc
-            .ok_or_else(|| LyricsHelperError::ProviderNotSupported(provider_name.to_string()))?;
-
-        let song_id = song_id.to_string();
-
-        Ok(Box::pin(
-            async move { provider.get_full_lyrics(&song_id).await },
-        ))
+            .map(|p| self.with_cache(p.clone()))
+            .ok_or_else(|| LyricsHelperError::ProviderNotSupported(provider_name.to_string()))
     }
 
     /// 执行一次完整的、多文件的歌词转换。
@@ -480,7 +1015,7 @@ impl LyricsHelper {
             return Err(LyricsHelperError::ProvidersNotInitialized);
         }
 
-        let providers_to_search = get_providers_for_mode(&self.providers, &mode)?;
+        let providers_to_search = get_providers_for_mode(&self.active_providers(), &mode)?;
         if providers_to_search.is_empty() {
             return Ok(Box::pin(async { Ok(None) }));
         }
@@ -492,14 +1027,15 @@ impl LyricsHelper {
         );
 
         let track_meta = track_meta.clone();
+        let policy = self.search_policy.clone();
 
         Ok(Box::pin(async move {
             match mode {
                 SearchMode::Ordered | SearchMode::Specific(_) => {
-                    search_ordered(&providers_to_search, &track_meta).await
+                    search_ordered(&providers_to_search, &track_meta, &policy).await
                 }
                 SearchMode::Parallel | SearchMode::Subset(_) => {
-                    search_lyrics_parallel(&providers_to_search, &track_meta).await
+                    search_lyrics_parallel(&providers_to_search, &track_meta, &policy).await
                 }
             }
         }))
@@ -516,19 +1052,37 @@ impl LyricsHelper {
     /// * `Ok(Some(ComprehensiveSearchResult))` - 如果成功找到歌词，包含歌词和所有候选项。
     /// * `Ok(None)` - 如果未找到任何歌词。
     /// * `Err(LyricsHelperError)` - 如果发生错误。
+    ///
+    /// 需要区分"未找到"和"提供商不可用"时，请使用 `search_lyrics_comprehensive_with_report`。
     pub fn search_lyrics_comprehensive<'a>(
         &self,
         track_meta: &Track<'a>,
         mode: &SearchMode,
         cancellation_token: Option<CancellationToken>,
     ) -> Result<SearchLyricsComprehensiveFuture<'a>> {
+        let report =
+            self.search_lyrics_comprehensive_with_report(track_meta, mode, cancellation_token)?;
+        Ok(Box::pin(async move { Ok(report.await?.result) }))
+    }
+
+    /// 与 `search_lyrics_comprehensive` 相同，但同时返回每个提供商的搜索状态。
+    ///
+    /// 每个提供商的搜索和获取歌词都受 [`SearchPolicy`] 中的时限约束。
+    /// 在 [`FailurePolicy::BestEffort`] 下，超时或出错的提供商会被跳过并记录在报告中；
+    /// 在 [`FailurePolicy::FailFast`] 下则直接返回错误。
+    pub fn search_lyrics_comprehensive_with_report<'a>(
+        &self,
+        track_meta: &Track<'a>,
+        mode: &SearchMode,
+        cancellation_token: Option<CancellationToken>,
+    ) -> Result<SearchLyricsReportFuture<'a>> {
         if self.providers.is_empty() {
             return Err(LyricsHelperError::ProvidersNotInitialized);
         }
 
-        let providers_to_search = get_providers_for_mode(&self.providers, mode)?;
+        let providers_to_search = get_providers_for_mode(&self.active_providers(), mode)?;
         if providers_to_search.is_empty() {
-            return Ok(Box::pin(async { Ok(None) }));
+            return Ok(Box::pin(async { Ok(ComprehensiveSearchReport::default()) }));
         }
 
         tracing::info!(
@@ -538,46 +1092,175 @@ impl LyricsHelper {
         );
 
         let track_meta = track_meta.clone();
+        let policy = self.search_policy.clone();
 
         Ok(Box::pin(async move {
-            search_comprehensive_unified(&providers_to_search, &track_meta, cancellation_token)
-                .await
+            search_comprehensive_unified(
+                &providers_to_search,
+                &track_meta,
+                &policy,
+                cancellation_token.as_ref(),
+            )
+            .await
         }))
     }
 
-    /// 获取最佳的封面。
+    /// 根据分享链接或平台 ID 直接获取歌词，跳过模糊搜索。
+    ///
+    /// 支持的输入格式见 [`search::reference`]。解析出的 ID 会先交给维护跨平台 ID 映射的提供商
+    /// （如 AMLL TTML Database）查找；找不到时，如果 ID 属于某个已启用的提供商
+    /// （QQ音乐、网易云音乐、酷狗音乐），则直接从该提供商获取。
+    ///
+    /// # 返回
+    /// * `Ok(Some(LyricsAndMetadata))` - 成功获取到歌词。
+    /// * `Ok(None)` - 引用有效，但没有提供商能直接提供歌词，调用方可以回退到模糊搜索。
+    /// * `Err(LyricsHelperError::InvalidReference)` - 无法识别输入的链接或 ID。
+    pub fn resolve_reference<'a>(&self, reference: &str) -> Result<SearchLyricsFuture<'a>> {
+        if self.providers.is_empty() {
+            return Err(LyricsHelperError::ProvidersNotInitialized);
+        }
+
+        let song_reference = search::reference::parse_reference(reference)
+            .ok_or_else(|| LyricsHelperError::InvalidReference(reference.to_string()))?;
+        tracing::info!("已将 '{}' 解析为 {:?}", reference, song_reference);
+
+        let providers = self.active_providers();
+        let policy = self.search_policy.clone();
+
+        Ok(Box::pin(async move {
+            resolve_song_reference(&providers, &song_reference, &policy).await
+        }))
+    }
+
+    /// 从多个搜索结果获取歌词，并合成为一份。
+    ///
+    /// 主歌词、翻译和罗马音分别按 `options` 中的提供商偏好取自不同的来源，
+    /// 按行的时间和文本对齐后合并。每条轨道的来源记录在
+    /// [`converter::processors::lyrics_composer::SOURCE_METADATA_KEY`] 元数据中。
     ///
     /// # 参数
-    /// * `candidates` - 搜索结果候选项列表
+    /// * `candidates` - 匹配的搜索结果，通常来自 `search_lyrics_comprehensive` 的候选项。
+    ///   每个提供商只使用排在最前面的一个候选项。
+    /// * `options` - 合成选项。
+    /// * `cancellation_token` - 用于取消获取。
     ///
     /// # 返回
-    /// * `Some(Vec<u8>)` - 成功获取到的封面数据
-    /// * `None` - 所有候选项都无法提供封面
-    #[must_use]
-    pub fn get_best_cover<'a>(
-        &'a self,
-        candidates: &'a [SearchResult],
-    ) -> Pin<Box<dyn Future<Output = Option<Vec<u8>>> + Send + 'a>> {
-        let providers_map: HashMap<_, _> = self
-            .providers
+    /// * `Ok(Some(ParsedSourceData))` - 合成后的歌词。
+    /// * `Ok(None)` - 所有候选项都无法提供歌词。
+    pub fn compose_lyrics<'a>(
+        &self,
+        candidates: &[SearchResult],
+        options: &LyricsCompositionOptions,
+        cancellation_token: Option<CancellationToken>,
+    ) -> Result<ComposeLyricsFuture<'a>> {
+        if self.providers.is_empty() {
+            return Err(LyricsHelperError::ProvidersNotInitialized);
+        }
+
+        let providers = self.active_providers();
+        let mut seen_providers = HashSet::new();
+        let candidates: Vec<SearchResult> = candidates
             .iter()
-            .map(|p| (p.name(), p.clone()))
+            .filter(|c| seen_providers.insert(c.provider_name.clone()))
+            .cloned()
             .collect();
+        let options = options.clone();
+        let policy = self.search_policy.clone();
+
+        Ok(Box::pin(async move {
+            let sources = fetch_composition_sources(
+                &providers,
+                &candidates,
+                &policy,
+                cancellation_token.as_ref(),
+            )
+            .await?;
+            Ok(converter::processors::lyrics_composer::compose_lyrics(
+                &sources, &options,
+            ))
+        }))
+    }
+
+    /// 下载专辑、歌手或歌单中所有歌曲的歌词。
+    ///
+    /// 分页获取集合中的歌曲，以 `options.concurrency` 的并发获取歌词并转换为目标格式，
+    /// 按文件名模板写入 `options.output_dir`。已存在的文件会被跳过，中断后用相同的选项
+    /// 重新调用即可继续。每首歌曲的结果同时写入输出目录下的 [`download::REPORT_FILE_NAME`]。
+    ///
+    /// # 参数
+    /// * `collection` - 集合的分享链接或 ID，支持的格式见
+    ///   [`search::reference::parse_collection_reference`]。
+    /// * `options` - 下载选项。
+    /// * `cancellation_token` - 用于取消下载，尚未处理的歌曲会被标记为已取消。
+    ///
+    /// # 返回
+    /// * `Err(LyricsHelperError::InvalidReference)` - 无法识别输入的链接或 ID。
+    /// * `Err(LyricsHelperError::ProviderNotSupported)` - 集合所属的提供商未启用。
+    pub fn download_collection<'a>(
+        &self,
+        collection: &str,
+        options: &CollectionDownloadOptions,
+        cancellation_token: Option<CancellationToken>,
+    ) -> Result<DownloadCollectionFuture<'a>> {
+        if self.providers.is_empty() {
+            return Err(LyricsHelperError::ProvidersNotInitialized);
+        }
+
+        let collection_reference = search::reference::parse_collection_reference(collection)
+            .ok_or_else(|| LyricsHelperError::InvalidReference(collection.to_string()))?;
+        let provider_name = collection_reference
+            .platform
+            .provider_name()
+            .ok_or_else(|| LyricsHelperError::ProviderNotSupported(collection.to_string()))?;
+        let provider = self
+            .active_providers()
+            .into_iter()
+            .find(|p| p.name() == provider_name)
+            .ok_or_else(|| LyricsHelperError::ProviderNotSupported(provider_name.to_string()))?;
+
+        let options = options.clone();
+        let policy = self.search_policy.clone();
+
+        Ok(Box::pin(download::download_collection(
+            provider,
+            collection_reference,
+            options,
+            policy,
+            cancellation_token,
+        )))
+    }
+
+    /// 获取最佳的封面。
+    ///
+    /// # 参数
+    /// * `candidates` - 搜索结果候选项列表
+    ///
+    /// # 返回
+    /// * `Some(Vec<u8>)` - 成功获取到的封面数据
+    /// * `None` - 所有候选项都无法提供封面
+    #[must_use]
+    pub fn get_best_cover<'a>(
+        &'a self,
+        candidates: &'a [SearchResult],
+    ) -> Pin<Box<dyn Future<Output = Option<Vec<u8>>> + Send + 'a>> {
+        let providers_map: HashMap<_, _> = self
+            .providers
+            .iter()
+            .map(|p| (p.name(), p.clone()))
+            .collect();
+
+        let candidates = candidates.to_vec();
+
+        Box::pin(async move {
+            let candidates_to_try = candidates.iter().take(5);
 
-        let candidates = candidates.to_vec();
-
-        Box::pin(async move {
-            let candidates_to_try = candidates.iter().take(5);
-
             for candidate in candidates_to_try {
                 if let Some(album_id) = &candidate.album_id
                     && let Some(provider) = providers_map.get(candidate.provider_name.as_str())
                     && let Ok(url) = provider
                         .get_album_cover_url(album_id, CoverSize::Large)
                         .await
-                    && let Some(provider_name_enum) =
-                        ProviderName::try_from_str(&candidate.provider_name)
-                    && let Some(client) = self.http_clients.get(&provider_name_enum)
+                    && let Some(client) = self.http_clients.get(candidate.provider_name.as_str())
                     && let Ok(response) = client.get(&url).await
                     && response.status < 300
                 {
@@ -596,21 +1279,188 @@ impl LyricsHelper {
 
         let amll_client = self
             .http_clients
-            .get(&ProviderName::AmllTtmlDatabase)
+            .get(ProviderName::AmllTtmlDatabase.as_str())
             .ok_or_else(|| {
                 LyricsHelperError::ProviderNotSupported("amll-ttml-database".to_string())
             })?
             .clone();
 
         let new_amll_provider = AmllTtmlDatabase::with_http_client(amll_client).await?;
-        let new_provider_arc = Arc::new(new_amll_provider);
-        self.providers.retain(|p| p.name() != "amll-ttml-database");
-        self.providers.push(new_provider_arc);
+        self.register_provider(Arc::new(new_amll_provider));
         tracing::info!("[LyricsHelper] AMLL 索引更新流程完成。");
         Ok(())
     }
 }
 
+/// 待注册的自定义提供商及其可选的 HTTP 客户端。
+type ProviderRegistration = (Arc<dyn Provider + Send + Sync>, Option<Arc<dyn HttpClient>>);
+
+/// `LyricsHelper` 的构建器。
+///
+/// 默认会加载所有内置提供商，可以通过 [`Self::builtin_providers`] 调整，
+/// 并通过 [`Self::register_provider`] 加入自定义的 `Provider` 实现。
+pub struct LyricsHelperBuilder {
+    builtin_providers: Vec<ProviderName>,
+    custom_providers: Vec<ProviderRegistration>,
+    disabled_providers: HashSet<String>,
+    provider_priorities: HashMap<String, i32>,
+    cache: Option<Arc<ProviderCache>>,
+    http_config: HttpClientConfig,
+    search_policy: SearchPolicy,
+    load_saved_aliases: bool,
+    load_saved_http_client_config: bool,
+}
+
+impl Default for LyricsHelperBuilder {
+    fn default() -> Self {
+        Self {
+            builtin_providers: ProviderName::all(),
+            custom_providers: Vec::new(),
+            disabled_providers: HashSet::new(),
+            provider_priorities: HashMap::new(),
+            cache: None,
+            http_config: HttpClientConfig::default(),
+            search_policy: SearchPolicy::default(),
+            load_saved_aliases: false,
+            load_saved_http_client_config: false,
+        }
+    }
+}
+
+impl LyricsHelperBuilder {
+    /// 只加载指定的内置提供商。
+    #[must_use]
+    pub fn builtin_providers(mut self, providers: Vec<ProviderName>) -> Self {
+        self.builtin_providers = providers;
+        self
+    }
+
+    /// 不加载任何内置提供商，构建时不会发起网络请求。
+    #[must_use]
+    pub fn without_builtin_providers(mut self) -> Self {
+        self.builtin_providers.clear();
+        self
+    }
+
+    /// 注册一个自定义提供商。
+    #[must_use]
+    pub fn register_provider(mut self, provider: Arc<dyn Provider + Send + Sync>) -> Self {
+        self.custom_providers.push((provider, None));
+        self
+    }
+
+    /// 注册一个自定义提供商，并指定获取封面等额外请求时使用的 HTTP 客户端。
+    #[must_use]
+    pub fn register_provider_with_http_client(
+        mut self,
+        provider: Arc<dyn Provider + Send + Sync>,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Self {
+        self.custom_providers.push((provider, Some(http_client)));
+        self
+    }
+
+    /// 禁用指定名称的提供商。
+    #[must_use]
+    pub fn disable_provider(mut self, name: impl Into<String>) -> Self {
+        self.disabled_providers.insert(name.into());
+        self
+    }
+
+    /// 设置提供商的优先级，数值越大越先被搜索。
+    #[must_use]
+    pub fn provider_priority(mut self, name: impl Into<String>, priority: i32) -> Self {
+        self.provider_priorities.insert(name.into(), priority);
+        self
+    }
+
+    /// 为所有提供商启用响应缓存。
+    #[must_use]
+    pub fn cache(mut self, cache: Arc<ProviderCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// 设置内置提供商的网络配置（代理、超时、证书等）。
+    #[must_use]
+    pub fn http_client_config(mut self, config: HttpClientConfig) -> Self {
+        self.http_config = config;
+        self
+    }
+
+    /// 设置搜索歌词时每个提供商的时限和失败处理方式。
+    #[must_use]
+    pub fn search_policy(mut self, policy: SearchPolicy) -> Self {
+        self.search_policy = policy;
+        self
+    }
+
+    /// 构建时读取配置目录中用户维护的别名表，并合并到搜索策略的评分配置中。
+    ///
+    /// 参见 [`config::load_alias_table`]。
+    #[must_use]
+    pub const fn load_saved_aliases(mut self) -> Self {
+        self.load_saved_aliases = true;
+        self
+    }
+
+    /// 构建时读取配置目录中持久化的网络配置，覆盖 [`Self::http_client_config`] 设置的值。
+    ///
+    /// 参见 [`config::load_http_client_config`]。
+    #[must_use]
+    pub const fn load_saved_http_client_config(mut self) -> Self {
+        self.load_saved_http_client_config = true;
+        self
+    }
+
+    /// 构建 `LyricsHelper`，并初始化所有内置提供商。
+    pub async fn build(self) -> Result<LyricsHelper> {
+        let mut helper = LyricsHelper::new();
+        helper.disabled_providers = self.disabled_providers;
+        helper.provider_priorities = self.provider_priorities;
+        helper.cache = self.cache;
+        helper.http_config = self.http_config;
+        helper.search_policy = self.search_policy;
+        if self.load_saved_aliases {
+            helper.load_saved_aliases().await?;
+        }
+        if self.load_saved_http_client_config {
+            helper.load_saved_http_client_config().await?;
+        }
+
+        if !self.builtin_providers.is_empty() {
+            helper
+                .load_builtin_providers(self.builtin_providers)
+                .await?;
+        }
+
+        for (provider, http_client) in self.custom_providers {
+            match http_client {
+                Some(client) => helper.register_provider_with_http_client(provider, client),
+                None => helper.register_provider(provider),
+            }
+        }
+        Ok(helper)
+    }
+}
+
+/// 合并来自多个提供商的结果：按匹配度从高到低排序，并去除重复的条目。
+///
+/// 匹配度相同时保持原有顺序。
+fn merge_search_results(mut results: Vec<SearchResult>) -> Vec<SearchResult> {
+    results.sort_by_key(|r| std::cmp::Reverse(r.match_type));
+    let mut seen_keys = HashSet::new();
+    results.retain(|r| seen_keys.insert((r.provider_name.clone(), r.provider_id.clone())));
+    results
+}
+
+/// 合并按提供商序号标记的结果：匹配度相同时按提供商的优先级排序，而不是按响应的先后。
+fn merge_ranked_results(collected: &[(usize, SearchResult)]) -> Vec<SearchResult> {
+    let mut ranked = collected.to_vec();
+    ranked.sort_by_key(|(index, r)| (std::cmp::Reverse(r.match_type), *index));
+    merge_search_results(ranked.into_iter().map(|(_, r)| r).collect())
+}
+
 /// 根据 `SearchMode` 筛选出要使用的提供商列表。
 fn get_providers_for_mode(
     all_providers: &[Arc<dyn Provider + Send + Sync>],
@@ -644,20 +1494,118 @@ fn get_providers_for_mode(
     })
 }
 
+/// 在时限内执行一个操作，并在取消令牌被触发时提前结束。
+async fn run_with_limits<T>(
+    operation: impl Future<Output = Result<T>>,
+    timeout: Option<Duration>,
+    cancellation_token: Option<&CancellationToken>,
+    description: impl FnOnce() -> String,
+) -> Result<T> {
+    let limited = async {
+        match timeout {
+            Some(timeout) => tokio::time::timeout(timeout, operation)
+                .await
+                .unwrap_or_else(|_| {
+                    Err(LyricsHelperError::Timeout(format!(
+                        "{} 超过了 {timeout:?}",
+                        description()
+                    )))
+                }),
+            None => operation.await,
+        }
+    };
+
+    match cancellation_token {
+        Some(token) => {
+            tokio::select! {
+                () = token.cancelled() => Err(LyricsHelperError::Cancelled),
+                result = limited => result,
+            }
+        }
+        None => limited.await,
+    }
+}
+
+/// 在时限内对单个提供商执行搜索，并生成搜索报告。
+async fn search_provider(
+    provider: &Arc<dyn Provider + Send + Sync>,
+    track_meta: &Track<'_>,
+    scoring: &MatchScoringConfig,
+    timeout: Option<Duration>,
+    cancellation_token: Option<&CancellationToken>,
+) -> (Result<Vec<SearchResult>>, ProviderSearchReport) {
+    let started = Instant::now();
+    let result = run_with_limits(
+        search::search_track_with_scoring(provider.as_ref(), track_meta, true, scoring),
+        timeout,
+        cancellation_token,
+        || format!("提供商 '{}' 的搜索", provider.name()),
+    )
+    .await;
+    let report = ProviderSearchReport {
+        provider: provider.name().to_string(),
+        status: ProviderSearchStatus::from_result(&result),
+        elapsed: started.elapsed(),
+    };
+    (result, report)
+}
+
+/// 在时限内从提供商获取歌词。
+async fn fetch_lyrics(
+    provider: &Arc<dyn Provider + Send + Sync>,
+    song_id: &str,
+    policy: &SearchPolicy,
+    cancellation_token: Option<&CancellationToken>,
+) -> Result<FullLyricsResult> {
+    run_with_limits(
+        provider.get_full_lyrics(song_id),
+        policy.fetch_timeout_for(provider.name()),
+        cancellation_token,
+        || format!("从提供商 '{}' 获取歌词", provider.name()),
+    )
+    .await
+}
+
+/// 获取搜索结果对应的歌词，并记录搜索结果中已有的歌曲和专辑 ID。
+async fn fetch_candidate_lyrics(
+    provider: &Arc<dyn Provider + Send + Sync>,
+    candidate: &SearchResult,
+    policy: &SearchPolicy,
+    cancellation_token: Option<&CancellationToken>,
+) -> Result<FullLyricsResult> {
+    let mut lyrics =
+        fetch_lyrics(provider, &candidate.provider_id, policy, cancellation_token).await?;
+    providers::record_source_ids(
+        &mut lyrics.parsed,
+        &candidate.provider_name,
+        &candidate.provider_id,
+        candidate.album_id.as_deref(),
+    );
+    Ok(lyrics)
+}
+
 /// 对单个提供商执行搜索并获取操作。
 async fn search_and_fetch_from_provider(
     provider: &Arc<dyn Provider + Send + Sync>,
     track_meta: &Track<'_>,
+    policy: &SearchPolicy,
 ) -> Result<Option<LyricsAndMetadata>> {
-    let search_results = search::search_track(provider.as_ref(), track_meta, true).await?;
-    if let Some(best_match) = search_results.first() {
+    let (search_results, _) = search_provider(
+        provider,
+        track_meta,
+        &policy.match_scoring,
+        policy.search_timeout_for(provider.name()),
+        None,
+    )
+    .await;
+    if let Some(best_match) = search_results?.first() {
         tracing::info!(
             "在提供商 '{}' 中找到匹配项: '{}' (ID: {}), 正在尝试获取歌词...",
             provider.name(),
             best_match.title,
             best_match.provider_id
         );
-        return match provider.get_full_lyrics(&best_match.provider_id).await {
+        return match fetch_candidate_lyrics(provider, best_match, policy, None).await {
             Ok(lyrics_data) => Ok(Some(LyricsAndMetadata {
                 lyrics: lyrics_data,
                 source_track: best_match.clone(),
@@ -688,38 +1636,87 @@ async fn search_and_fetch_from_provider(
 async fn search_ordered(
     providers: &[Arc<dyn Provider + Send + Sync>],
     track_meta: &Track<'_>,
+    policy: &SearchPolicy,
 ) -> Result<Option<LyricsAndMetadata>> {
     for provider in providers {
         tracing::debug!("正在尝试提供商: '{}'", provider.name());
-        match search_and_fetch_from_provider(provider, track_meta).await {
+        match search_and_fetch_from_provider(provider, track_meta, policy).await {
             Ok(Some(lyrics_result)) => {
                 tracing::info!("在 '{}' 成功获取到歌词，搜索结束。", provider.name());
                 return Ok(Some(lyrics_result));
             }
             Ok(None) => {}
-            Err(e) => return Err(e), // 发生网络错误等
+            Err(e) if policy.is_fail_fast() => return Err(e),
+            Err(e) => {
+                tracing::warn!(
+                    "提供商 '{}' 不可用: {}，尝试下一个提供商。",
+                    provider.name(),
+                    e
+                );
+            }
         }
     }
     tracing::info!("所有指定提供商都未能找到歌词。");
     Ok(None)
 }
 
-async fn search_lyrics_parallel(
+/// 并发搜索所有提供商，按策略处理失败的提供商，返回合并后的候选项和每个提供商的报告。
+async fn search_all_providers(
     providers: &[Arc<dyn Provider + Send + Sync>],
     track_meta: &Track<'_>,
-) -> Result<Option<LyricsAndMetadata>> {
-    let search_futures = providers
+    policy: &SearchPolicy,
+    cancellation_token: Option<&CancellationToken>,
+) -> Result<(Vec<SearchResult>, Vec<ProviderSearchReport>)> {
+    // 按完成顺序处理，FailFast 时第一个错误出现就返回，丢弃仍在进行的搜索
+    let mut pending: FuturesUnordered<_> = providers
         .iter()
-        .map(|provider| search::search_track(provider.as_ref(), track_meta, true));
-
-    let all_results: Vec<SearchResult> = future::join_all(search_futures)
-        .await
-        .into_iter()
-        .filter_map(Result::ok)
-        .flatten()
+        .enumerate()
+        .map(|(index, provider)| async move {
+            let outcome = search_provider(
+                provider,
+                track_meta,
+                &policy.match_scoring,
+                policy.search_timeout_for(provider.name()),
+                cancellation_token,
+            )
+            .await;
+            (index, outcome)
+        })
         .collect();
-    let mut sorted_results = all_results;
-    sorted_results.sort_by(|a, b| b.match_type.cmp(&a.match_type));
+
+    let mut outcomes = Vec::with_capacity(providers.len());
+    while let Some((index, (result, report))) = pending.next().await {
+        let results = match result {
+            Ok(results) => results,
+            Err(LyricsHelperError::Cancelled) => return Err(LyricsHelperError::Cancelled),
+            Err(e) if policy.is_fail_fast() => return Err(e),
+            Err(e) => {
+                tracing::warn!("提供商 '{}' 的搜索失败: {}，将忽略。", report.provider, e);
+                Vec::new()
+            }
+        };
+        outcomes.push((index, results, report));
+    }
+
+    // 恢复提供商的顺序，使匹配度相同的候选项和报告的顺序保持稳定
+    outcomes.sort_by_key(|(index, ..)| *index);
+    let mut candidates = Vec::new();
+    let mut reports = Vec::with_capacity(outcomes.len());
+    for (_, results, report) in outcomes {
+        candidates.extend(results);
+        reports.push(report);
+    }
+
+    candidates.sort_by_key(|r| std::cmp::Reverse(r.match_type));
+    Ok((candidates, reports))
+}
+
+async fn search_lyrics_parallel(
+    providers: &[Arc<dyn Provider + Send + Sync>],
+    track_meta: &Track<'_>,
+    policy: &SearchPolicy,
+) -> Result<Option<LyricsAndMetadata>> {
+    let (sorted_results, _) = search_all_providers(providers, track_meta, policy, None).await?;
 
     if let Some(best_match) = sorted_results.first() {
         tracing::info!(
@@ -733,7 +1730,7 @@ async fn search_lyrics_parallel(
             .iter()
             .find(|p| p.name() == best_match.provider_name)
         {
-            match provider.get_full_lyrics(&best_match.provider_id).await {
+            match fetch_candidate_lyrics(provider, best_match, policy, None).await {
                 Ok(lyrics_data) => Ok(Some(LyricsAndMetadata {
                     lyrics: lyrics_data,
                     source_track: best_match.clone(),
@@ -755,31 +1752,252 @@ async fn search_lyrics_parallel(
     }
 }
 
-async fn search_comprehensive_unified(
+/// 同时获取若干候选项的歌词，返回综合得分最高者的序号、歌词和质量评估。
+async fn fetch_best_ranked_lyrics(
     providers: &[Arc<dyn Provider + Send + Sync>],
-    track_meta: &Track<'_>,
-    cancellation_token: Option<CancellationToken>,
-) -> Result<Option<ComprehensiveSearchResult>> {
-    let search_futures = providers
-        .iter()
-        .map(|provider| search::search_track(provider.as_ref(), track_meta, true));
+    contenders: &[SearchResult],
+    policy: &SearchPolicy,
+    cancellation_token: Option<&CancellationToken>,
+) -> Result<Option<(usize, FullLyricsResult, LyricsQuality)>> {
+    let fetches = contenders.iter().map(|candidate| async move {
+        let provider = providers
+            .iter()
+            .find(|p| p.name() == candidate.provider_name)?;
+        Some(fetch_candidate_lyrics(provider, candidate, policy, cancellation_token).await)
+    });
+
+    let mut best: Option<(f64, usize, FullLyricsResult, LyricsQuality)> = None;
+    for (index, outcome) in future::join_all(fetches).await.into_iter().enumerate() {
+        let candidate = &contenders[index];
+        match outcome {
+            Some(Ok(lyrics_data)) => {
+                let quality =
+                    search::quality::assess_lyrics_quality(&lyrics_data.parsed, candidate.duration);
+                let score = policy
+                    .ranking
+                    .combined_score(candidate.match_type, &quality);
+                tracing::debug!(
+                    "候选项 '{}' ({}) 的歌词质量为 {:.1}，综合得分 {:.3}",
+                    candidate.title,
+                    candidate.provider_name,
+                    quality.score,
+                    score
+                );
+                if best
+                    .as_ref()
+                    .is_none_or(|(best_score, ..)| score > *best_score)
+                {
+                    best = Some((score, index, lyrics_data, quality));
+                }
+            }
+            Some(Err(LyricsHelperError::LyricNotFound)) | None => {
+                tracing::info!("候选项 '{}' 无歌词，尝试下一个。", candidate.title);
+            }
+            Some(Err(LyricsHelperError::Cancelled)) => return Err(LyricsHelperError::Cancelled),
+            Some(Err(e)) if policy.is_fail_fast() => return Err(e),
+            Some(Err(e)) => {
+                tracing::warn!(
+                    "从 '{}' 获取歌词失败: {}，尝试下一个。",
+                    candidate.provider_name,
+                    e
+                );
+            }
+        }
+    }
 
-    let all_candidates: Vec<SearchResult> = future::join_all(search_futures)
-        .await
-        .into_iter()
-        .filter_map(Result::ok)
-        .flatten()
-        .collect();
+    Ok(best.map(|(_, index, lyrics_data, quality)| (index, lyrics_data, quality)))
+}
 
-    let mut sorted_candidates = all_candidates;
-    sorted_candidates.sort_by(|a, b| b.match_type.cmp(&a.match_type));
+/// 先在跨平台 ID 映射中查找歌曲引用，再尝试从引用所属的提供商直接获取歌词。
+async fn resolve_song_reference(
+    providers: &[Arc<dyn Provider + Send + Sync>],
+    reference: &SongReference,
+    policy: &SearchPolicy,
+) -> Result<Option<LyricsAndMetadata>> {
+    for provider in providers {
+        let candidates = match provider.find_by_reference(reference).await {
+            Ok(candidates) => candidates,
+            Err(e) if policy.is_fail_fast() => return Err(e),
+            Err(e) => {
+                tracing::warn!(
+                    "在 '{}' 中查找 {:?} 失败: {}",
+                    provider.name(),
+                    reference,
+                    e
+                );
+                continue;
+            }
+        };
+        for candidate in candidates {
+            match fetch_candidate_lyrics(provider, &candidate, policy, None).await {
+                Ok(lyrics) => {
+                    return Ok(Some(LyricsAndMetadata {
+                        lyrics,
+                        source_track: candidate,
+                    }));
+                }
+                Err(LyricsHelperError::LyricNotFound) => {}
+                Err(e) if policy.is_fail_fast() => return Err(e),
+                Err(e) => {
+                    tracing::warn!("从 '{}' 获取歌词失败: {}，尝试下一个。", provider.name(), e);
+                }
+            }
+        }
+    }
+
+    let Some(provider) = reference
+        .platform
+        .provider_name()
+        .and_then(|name| providers.iter().find(|p| p.name() == name))
+    else {
+        return Ok(None);
+    };
+
+    let (lyrics, song) = future::join(
+        fetch_lyrics(provider, &reference.id, policy, None),
+        provider.get_song_info(&reference.id),
+    )
+    .await;
+    let mut lyrics = match lyrics {
+        Ok(lyrics) => lyrics,
+        Err(LyricsHelperError::LyricNotFound) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let source_track = song.map_or_else(
+        |e| {
+            tracing::debug!("获取 {:?} 的歌曲信息失败: {}", reference, e);
+            SearchResult::default()
+        },
+        song_to_search_result,
+    );
+    let source_track = SearchResult {
+        provider_id: reference.id.clone(),
+        provider_name: provider.name().to_string(),
+        provider_id_num: reference.id.parse().ok(),
+        match_type: MatchType::Perfect,
+        ..source_track
+    };
+    providers::record_source_ids(
+        &mut lyrics.parsed,
+        &source_track.provider_name,
+        &source_track.provider_id,
+        source_track.album_id.as_deref(),
+    );
+
+    Ok(Some(LyricsAndMetadata {
+        lyrics,
+        source_track,
+    }))
+}
+
+fn song_to_search_result(song: generic::Song) -> SearchResult {
+    SearchResult {
+        title: song.name,
+        artists: song.artists,
+        album: song.album,
+        album_id: song.album_id,
+        duration: song
+            .duration
+            .map(|d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX)),
+        cover_url: song.cover_url,
+        ..Default::default()
+    }
+}
+
+/// 同时获取各候选项的歌词，作为合成的来源。
+async fn fetch_composition_sources(
+    providers: &[Arc<dyn Provider + Send + Sync>],
+    candidates: &[SearchResult],
+    policy: &SearchPolicy,
+    cancellation_token: Option<&CancellationToken>,
+) -> Result<Vec<ParsedSourceData>> {
+    let fetches = candidates.iter().map(|candidate| async move {
+        let provider = providers
+            .iter()
+            .find(|p| p.name() == candidate.provider_name)?;
+        Some(fetch_candidate_lyrics(provider, candidate, policy, cancellation_token).await)
+    });
+
+    let mut sources = Vec::new();
+    for (candidate, outcome) in candidates.iter().zip(future::join_all(fetches).await) {
+        match outcome {
+            Some(Ok(lyrics_data)) => {
+                let mut parsed = lyrics_data.parsed;
+                if parsed.source_name.is_empty() {
+                    parsed.source_name.clone_from(&candidate.provider_name);
+                }
+                sources.push(parsed);
+            }
+            Some(Err(LyricsHelperError::LyricNotFound)) | None => {
+                tracing::info!("候选项 '{}' 无歌词，跳过。", candidate.title);
+            }
+            Some(Err(LyricsHelperError::Cancelled)) => return Err(LyricsHelperError::Cancelled),
+            Some(Err(e)) if policy.is_fail_fast() => return Err(e),
+            Some(Err(e)) => {
+                tracing::warn!(
+                    "从 '{}' 获取歌词失败: {}，跳过。",
+                    candidate.provider_name,
+                    e
+                );
+            }
+        }
+    }
+    Ok(sources)
+}
+
+async fn search_comprehensive_unified(
+    providers: &[Arc<dyn Provider + Send + Sync>],
+    track_meta: &Track<'_>,
+    policy: &SearchPolicy,
+    cancellation_token: Option<&CancellationToken>,
+) -> Result<ComprehensiveSearchReport> {
+    let (sorted_candidates, reports) =
+        search_all_providers(providers, track_meta, policy, cancellation_token).await?;
 
     if sorted_candidates.is_empty() {
         tracing::info!("所有提供商都未找到任何搜索结果。");
-        return Ok(None);
+        return Ok(ComprehensiveSearchReport {
+            result: None,
+            providers: reports,
+        });
     }
 
-    for candidate in &sorted_candidates {
+    let ranking = &policy.ranking;
+    let contender_count = sorted_candidates
+        .iter()
+        .take(ranking.max_candidates.max(1))
+        .take_while(|c| c.match_type >= ranking.min_match_type)
+        .count();
+
+    // 先同时获取匹配度最高的几个候选项的歌词，按匹配度和歌词质量挑选
+    if let Some((index, lyrics_data, quality)) = fetch_best_ranked_lyrics(
+        providers,
+        &sorted_candidates[..contender_count],
+        policy,
+        cancellation_token,
+    )
+    .await?
+    {
+        let candidate = sorted_candidates[index].clone();
+        tracing::info!(
+            "成功获取到歌词。最佳结果来自 '{}': '{}'",
+            candidate.provider_name,
+            candidate.title
+        );
+        return Ok(ComprehensiveSearchReport {
+            result: Some(ComprehensiveSearchResult {
+                primary_lyric_result: LyricsAndMetadata {
+                    lyrics: lyrics_data,
+                    source_track: candidate,
+                },
+                all_search_candidates: sorted_candidates,
+                primary_quality: Some(quality),
+            }),
+            providers: reports,
+        });
+    }
+
+    for candidate in &sorted_candidates[contender_count..] {
         tracing::debug!(
             "尝试从 '{}' 获取歌词: '{}' (ID: {})",
             candidate.provider_name,
@@ -791,20 +2009,7 @@ async fn search_comprehensive_unified(
             .iter()
             .find(|p| p.name() == candidate.provider_name)
         {
-            let fetch_future = provider.get_full_lyrics(&candidate.provider_id);
-
-            let lyrics_result = if let Some(token) = &cancellation_token {
-                tokio::select! {
-                    () = token.cancelled() => {
-                        Err(LyricsHelperError::Cancelled)
-                    }
-                    res = fetch_future => res,
-                }
-            } else {
-                fetch_future.await
-            };
-
-            match lyrics_result {
+            match fetch_candidate_lyrics(provider, candidate, policy, cancellation_token).await {
                 Ok(lyrics_data) => {
                     tracing::info!(
                         "成功获取到歌词。最佳匹配项来自 '{}': '{}'",
@@ -812,13 +2017,17 @@ async fn search_comprehensive_unified(
                         candidate.title
                     );
 
-                    return Ok(Some(ComprehensiveSearchResult {
-                        primary_lyric_result: LyricsAndMetadata {
-                            lyrics: lyrics_data,
-                            source_track: candidate.clone(),
-                        },
-                        all_search_candidates: sorted_candidates,
-                    }));
+                    return Ok(ComprehensiveSearchReport {
+                        result: Some(ComprehensiveSearchResult {
+                            primary_lyric_result: LyricsAndMetadata {
+                                lyrics: lyrics_data,
+                                source_track: candidate.clone(),
+                            },
+                            all_search_candidates: sorted_candidates,
+                            primary_quality: None,
+                        }),
+                        providers: reports,
+                    });
                 }
                 Err(LyricsHelperError::LyricNotFound) => {
                     tracing::info!("候选项 '{}' 无歌词，尝试下一个。", candidate.title);
@@ -826,6 +2035,7 @@ async fn search_comprehensive_unified(
                 Err(LyricsHelperError::Cancelled) => {
                     return Err(LyricsHelperError::Cancelled);
                 }
+                Err(e) if policy.is_fail_fast() => return Err(e),
                 Err(e) => {
                     tracing::warn!(
                         "从 '{}' 获取歌词失败: {}，尝试下一个。",
@@ -838,7 +2048,367 @@ async fn search_comprehensive_unified(
     }
 
     tracing::info!("所有候选项都无法提供歌词。");
-    Ok(None)
+    Ok(ComprehensiveSearchReport {
+        result: None,
+        providers: reports,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use lyrics_helper_core::{FullLyricsResult, ParsedSourceData};
+
+    use super::*;
+    use crate::providers::mock::{MockProvider, search_result};
+
+    /// 搜索时返回一条 "Song A" 结果的提供商。
+    fn mock(name: &'static str) -> MockProvider {
+        mock_with_lyrics(name, "Song A", ParsedSourceData::default())
+    }
+
+    fn mock_with_lyrics(name: &'static str, title: &str, lyrics: ParsedSourceData) -> MockProvider {
+        MockProvider::new(name)
+            .with_results(vec![search_result(
+                name,
+                &format!("{name}_1"),
+                title,
+                "Artist A",
+            )])
+            .with_lyrics(move |_| {
+                Ok(FullLyricsResult {
+                    parsed: lyrics.clone(),
+                    ..Default::default()
+                })
+            })
+    }
+
+    const TRACK: Track<'static> = Track {
+        title: Some("Song A"),
+        artists: Some(&["Artist A"]),
+        album: None,
+        duration: None,
+        version_hints: None,
+    };
+
+    async fn build_helper() -> LyricsHelper {
+        LyricsHelper::builder()
+            .without_builtin_providers()
+            .register_provider(Arc::new(mock("first")))
+            .register_provider(Arc::new(mock("second")))
+            .register_provider(Arc::new(mock("third")))
+            .build()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_priority_and_enabled_state_control_search_order() {
+        let mut helper = build_helper().await;
+        assert_eq!(helper.provider_names(), ["first", "second", "third"]);
+
+        helper.set_provider_priority("third", 10);
+        helper.set_provider_enabled("first", false);
+        assert_eq!(helper.provider_names(), ["third", "second"]);
+        assert!(!helper.is_provider_enabled("first"));
+
+        let result = helper
+            .search_lyrics(&TRACK, SearchMode::Ordered)
+            .unwrap()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.source_track.provider_name, "third");
+    }
+
+    #[tokio::test]
+    async fn test_get_lyrics_uses_provider_cache() {
+        let dir = std::env::temp_dir().join(format!("provider-cache-{}", uuid::Uuid::new_v4()));
+        let cache = Arc::new(ProviderCache::with_dir(
+            &dir,
+            cache::CacheOptions::default(),
+        ));
+        let mut helper = build_helper().await;
+        helper.set_cache(Some(cache.clone()));
+
+        // MockProvider 未实现 get_lyrics，能成功说明请求经过了缓存层
+        helper
+            .get_lyrics("first", "first_1")
+            .unwrap()
+            .await
+            .unwrap();
+        assert!(cache.get_lyrics("first", "first_1").await.is_some());
+
+        cache.set_offline_only(true);
+        assert!(helper.get_lyrics("first", "first_1").unwrap().await.is_ok());
+        assert!(
+            helper
+                .get_full_lyrics("first", "first_1")
+                .unwrap()
+                .await
+                .is_ok()
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_custom_provider_in_subset_mode() {
+        let mut helper = build_helper().await;
+        let mode = SearchMode::Subset(vec![ProviderName::custom("second"), ProviderName::Netease]);
+        let result = helper
+            .search_lyrics(&TRACK, mode)
+            .unwrap()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.source_track.provider_name, "second");
+
+        assert!(helper.unregister_provider("second").is_some());
+        assert!(matches!(
+            helper.search_lyrics(&TRACK, SearchMode::Specific(ProviderName::custom("second"))),
+            Err(LyricsHelperError::ProviderNotSupported(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_register_provider_replaces_same_name() {
+        let mut helper = build_helper().await;
+        helper.register_provider(Arc::new(mock("first")));
+        assert_eq!(helper.provider_names(), ["first", "second", "third"]);
+        assert_eq!(ProviderName::from_name("qq"), ProviderName::QQMusic);
+        assert!(!ProviderName::from_name("first").is_builtin());
+        assert!("first".parse::<ProviderName>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_track_stream_yields_as_providers_respond() {
+        let helper = LyricsHelper::builder()
+            .without_builtin_providers()
+            .register_provider(Arc::new(
+                mock("slow").with_delay(std::time::Duration::from_millis(50)),
+            ))
+            .register_provider(Arc::new(mock("broken").failing()))
+            .register_provider(Arc::new(mock("fast")))
+            .build()
+            .await
+            .unwrap();
+
+        let events: Vec<_> = helper.search_track_stream(&TRACK).unwrap().collect().await;
+
+        let order: Vec<&str> = events
+            .iter()
+            .filter_map(|event| match event {
+                ProviderSearchEvent::ProviderResults { provider, .. }
+                | ProviderSearchEvent::ProviderFailed { provider, .. } => Some(provider.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(order.last(), Some(&"slow"));
+        assert!(order.contains(&"broken"));
+
+        let rankings: Vec<usize> = events
+            .iter()
+            .filter_map(|event| match event {
+                ProviderSearchEvent::Ranking(results) => Some(results.len()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(rankings, [1, 2]);
+
+        let Some(ProviderSearchEvent::Finished(results)) = events.last() else {
+            panic!("最后一个事件应为 Finished");
+        };
+        // 结果按提供商的优先级排列，而不是按响应的先后
+        let providers: Vec<&str> = results.iter().map(|r| r.provider_name.as_str()).collect();
+        assert_eq!(providers, ["slow", "fast"]);
+        assert_eq!(helper.search_track(&TRACK).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_comprehensive_search_reports_unavailable_providers() {
+        let mut helper = LyricsHelper::builder()
+            .without_builtin_providers()
+            .register_provider(Arc::new(
+                mock("slow").with_delay(std::time::Duration::from_secs(5)),
+            ))
+            .register_provider(Arc::new(mock("broken").failing()))
+            .register_provider(Arc::new(mock("healthy")))
+            .search_policy(SearchPolicy {
+                search_timeout: Some(std::time::Duration::from_millis(20)),
+                ..Default::default()
+            })
+            .build()
+            .await
+            .unwrap();
+
+        let report = helper
+            .search_lyrics_comprehensive_with_report(&TRACK, &SearchMode::Parallel, None)
+            .unwrap()
+            .await
+            .unwrap();
+        let result = report.result.as_ref().unwrap();
+        assert_eq!(
+            result.primary_lyric_result.source_track.provider_name,
+            "healthy"
+        );
+
+        let status_of = |name: &str| {
+            report
+                .providers
+                .iter()
+                .find(|r| r.provider == name)
+                .map(|r| r.status.clone())
+                .unwrap()
+        };
+        assert_eq!(status_of("slow"), ProviderSearchStatus::TimedOut);
+        assert!(matches!(
+            status_of("broken"),
+            ProviderSearchStatus::Failed(_)
+        ));
+        assert_eq!(status_of("healthy"), ProviderSearchStatus::Found(1));
+        assert_eq!(report.unavailable_providers().count(), 2);
+
+        // 有序模式下，出错的提供商不会再中断整个搜索
+        helper.set_provider_priority("broken", 10);
+        let ordered = helper
+            .search_lyrics(&TRACK, SearchMode::Ordered)
+            .unwrap()
+            .await
+            .unwrap();
+        assert_eq!(ordered.unwrap().source_track.provider_name, "healthy");
+
+        helper.set_search_policy(SearchPolicy {
+            search_timeout: Some(std::time::Duration::from_millis(20)),
+            failure_policy: FailurePolicy::FailFast,
+            ..Default::default()
+        });
+        let fail_fast = helper
+            .search_lyrics_comprehensive(&TRACK, &SearchMode::Parallel, None)
+            .unwrap()
+            .await;
+        assert!(matches!(
+            fail_fast,
+            Err(LyricsHelperError::Timeout(_) | LyricsHelperError::Http(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fail_fast_returns_before_slow_provider_finishes() {
+        let helper = LyricsHelper::builder()
+            .without_builtin_providers()
+            .register_provider(Arc::new(
+                mock("slow").with_delay(std::time::Duration::from_secs(30)),
+            ))
+            .register_provider(Arc::new(mock("broken").failing()))
+            .search_policy(SearchPolicy {
+                failure_policy: FailurePolicy::FailFast,
+                ..Default::default()
+            })
+            .build()
+            .await
+            .unwrap();
+
+        // 慢速提供商没有时限，FailFast 必须在它完成之前就返回
+        let result = tokio::time::timeout(
+            std::time::Duration::from_secs(2),
+            helper
+                .search_lyrics_comprehensive(&TRACK, &SearchMode::Parallel, None)
+                .unwrap(),
+        )
+        .await
+        .expect("FailFast 应在慢速提供商完成前返回");
+        assert!(matches!(result, Err(LyricsHelperError::Http(_))));
+    }
+
+    fn mock_lyrics(word_timed: bool) -> ParsedSourceData {
+        let line = |i: u64| {
+            let syllables = ["hello", "world"]
+                .iter()
+                .enumerate()
+                .map(|(j, text)| {
+                    let start = i * 5000 + u64::try_from(j).unwrap() * 500;
+                    lyrics_helper_core::LyricSyllable {
+                        text: (*text).to_string(),
+                        start_ms: start,
+                        end_ms: if word_timed { start + 500 } else { start },
+                        ends_with_space: true,
+                        ..Default::default()
+                    }
+                })
+                .collect();
+            let mut track = lyrics_helper_core::AnnotatedTrack {
+                content: lyrics_helper_core::LyricTrack {
+                    words: vec![lyrics_helper_core::Word {
+                        syllables,
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            if word_timed {
+                track.add_translation("你好世界", "zh-Hans");
+            }
+            lyrics_helper_core::LyricLine {
+                tracks: vec![track],
+                ..Default::default()
+            }
+        };
+        ParsedSourceData {
+            lines: (0..20).map(line).collect(),
+            is_line_timed_source: !word_timed,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_comprehensive_search_prefers_richer_lyrics() {
+        let helper = LyricsHelper::builder()
+            .without_builtin_providers()
+            .register_provider(Arc::new(mock_with_lyrics(
+                "line_timed",
+                "Song A",
+                mock_lyrics(false),
+            )))
+            .register_provider(Arc::new(mock_with_lyrics(
+                "word_timed",
+                "Song A!",
+                mock_lyrics(true),
+            )))
+            .build()
+            .await
+            .unwrap();
+
+        let result = helper
+            .search_lyrics_comprehensive(&TRACK, &SearchMode::Parallel, None)
+            .unwrap()
+            .await
+            .unwrap()
+            .unwrap();
+        let source = &result.primary_lyric_result.source_track;
+        assert_eq!(source.provider_name, "word_timed");
+        assert!(source.match_type < result.all_search_candidates[0].match_type);
+        assert!(result.primary_quality.unwrap().word_timed_coverage > 0.99);
+
+        // 只按匹配度挑选时回到匹配度最高的候选项
+        let mut helper = helper;
+        helper.set_search_policy(SearchPolicy {
+            ranking: LyricsRankingPolicy {
+                quality_weight: 0.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let result = helper
+            .search_lyrics_comprehensive(&TRACK, &SearchMode::Parallel, None)
+            .unwrap()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            result.primary_lyric_result.source_track.provider_name,
+            "line_timed"
+        );
+    }
 }
 
 #[cfg(test)]
@@ -875,6 +2445,7 @@ mod integration_tests {
             artists: Some(&["Yunomi", "nicamoq"]),
             album: Some("インドア系ならトラックメイカー"),
             duration: None,
+            version_hints: None,
         };
 
         let search_results = helper
@@ -989,6 +2560,7 @@ mod integration_tests {
             artists: Some(&["小蓝背心"]),
             album: None,
             duration: None,
+            version_hints: None,
         };
 
         let result = helper
@@ -1017,6 +2589,7 @@ mod integration_tests {
             artists: Some(&["小蓝背心"]),
             album: None,
             duration: None,
+            version_hints: None,
         };
 
         let result = helper
@@ -1045,6 +2618,7 @@ mod integration_tests {
             artists: Some(&["小蓝背心"]),
             album: Some("星夏"),
             duration: None,
+            version_hints: None,
         };
 
         let provider_to_test = ProviderName::AmllTtmlDatabase;
@@ -1079,6 +2653,7 @@ mod integration_tests {
             artists: Some(&["小蓝背心"]),
             album: None,
             duration: None,
+            version_hints: None,
         };
 
         let providers_to_test = vec![ProviderName::Netease, ProviderName::QQMusic];
@@ -1,42 +1,7 @@
 //! 定义了用于匹配度量和评分的数据结构。
 
 use lyrics_helper_core::MatchType;
-
-/// 名称（标题/专辑）的匹配程度。
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
-pub(crate) enum NameMatchType {
-    #[default]
-    NoMatch,
-    Low,
-    Medium,
-    High,
-    VeryHigh,
-    Perfect,
-}
-
-/// 艺术家列表的匹配程度。
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
-pub(crate) enum ArtistMatchType {
-    #[default]
-    NoMatch,
-    Low,
-    Medium,
-    High,
-    VeryHigh,
-    Perfect,
-}
-
-/// 时长的匹配程度。
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
-pub(crate) enum DurationMatchType {
-    #[default]
-    NoMatch,
-    Low,
-    Medium,
-    High,
-    VeryHigh,
-    Perfect,
-}
+pub use lyrics_helper_core::{ArtistMatchType, DurationMatchType, NameMatchType};
 
 /// 将匹配结果转换为可计算的分数。
 pub(crate) trait MatchScorable {
@@ -63,7 +28,7 @@ impl MatchScorable for NameMatchType {
         match self {
             Self::Perfect => 7,
             Self::VeryHigh => 6,
-            Self::High => 5,
+            Self::High | Self::Transliterated => 5,
             Self::Medium => 4,
             Self::Low => 2,
             Self::NoMatch => 0,
@@ -76,7 +41,7 @@ impl MatchScorable for ArtistMatchType {
         match self {
             Self::Perfect => 7,
             Self::VeryHigh => 6,
-            Self::High => 5,
+            Self::High | Self::Transliterated => 5,
             Self::Medium => 4,
             Self::Low => 2,
             Self::NoMatch => 0,
@@ -16,11 +16,16 @@ use crate::{
 };
 
 use lyrics_helper_core::{
-    ConversionInput, ConversionOptions, CoverSize, FullLyricsResult, InputFile, LyricFormat,
-    MatchType, ParsedSourceData, RawLyrics, SearchResult, Track, model::generic,
+    CanonicalMetadataKey, ContributorRole, ConversionInput, ConversionOptions, CoverSize,
+    FullLyricsResult, InputFile, LyricContributor, LyricFormat, MatchType, MetadataStore,
+    ParsedSourceData, RawLyrics, SearchResult, SongReference, Track, model::generic,
 };
 
+mod search_index;
+pub mod snapshot;
 mod types;
+use search_index::{AmllSearchIndex, IndexField};
+use snapshot::LocalSnapshot;
 use types::IndexEntry;
 
 const GITHUB_API_BASE_URL: &str = "https://api.github.com";
@@ -39,11 +44,22 @@ struct GitHubCommitInfo {
     sha: String,
 }
 
+/// 歌词文件的来源。
+enum LyricsSource {
+    /// 通过 HTTP 从 GitHub 或镜像下载。`url_template` 包含 `{song_id}` 占位符。
+    Remote {
+        http_client: Arc<dyn HttpClient>,
+        url_template: String,
+    },
+    /// 从本地快照读取。
+    Local(LocalSnapshot),
+}
+
 /// AMLL TTML Database 提供商的实现。
 pub struct AmllTtmlDatabase {
     index: Arc<Vec<IndexEntry>>,
-    http_client: Arc<dyn HttpClient>,
-    lyrics_url_template: String,
+    search_index: Arc<AmllSearchIndex>,
+    lyrics_source: LyricsSource,
 }
 
 #[async_trait]
@@ -56,12 +72,12 @@ impl Provider for AmllTtmlDatabase {
     where
         Self: Sized,
     {
-        let config = crate::config::load_amll_config().unwrap_or_else(|e| {
+        let config = crate::config::load_amll_config().await.unwrap_or_else(|e| {
             tracing::error!("[AMLL] 加载 AMLL 镜像配置失败: {}. 使用默认 GitHub 源。", e);
             crate::config::AmllConfig::default()
         });
 
-        let (index_url, lyrics_url_template) = match &config.mirror {
+        let (index_url, lyrics_url_template) = match config.mirror {
             AmllMirror::GitHub => (
                 format!(
                     "{RAW_CONTENT_BASE_URL}/{REPO_OWNER}/{REPO_NAME}/{REPO_BRANCH}/{INDEX_FILE_PATH_IN_REPO}"
@@ -83,7 +99,16 @@ impl Provider for AmllTtmlDatabase {
             AmllMirror::Custom {
                 index_url,
                 lyrics_url_template,
-            } => (index_url.clone(), lyrics_url_template.clone()),
+            } => (index_url, lyrics_url_template),
+            AmllMirror::LocalPath { path } => {
+                tracing::info!("[AMLL] 使用本地快照: {}", path.display());
+                // 打开快照时可能需要解压整个归档并构建检索索引，放到阻塞线程池中进行
+                return tokio::task::spawn_blocking(move || {
+                    LocalSnapshot::open(path).and_then(Self::from_snapshot)
+                })
+                .await
+                .map_err(|e| LyricsHelperError::Internal(format!("打开本地快照失败: {e}")))?;
+            }
         };
 
         let remote_head_result = fetch_remote_index_head(http_client.as_ref()).await;
@@ -102,21 +127,23 @@ impl Provider for AmllTtmlDatabase {
 
         let index_entries_result = load_index_from_cache();
 
-        let index_entries = match (should_update, remote_head, index_entries_result) {
+        let (index_entries, index_head) = match (should_update, remote_head, index_entries_result) {
             (false, _, Ok(entries)) => {
                 tracing::info!("[AMLL] 索引缓存有效，从本地加载...");
-                entries
+                (entries, load_cached_index_head())
             }
             (true, Some(sha), _) | (false, Some(sha), Err(_)) => {
                 tracing::info!(
                     "[AMLL] 索引需要更新或本地缓存不可用，正在从 {} 下载...",
                     index_url
                 );
-                download_and_parse_index(&sha, http_client.as_ref(), &index_url).await?
+                let entries =
+                    download_and_parse_index(&sha, http_client.as_ref(), &index_url).await?;
+                (entries, Some(sha))
             }
             (true, None, Ok(entries)) => {
                 tracing::warn!("[AMLL] 无法检查更新，将使用可能已过期的本地缓存。");
-                entries
+                (entries, load_cached_index_head())
             }
             (_, _, Err(_)) => {
                 return Err(LyricsHelperError::Internal(
@@ -126,10 +153,14 @@ impl Provider for AmllTtmlDatabase {
         };
 
         tracing::info!("[AMLL] 索引加载完成，共 {} 条记录。", index_entries.len());
+        let search_index = AmllSearchIndex::load_or_build(&index_entries, index_head.as_deref())?;
         Ok(Self {
             index: Arc::new(index_entries),
-            http_client,
-            lyrics_url_template,
+            search_index: Arc::new(search_index),
+            lyrics_source: LyricsSource::Remote {
+                http_client,
+                url_template: lyrics_url_template,
+            },
         })
     }
 
@@ -138,7 +169,7 @@ impl Provider for AmllTtmlDatabase {
             return Ok(vec![]);
         };
 
-        let candidates = find_candidates(&self.index, title_to_search);
+        let candidates = self.find_candidates(title_to_search, track);
 
         if candidates.is_empty() {
             return Ok(vec![]);
@@ -166,20 +197,56 @@ impl Provider for AmllTtmlDatabase {
         Ok(final_results)
     }
 
+    /// 在索引中查找带有该平台 ID 的歌词文件，较新的文件排在前面。
+    async fn find_by_reference(&self, reference: &SongReference) -> Result<Vec<SearchResult>> {
+        let mut entries: Vec<&IndexEntry> = self
+            .search_index
+            .lookup_platform_id(reference.platform, &reference.id)
+            .into_iter()
+            .filter_map(|id| self.index.get(id))
+            .collect();
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.raw_lyric_file.timestamp));
+
+        Ok(entries
+            .into_iter()
+            .map(|entry| {
+                let mut result = into_search_result(
+                    entry,
+                    entry.metadata.titles.first().cloned().unwrap_or_default(),
+                    entry.metadata.albums.first().cloned(),
+                    self.name(),
+                );
+                result.match_type = MatchType::Perfect;
+                result
+            })
+            .collect())
+    }
+
     #[allow(clippy::literal_string_with_formatting_args)]
     /// 获取并解析完整的 TTML 歌词文件。
     async fn get_full_lyrics(&self, song_id: &str) -> Result<FullLyricsResult> {
-        let ttml_url = self.lyrics_url_template.replace("{song_id}", song_id);
-        tracing::info!("[AMLL] 下载并解析 TTML: {}", ttml_url);
-
-        let response = self.http_client.get(&ttml_url).await?;
-        if response.status >= 400 {
-            return Err(LyricsHelperError::Http(format!(
-                "下载 AMLL 歌词失败，状态码: {}",
-                response.status
-            )));
-        }
-        let response_text = response.text()?;
+        let response_text = match &self.lyrics_source {
+            LyricsSource::Remote {
+                http_client,
+                url_template,
+            } => {
+                let ttml_url = url_template.replace("{song_id}", song_id);
+                tracing::info!("[AMLL] 下载并解析 TTML: {}", ttml_url);
+
+                let response = http_client.get(&ttml_url).await?;
+                if response.status >= 400 {
+                    return Err(LyricsHelperError::Http(format!(
+                        "下载 AMLL 歌词失败，状态码: {}",
+                        response.status
+                    )));
+                }
+                response.text()?
+            }
+            LyricsSource::Local(snapshot) => {
+                tracing::info!("[AMLL] 从本地快照读取并解析 TTML: {}", song_id);
+                snapshot.read_lyric(song_id)?
+            }
+        };
 
         let conversion_input = ConversionInput {
             main_lyric: InputFile {
@@ -206,6 +273,7 @@ impl Provider for AmllTtmlDatabase {
             content: response_text,
             translation: None,  // 由 TTML 解析器自己处理
             romanization: None, // 由 TTML 解析器自己处理
+            contributors: ttml_authors(&parsed_data),
         };
 
         Ok(FullLyricsResult {
@@ -267,6 +335,56 @@ impl Provider for AmllTtmlDatabase {
     }
 }
 
+impl AmllTtmlDatabase {
+    /// 从本地快照创建提供商，不进行任何网络访问。
+    ///
+    /// 搜索索引按快照索引内容的哈希缓存，快照更新后会自动重建。
+    pub fn from_snapshot(snapshot: LocalSnapshot) -> Result<Self> {
+        let content = snapshot.read_index()?;
+        let index_entries = parse_index(&content);
+        if index_entries.is_empty() && !content.trim().is_empty() {
+            return Err(LyricsHelperError::Internal(
+                "本地快照的索引文件内容非空但无法解析出任何条目".into(),
+            ));
+        }
+
+        tracing::info!(
+            "[AMLL] 本地索引加载完成，共 {} 条记录。",
+            index_entries.len()
+        );
+        let index_head = snapshot::index_head(&content);
+        let search_index = AmllSearchIndex::load_or_build(&index_entries, Some(&index_head))?;
+        Ok(Self {
+            index: Arc::new(index_entries),
+            search_index: Arc::new(search_index),
+            lyrics_source: LyricsSource::Local(snapshot),
+        })
+    }
+
+    /// 通过检索索引找出候选条目，较新的条目排在前面。
+    ///
+    /// 标题命中的条目都是候选项；标题未命中但艺术家和专辑都命中的条目
+    /// （例如标题使用了另一种文字）也会作为候选项交给评分环节。
+    fn find_candidates(&self, title: &str, track: &Track<'_>) -> Vec<IndexEntry> {
+        let mut ids = self.search_index.search(title, IndexField::Title);
+
+        if let (Some(artists), Some(album)) = (track.artists, track.album) {
+            let album_hits = self.search_index.search(album, IndexField::Album);
+            let artist_hits: Vec<usize> = artists
+                .iter()
+                .flat_map(|artist| self.search_index.search(artist, IndexField::Artist))
+                .collect();
+            ids.extend(album_hits.into_iter().filter(|id| artist_hits.contains(id)));
+        }
+
+        ids.sort_unstable_by(|a, b| b.cmp(a));
+        ids.dedup();
+        ids.into_iter()
+            .filter_map(|id| self.index.get(id).cloned())
+            .collect()
+    }
+}
+
 /// 从 GitHub API 获取索引文件的最新 commit SHA。
 async fn fetch_remote_index_head(http_client: &dyn HttpClient) -> Result<String> {
     let url = format!(
@@ -318,17 +436,7 @@ fn load_index_from_cache() -> Result<Vec<IndexEntry>> {
     let content = crate::config::read_from_cache(INDEX_CACHE_FILENAME)
         .map_err(|e| LyricsHelperError::Internal(format!("Failed to read index cache: {e}")))?;
 
-    let entries = content
-        .lines()
-        .filter_map(|line| {
-            if line.trim().is_empty() {
-                None
-            } else {
-                serde_json::from_str::<IndexEntry>(line).ok()
-            }
-        })
-        .collect();
-    Ok(entries)
+    Ok(parse_index(&content))
 }
 
 /// 下载、解析索引文件，并更新本地缓存。
@@ -346,7 +454,21 @@ async fn download_and_parse_index(
     }
     let response_text = response.text()?;
 
-    let entries: Vec<IndexEntry> = response_text
+    let entries = parse_index(&response_text);
+
+    if entries.is_empty() && !response_text.trim().is_empty() {
+        return Err(LyricsHelperError::Internal(
+            "下载的索引文件内容非空但无法解析出任何条目".into(),
+        ));
+    }
+
+    save_index_to_cache(&response_text, remote_head_sha)?;
+    Ok(entries)
+}
+
+/// 解析 JSONL 格式的索引文件，忽略损坏的行。
+fn parse_index(content: &str) -> Vec<IndexEntry> {
+    content
         .lines()
         .filter(|line| !line.trim().is_empty())
         .filter_map(|line| match serde_json::from_str(line) {
@@ -356,16 +478,34 @@ async fn download_and_parse_index(
                 None
             }
         })
-        .collect();
-
-    if entries.is_empty() && !response_text.trim().is_empty() {
-        return Err(LyricsHelperError::Internal(
-            "下载的索引文件内容非空但无法解析出任何条目".into(),
-        ));
-    }
+        .collect()
+}
 
-    save_index_to_cache(&response_text, remote_head_sha)?;
-    Ok(entries)
+/// 从 TTML 元数据中读取逐词歌词作者。
+///
+/// 作者已经以 `ttmlAuthorGithubLogin` 记录在元数据中，这里只补充到原始歌词的贡献者列表。
+fn ttml_authors(parsed: &ParsedSourceData) -> Vec<LyricContributor> {
+    let metadata = MetadataStore::from(parsed);
+    let logins = metadata
+        .get_multiple_values(&CanonicalMetadataKey::TtmlAuthorGithubLogin)
+        .cloned()
+        .unwrap_or_default();
+    let ids = metadata
+        .get_multiple_values(&CanonicalMetadataKey::TtmlAuthorGithub)
+        .cloned()
+        .unwrap_or_default();
+    // 两者数量不一致时无法确定对应关系，不填写 ID
+    let ids_match = logins.len() == ids.len();
+
+    logins
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| LyricContributor {
+            role: ContributorRole::Lyrics,
+            name,
+            user_id: ids_match.then(|| ids[i].clone()),
+        })
+        .collect()
 }
 
 /// 将下载的内容和最新的 SHA 写入本地缓存文件。
@@ -379,22 +519,6 @@ fn save_index_to_cache(content: &str, head_sha: &str) -> Result<()> {
     Ok(())
 }
 
-fn find_candidates(index: &[IndexEntry], keyword: &str) -> Vec<IndexEntry> {
-    let lower_keyword = keyword.to_lowercase();
-    index
-        .iter()
-        .rev()
-        .filter(|entry| {
-            entry
-                .metadata
-                .titles
-                .iter()
-                .any(|v| v.to_lowercase().contains(&lower_keyword))
-        })
-        .cloned()
-        .collect()
-}
-
 fn calculate_best_match(
     entry: &IndexEntry,
     track: &Track<'_>,
@@ -506,19 +630,34 @@ fn into_search_result(
 
 #[cfg(test)]
 mod tests {
+    use lyrics_helper_core::ReferencePlatform;
+
     use super::*;
+    use crate::http::{
+        MatchRules, ReplayHttpClient,
+        replay::{HttpFixture, fixture_client},
+    };
+
+    /// 不需要网络请求的测试使用空夹具，任何请求都会失败。
+    fn offline_client() -> Arc<dyn HttpClient> {
+        Arc::new(ReplayHttpClient::new(HttpFixture::default()))
+    }
 
-    fn create_test_provider() -> (AmllTtmlDatabase, IndexEntry) {
+    fn create_test_provider(http_client: Arc<dyn HttpClient>) -> (AmllTtmlDatabase, IndexEntry) {
         let sample_json = r#"{"metadata":[["musicName",["明明 (深爱着你) (Live)"]],["artists",["李宇春","丁肆Dicey"]],["album",["有歌2024 第4期"]],["ncmMusicId",["2642164541"]],["qqMusicId",["000pF84f1Mqkf7"]],["spotifyId",["29OlvJxVuNd8BJazjvaYpP"]],["isrc",["CNUM72400589"]],["ttmlAuthorGithub",["108002475"]],["ttmlAuthorGithubLogin",["apoint123"]]],"rawLyricFile":"1746678978875-108002475-0a0fb081.ttml"}"#;
 
         let index_entry: IndexEntry = serde_json::from_str(sample_json).unwrap();
+        let entries = vec![index_entry.clone()];
 
         let provider = AmllTtmlDatabase {
-            index: Arc::new(vec![index_entry.clone()]),
-            http_client: Arc::new(crate::http::WreqClient::new().unwrap()),
-            lyrics_url_template: format!(
-                "{RAW_CONTENT_BASE_URL}/{REPO_OWNER}/{REPO_NAME}/{REPO_BRANCH}/raw-lyrics/{{song_id}}"
-            ),
+            search_index: Arc::new(AmllSearchIndex::build(&entries).unwrap()),
+            index: Arc::new(entries),
+            lyrics_source: LyricsSource::Remote {
+                http_client,
+                url_template: format!(
+                    "{RAW_CONTENT_BASE_URL}/{REPO_OWNER}/{REPO_NAME}/{REPO_BRANCH}/raw-lyrics/{{song_id}}"
+                ),
+            },
         };
 
         (provider, index_entry)
@@ -526,7 +665,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_amll_search() {
-        let (provider, expected_entry) = create_test_provider();
+        let (provider, expected_entry) = create_test_provider(offline_client());
 
         // --- 案例 1: 仅按标题搜索 ---
         let search_query1 = Track {
@@ -534,6 +673,7 @@ mod tests {
             artists: None,
             album: None,
             duration: None,
+            version_hints: None,
         };
         let results1 = provider.search_songs(&search_query1).await.unwrap();
         assert_eq!(results1.len(), 1, "应该找到一个结果");
@@ -549,6 +689,7 @@ mod tests {
             artists: Some(&["李宇春"]),
             album: None,
             duration: None,
+            version_hints: None,
         };
         let results2 = provider.search_songs(&search_query2).await.unwrap();
         assert_eq!(results2.len(), 1, "应该找到一个结果");
@@ -562,6 +703,7 @@ mod tests {
             artists: Some(&["丁肆dicey"]),
             album: None,
             duration: None,
+            version_hints: None,
         };
         let results3 = provider.search_songs(&search_query3).await.unwrap();
         assert_eq!(results3.len(), 1, "大小写不敏感的搜索应该工作");
@@ -572,6 +714,7 @@ mod tests {
             artists: Some(&["周杰伦"]),
             album: None,
             duration: None,
+            version_hints: None,
         };
         let results4 = provider.search_songs(&search_query4).await.unwrap();
         assert_eq!(
@@ -590,15 +733,81 @@ mod tests {
             artists: None,
             album: None,
             duration: None,
+            version_hints: None,
         };
         let results5 = provider.search_songs(&search_query5).await.unwrap();
         assert!(results5.is_empty(), "用错误的歌曲名应该搜索不到结果");
     }
 
     #[tokio::test]
-    #[ignore]
+    async fn test_amll_find_by_reference() {
+        let (provider, expected_entry) = create_test_provider(offline_client());
+
+        for (platform, id) in [
+            (ReferencePlatform::Qq, "000pF84f1Mqkf7"),
+            (ReferencePlatform::Netease, "2642164541"),
+            (ReferencePlatform::Spotify, "29OlvJxVuNd8BJazjvaYpP"),
+            (ReferencePlatform::Isrc, "cnum72400589"),
+        ] {
+            let reference = SongReference {
+                platform,
+                id: id.to_string(),
+            };
+            let results = provider.find_by_reference(&reference).await.unwrap();
+            assert_eq!(results.len(), 1, "应该通过 {platform:?} ID 找到歌词");
+            assert_eq!(
+                results[0].provider_id,
+                expected_entry.raw_lyric_file.filename
+            );
+            assert_eq!(results[0].match_type, MatchType::Perfect);
+        }
+
+        let reference = SongReference {
+            platform: ReferencePlatform::AppleMusic,
+            id: "2642164541".to_string(),
+        };
+        assert!(
+            provider
+                .find_by_reference(&reference)
+                .await
+                .unwrap()
+                .is_empty(),
+            "不同平台的相同 ID 不应匹配"
+        );
+    }
+
+    #[test]
+    fn test_ttml_authors_user_ids() {
+        let mut parsed = ParsedSourceData::default();
+        parsed.raw_metadata.insert(
+            "ttmlAuthorGithubLogin".to_string(),
+            vec!["apoint123".to_string(), "someone".to_string()],
+        );
+        parsed.raw_metadata.insert(
+            "ttmlAuthorGithub".to_string(),
+            vec!["108002475".to_string()],
+        );
+
+        // 数量不一致时不应错配 ID
+        let authors = ttml_authors(&parsed);
+        assert_eq!(authors.len(), 2);
+        assert!(authors.iter().all(|a| a.user_id.is_none()));
+
+        parsed.raw_metadata.insert(
+            "ttmlAuthorGithub".to_string(),
+            vec!["108002475".to_string(), "1".to_string()],
+        );
+        let authors = ttml_authors(&parsed);
+        assert_eq!(authors[0].user_id.as_deref(), Some("108002475"));
+        assert_eq!(authors[1].user_id.as_deref(), Some("1"));
+    }
+
+    #[tokio::test]
     async fn test_amll_fetch_lyrics() {
-        let (provider, entry) = create_test_provider();
+        let (provider, entry) = create_test_provider(fixture_client(
+            "amll_ttml_database/fetch_lyrics",
+            MatchRules::default(),
+        ));
         let song_id = &entry.raw_lyric_file.filename;
 
         println!("正在获取 id 为 {song_id} 的歌词");
@@ -620,5 +829,10 @@ mod tests {
         let first_line = &parsed_data.parsed.lines[0];
         println!("第一行的开始时间: {}ms", first_line.start_ms);
         assert!(first_line.start_ms > 0, "第一行应该有开始时间");
+
+        let authors = &parsed_data.raw.contributors;
+        assert_eq!(authors.len(), 1);
+        assert_eq!(authors[0].name, "apoint123");
+        assert_eq!(authors[0].user_id.as_deref(), Some("108002475"));
     }
 }
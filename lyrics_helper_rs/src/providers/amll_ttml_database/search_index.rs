@@ -0,0 +1,312 @@
+//! AMLL TTML Database 索引的检索索引。
+//!
+//! 从每个条目的标题、艺术家、专辑和各平台 ID 构建一个 FST，
+//! 键为 `检索词 \0 字段 条目编号`，值为条目编号。
+//! 文本字段同时使用前缀匹配和 Levenshtein 自动机检索，以容忍拼写错误。
+//! CJK 文本另外索引了每个字符开始的后缀，使词中间的片段也能被前缀匹配命中。
+//!
+//! 索引保存在 `index.jsonl` 旁边，只有索引文件的 HEAD SHA 变化时才会重新构建。
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+};
+
+use fst::{
+    Automaton, IntoStreamer, Map, MapBuilder, Streamer,
+    automaton::{Levenshtein, Str},
+};
+use lyrics_helper_core::ReferencePlatform;
+
+use super::types::IndexEntry;
+use crate::{
+    error::{LyricsHelperError, Result},
+    search::terms::{index_terms, search_terms},
+};
+
+const SEARCH_INDEX_CACHE_FILENAME: &str = "amll_ttml_db/search_index.fst";
+const SEARCH_INDEX_HEAD_CACHE_FILENAME: &str = "amll_ttml_db/search_index.fst.head";
+
+/// 索引格式的版本，检索词的生成方式变化时递增，使旧的缓存失效。
+const SEARCH_INDEX_VERSION: u32 = 2;
+
+/// 检索词和字段、条目编号之间的分隔符，检索词本身不会包含它。
+const TERM_SEPARATOR: u8 = 0;
+
+/// 单个检索词允许的最大编辑距离所对应的最短字符数。
+const FUZZY_ONE_EDIT_MIN_CHARS: usize = 4;
+const FUZZY_TWO_EDITS_MIN_CHARS: usize = 8;
+
+/// 检索词来自条目的哪个字段。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum IndexField {
+    Title = 0,
+    Artist = 1,
+    Album = 2,
+    PlatformId = 3,
+}
+
+impl IndexField {
+    const fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Self::Title),
+            1 => Some(Self::Artist),
+            2 => Some(Self::Album),
+            3 => Some(Self::PlatformId),
+            _ => None,
+        }
+    }
+}
+
+/// AMLL TTML Database 的检索索引。
+pub(super) struct AmllSearchIndex {
+    terms: Map<Vec<u8>>,
+}
+
+impl AmllSearchIndex {
+    /// 根据索引条目构建检索索引。条目编号即其在 `entries` 中的位置。
+    pub(super) fn build(entries: &[IndexEntry]) -> Result<Self> {
+        // FST 要求键按字节序插入且不重复
+        let mut keys: BTreeMap<Vec<u8>, u64> = BTreeMap::new();
+        let mut insert = |term: &str, field: IndexField, id: usize| {
+            let id = u32::try_from(id).unwrap_or(u32::MAX);
+            let mut key = term.as_bytes().to_vec();
+            key.push(TERM_SEPARATOR);
+            key.push(field as u8);
+            key.extend_from_slice(&id.to_be_bytes());
+            keys.insert(key, u64::from(id));
+        };
+
+        for (id, entry) in entries.iter().enumerate() {
+            let metadata = &entry.metadata;
+            for (field, texts) in [
+                (IndexField::Title, &metadata.titles),
+                (IndexField::Artist, &metadata.artists),
+                (IndexField::Album, &metadata.albums),
+            ] {
+                for term in texts.iter().flat_map(|text| index_terms(text)) {
+                    insert(&term, field, id);
+                }
+            }
+            for platform in [
+                ReferencePlatform::Qq,
+                ReferencePlatform::Netease,
+                ReferencePlatform::AppleMusic,
+                ReferencePlatform::Spotify,
+                ReferencePlatform::Isrc,
+            ] {
+                for platform_id in entry.platform_ids(platform) {
+                    insert(
+                        &platform_id_term(platform, platform_id),
+                        IndexField::PlatformId,
+                        id,
+                    );
+                }
+            }
+        }
+
+        let mut builder = MapBuilder::memory();
+        for (key, id) in keys {
+            builder.insert(key, id).map_err(|e| fst_error(&e))?;
+        }
+        Self::from_bytes(builder.into_inner().map_err(|e| fst_error(&e))?)
+    }
+
+    fn from_bytes(bytes: Vec<u8>) -> Result<Self> {
+        Ok(Self {
+            terms: Map::new(bytes).map_err(|e| fst_error(&e))?,
+        })
+    }
+
+    /// 从缓存加载检索索引；缓存不存在或与当前索引文件不一致时重新构建并写入缓存。
+    ///
+    /// # 参数
+    /// * `entries` - 索引条目。
+    /// * `index_head` - 索引文件对应的 HEAD SHA，未知时不会读写缓存。
+    pub(super) fn load_or_build(entries: &[IndexEntry], index_head: Option<&str>) -> Result<Self> {
+        // 条目数和索引格式版本一并写入，以防同一 SHA 下解析出的条目或检索词发生变化
+        let cache_key =
+            index_head.map(|head| format!("{head}:{}:v{SEARCH_INDEX_VERSION}", entries.len()));
+
+        if let Some(cache_key) = &cache_key
+            && crate::config::read_from_cache(SEARCH_INDEX_HEAD_CACHE_FILENAME)
+                .is_ok_and(|cached| cached.trim() == cache_key)
+            && let Some(index) = crate::config::get_cache_file_path(SEARCH_INDEX_CACHE_FILENAME)
+                .and_then(fs::read)
+                .ok()
+                .and_then(|bytes| Self::from_bytes(bytes).ok())
+        {
+            tracing::info!("[AMLL] 检索索引缓存有效，从本地加载。");
+            return Ok(index);
+        }
+
+        tracing::info!("[AMLL] 正在构建检索索引...");
+        let index = Self::build(entries)?;
+        if let Some(cache_key) = cache_key
+            && let Err(e) = index.save(&cache_key)
+        {
+            tracing::warn!("[AMLL] 写入检索索引缓存失败: {e}");
+        }
+        Ok(index)
+    }
+
+    fn save(&self, cache_key: &str) -> Result<()> {
+        let path = crate::config::get_cache_file_path(SEARCH_INDEX_CACHE_FILENAME)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.terms.as_fst().as_bytes())?;
+        crate::config::write_to_cache(SEARCH_INDEX_HEAD_CACHE_FILENAME, cache_key)
+            .map_err(|e| LyricsHelperError::Internal(format!("写入检索索引 HEAD 缓存失败: {e}")))
+    }
+
+    /// 在指定字段中检索文本，返回命中的条目编号。
+    ///
+    /// 查询中的每个词都能以前缀或在允许的编辑距离内命中同一字段时，条目才算命中；
+    /// 去掉分隔符后的完整查询命中时同样算命中。
+    pub(super) fn search(&self, query: &str, field: IndexField) -> Vec<usize> {
+        let terms = search_terms(query);
+        // `search_terms` 只在有多个词时才会在末尾追加去掉分隔符的完整文本
+        let (words, compact) = match terms.len() {
+            0 => return vec![],
+            1 => (&terms[..], None),
+            n => (&terms[..n - 1], Some(n - 1)),
+        };
+
+        let mut hits: HashMap<usize, u64> = HashMap::new();
+        for (index, term) in terms.iter().enumerate().take(64) {
+            for id in self.term_hits(term, field) {
+                *hits.entry(id).or_default() |= 1 << index;
+            }
+        }
+
+        let all_words = (1u64 << words.len().min(63)) - 1;
+        let mut ids: Vec<usize> = hits
+            .into_iter()
+            .filter(|&(_, mask)| {
+                mask & all_words == all_words || compact.is_some_and(|i| mask & (1 << i) != 0)
+            })
+            .map(|(id, _)| id)
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// 按平台 ID 精确查找条目。
+    pub(super) fn lookup_platform_id(&self, platform: ReferencePlatform, id: &str) -> Vec<usize> {
+        let mut prefix = platform_id_term(platform, id).into_bytes();
+        prefix.push(TERM_SEPARATOR);
+        prefix.push(IndexField::PlatformId as u8);
+        let mut stream = self.terms.range().ge(&prefix).into_stream();
+        let mut ids = Vec::new();
+        while let Some((key, id)) = stream.next() {
+            if !key.starts_with(&prefix) {
+                break;
+            }
+            ids.push(usize::try_from(id).unwrap_or(usize::MAX));
+        }
+        ids
+    }
+
+    /// 返回单个检索词以前缀或模糊方式命中的条目编号。
+    fn term_hits(&self, term: &str, field: IndexField) -> Vec<usize> {
+        let mut ids = self.collect(Str::new(term).starts_with(), field);
+        if let Some(fuzzy) = fuzzy_automaton(term) {
+            ids.extend(self.collect(fuzzy.starts_with(), field));
+        }
+        ids
+    }
+
+    fn collect<A: Automaton>(&self, automaton: A, field: IndexField) -> Vec<usize> {
+        let mut stream = self.terms.search(automaton).into_stream();
+        let mut ids = Vec::new();
+        while let Some((key, id)) = stream.next() {
+            // 键的末尾是 `\0`、字段和 4 字节的条目编号
+            let field_byte = key.len().checked_sub(5).map(|i| key[i]);
+            if field_byte.and_then(IndexField::from_byte) == Some(field) {
+                ids.push(usize::try_from(id).unwrap_or(usize::MAX));
+            }
+        }
+        ids
+    }
+}
+
+/// 为完整的词构建 Levenshtein 自动机，词越长允许的编辑距离越大。
+///
+/// 自动机匹配 `词\0`，因此只会命中编辑距离内的完整检索词，而不是任意前缀。
+fn fuzzy_automaton(term: &str) -> Option<Levenshtein> {
+    let chars = term.chars().count();
+    let distance = if chars >= FUZZY_TWO_EDITS_MIN_CHARS {
+        2
+    } else if chars >= FUZZY_ONE_EDIT_MIN_CHARS {
+        1
+    } else {
+        return None;
+    };
+    let query = format!("{term}\0");
+    // 过长的词在较大距离下可能超出自动机的状态上限，此时退回到较小的距离
+    (1..=distance)
+        .rev()
+        .find_map(|d| Levenshtein::new(&query, d).ok())
+}
+
+fn platform_id_term(platform: ReferencePlatform, id: &str) -> String {
+    let tag = match platform {
+        ReferencePlatform::Qq => "qq",
+        ReferencePlatform::Netease => "ncm",
+        ReferencePlatform::Kugou => "kugou",
+        ReferencePlatform::AppleMusic => "apple",
+        ReferencePlatform::Spotify => "spotify",
+        ReferencePlatform::Isrc => "isrc",
+    };
+    format!("{tag}:{}", id.to_lowercase())
+}
+
+fn fst_error(e: &fst::Error) -> LyricsHelperError {
+    LyricsHelperError::Internal(format!("构建 AMLL 检索索引失败: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(title: &str, artist: &str) -> IndexEntry {
+        let json = serde_json::json!({
+            "metadata": [["musicName", [title]], ["artists", [artist]], ["appleMusicId", ["1440"]]],
+            "rawLyricFile": format!("0-{title}.ttml"),
+        });
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_prefix_and_typo_tolerant_search() {
+        let entries = vec![
+            entry("Lemon", "米津玄師"),
+            entry("Shape of You", "Ed Sheeran"),
+            entry("明明 (深爱着你) (Live)", "李宇春"),
+        ];
+        let index = AmllSearchIndex::build(&entries).unwrap();
+
+        assert_eq!(index.search("Lemon", IndexField::Title), vec![0]);
+        assert_eq!(index.search("Lemmon", IndexField::Title), vec![0]);
+        assert_eq!(index.search("shape of yuo", IndexField::Title), vec![1]);
+        assert_eq!(index.search("Shape", IndexField::Title), vec![1]);
+        assert_eq!(index.search("明明", IndexField::Title), vec![2]);
+        // CJK 没有空格分词，词中间的片段也要能命中
+        assert_eq!(index.search("爱着你", IndexField::Title), vec![2]);
+        assert_eq!(index.search("玄師", IndexField::Artist), vec![0]);
+        assert_eq!(index.search("ed sheeran", IndexField::Artist), vec![1]);
+        assert!(index.search("ed sheeran", IndexField::Title).is_empty());
+        assert!(index.search("Melon Soda", IndexField::Title).is_empty());
+
+        assert_eq!(
+            index.lookup_platform_id(ReferencePlatform::AppleMusic, "1440"),
+            vec![0, 1, 2]
+        );
+        assert!(
+            index
+                .lookup_platform_id(ReferencePlatform::AppleMusic, "144")
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,419 @@
+//! AMLL TTML Database 的本地快照。
+//!
+//! 快照可以是 amll-ttml-db 仓库的本地克隆，也可以是下载的 `.tar.gz`/`.tgz`/`.tar`/`.zip` 归档
+//! （例如 GitHub 的 “Download ZIP”）。读取快照不需要任何网络访问。
+//!
+//! 归档会被解压到缓存目录，只保留索引文件和 `raw-lyrics` 目录；归档未变化时不会重复解压。
+//! 两个快照之间可以通过 [`LocalSnapshot::diff`] 比较差异，并用 [`LocalSnapshot::apply_update`]
+//! 把新快照中的变化应用到本地目录，从而在无法使用 git 的环境中更新数据库。
+
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Component, Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use super::INDEX_FILE_PATH_IN_REPO;
+use crate::error::{LyricsHelperError, Result};
+
+/// 仓库中存放 TTML 歌词文件的目录。
+const RAW_LYRICS_DIR: &str = "raw-lyrics";
+/// 解压归档时使用的缓存目录。
+const EXTRACT_CACHE_DIR: &str = "amll_ttml_db/local_snapshot";
+/// 记录解压来源的标记文件，归档未变化时跳过解压。
+const SOURCE_MARKER_FILENAME: &str = ".source";
+
+/// 一个本地的 AMLL TTML Database 快照。
+#[derive(Debug, Clone)]
+pub struct LocalSnapshot {
+    root: PathBuf,
+}
+
+/// 两个快照之间的差异，均以歌词文件名表示。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotDiff {
+    /// 新快照中新增的歌词文件。
+    pub added: Vec<String>,
+    /// 新快照中已移除的歌词文件。
+    pub removed: Vec<String>,
+    /// 歌词文件未变化、但索引中的元数据发生了变化的条目。
+    pub metadata_changed: Vec<String>,
+}
+
+impl SnapshotDiff {
+    /// 两个快照是否完全相同。
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.metadata_changed.is_empty()
+    }
+}
+
+/// 索引中的一行，只关心歌词文件名。
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IndexLine {
+    raw_lyric_file: String,
+}
+
+impl LocalSnapshot {
+    /// 打开一个本地快照。归档会被解压到缓存目录。
+    ///
+    /// # 参数
+    /// * `path` - 仓库克隆目录、解压后的目录，或 `.tar.gz`/`.tgz`/`.tar`/`.zip` 归档文件。
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if path.is_dir() {
+            return Self::open_dir(path);
+        }
+        let extract_dir = crate::config::get_cache_file_path(EXTRACT_CACHE_DIR)?;
+        Self::open_with_extract_dir(path, &extract_dir)
+    }
+
+    /// 与 [`Self::open`] 相同，但把归档解压到指定目录。
+    pub fn open_with_extract_dir(path: impl AsRef<Path>, extract_dir: &Path) -> Result<Self> {
+        let path = path.as_ref();
+        if path.is_dir() {
+            return Self::open_dir(path);
+        }
+
+        let source = archive_fingerprint(path)?;
+        let marker = extract_dir.join(SOURCE_MARKER_FILENAME);
+        if fs::read_to_string(&marker).is_ok_and(|cached| cached == source)
+            && let Ok(snapshot) = Self::open_dir(extract_dir)
+        {
+            tracing::info!("[AMLL] 归档 {} 未变化，使用已解压的快照。", path.display());
+            return Ok(snapshot);
+        }
+
+        tracing::info!(
+            "[AMLL] 正在解压 {} 到 {}...",
+            path.display(),
+            extract_dir.display()
+        );
+        match fs::remove_dir_all(extract_dir) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+            _ => {}
+        }
+        fs::create_dir_all(extract_dir)?;
+        extract_archive(path, extract_dir)?;
+        fs::write(marker, source)?;
+        Self::open_dir(extract_dir)
+    }
+
+    /// 打开一个目录。GitHub 的归档解压后会多出一层 `amll-ttml-db-main` 目录，也一并支持。
+    fn open_dir(dir: &Path) -> Result<Self> {
+        if dir.join(INDEX_FILE_PATH_IN_REPO).is_file() {
+            return Ok(Self {
+                root: dir.to_path_buf(),
+            });
+        }
+        for entry in fs::read_dir(dir)? {
+            let child = entry?.path();
+            if child.is_dir() && child.join(INDEX_FILE_PATH_IN_REPO).is_file() {
+                return Ok(Self { root: child });
+            }
+        }
+        Err(LyricsHelperError::Internal(format!(
+            "{} 不是有效的 AMLL TTML Database 快照：找不到 {INDEX_FILE_PATH_IN_REPO}",
+            dir.display()
+        )))
+    }
+
+    /// 快照的根目录。
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// 读取索引文件的内容。
+    pub fn read_index(&self) -> Result<String> {
+        Ok(fs::read_to_string(self.root.join(INDEX_FILE_PATH_IN_REPO))?)
+    }
+
+    /// 读取一个 TTML 歌词文件。
+    pub fn read_lyric(&self, filename: &str) -> Result<String> {
+        if !is_plain_filename(filename) {
+            return Err(LyricsHelperError::LyricNotFound);
+        }
+        match fs::read_to_string(self.root.join(RAW_LYRICS_DIR).join(filename)) {
+            Ok(content) => Ok(content),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Err(LyricsHelperError::LyricNotFound),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// 比较本快照与一个较新的快照。
+    pub fn diff(&self, newer: &Self) -> Result<SnapshotDiff> {
+        let old_lines = index_lines(&self.read_index()?);
+        let new_lines = index_lines(&newer.read_index()?);
+
+        let mut diff = SnapshotDiff::default();
+        for (filename, line) in &new_lines {
+            match old_lines.get(filename) {
+                None => diff.added.push(filename.clone()),
+                Some(old_line) if old_line != line => diff.metadata_changed.push(filename.clone()),
+                Some(_) => {}
+            }
+        }
+        diff.removed = old_lines
+            .keys()
+            .filter(|filename| !new_lines.contains_key(*filename))
+            .cloned()
+            .collect();
+
+        diff.added.sort();
+        diff.removed.sort();
+        diff.metadata_changed.sort();
+        Ok(diff)
+    }
+
+    /// 把较新快照中的变化应用到本快照所在的目录，并返回应用的差异。
+    ///
+    /// 新增的歌词文件会被复制过来，已移除的会被删除，最后替换索引文件。
+    /// 本快照应当是一个本地目录；对解压缓存的修改会在归档变化后被覆盖。
+    pub fn apply_update(&self, newer: &Self) -> Result<SnapshotDiff> {
+        let diff = self.diff(newer)?;
+        if diff.is_empty() {
+            return Ok(diff);
+        }
+
+        let lyrics_dir = self.root.join(RAW_LYRICS_DIR);
+        fs::create_dir_all(&lyrics_dir)?;
+        for filename in diff.added.iter().filter(|f| is_plain_filename(f)) {
+            fs::copy(
+                newer.root.join(RAW_LYRICS_DIR).join(filename),
+                lyrics_dir.join(filename),
+            )?;
+        }
+        for filename in diff.removed.iter().filter(|f| is_plain_filename(f)) {
+            match fs::remove_file(lyrics_dir.join(filename)) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+        }
+        fs::copy(
+            newer.root.join(INDEX_FILE_PATH_IN_REPO),
+            self.root.join(INDEX_FILE_PATH_IN_REPO),
+        )?;
+
+        tracing::info!(
+            "[AMLL] 已更新本地快照：新增 {} 个、移除 {} 个歌词文件，{} 个条目的元数据有变化。",
+            diff.added.len(),
+            diff.removed.len(),
+            diff.metadata_changed.len()
+        );
+        Ok(diff)
+    }
+}
+
+/// 根据索引内容计算一个稳定的版本标识，代替远程仓库的 HEAD SHA。
+pub(super) fn index_head(content: &str) -> String {
+    format!("local-{}", hex::encode(Sha256::digest(content)))
+}
+
+/// 按歌词文件名索引每一行。
+fn index_lines(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .filter_map(|line| {
+            let parsed: IndexLine = serde_json::from_str(line).ok()?;
+            Some((parsed.raw_lyric_file, line.to_string()))
+        })
+        .collect()
+}
+
+fn is_plain_filename(filename: &str) -> bool {
+    let mut components = Path::new(filename).components();
+    matches!(components.next(), Some(Component::Normal(_))) && components.next().is_none()
+}
+
+/// 用归档的路径、大小和修改时间标识归档，以判断是否需要重新解压。
+fn archive_fingerprint(path: &Path) -> Result<String> {
+    let metadata = fs::metadata(path)?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |duration| duration.as_secs());
+    let path = fs::canonicalize(path)?;
+    Ok(format!(
+        "{}\n{}\n{modified}",
+        path.display(),
+        metadata.len()
+    ))
+}
+
+fn extract_archive(path: &Path, dest: &Path) -> Result<()> {
+    let extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let file = fs::File::open(path)?;
+
+    match extension.as_str() {
+        "zip" => extract_zip(file, dest),
+        "gz" | "tgz" => extract_tar(flate2::read::GzDecoder::new(file), dest),
+        "tar" => extract_tar(file, dest),
+        _ => Err(LyricsHelperError::Internal(format!(
+            "不支持的快照格式: {}，请使用目录、.tar.gz、.tgz、.tar 或 .zip",
+            path.display()
+        ))),
+    }
+}
+
+fn extract_tar(reader: impl io::Read, dest: &Path) -> Result<()> {
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let Some(relative) = snapshot_relative_path(&entry.path()?) else {
+            continue;
+        };
+        let target = dest.join(relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        entry.unpack(&target)?;
+    }
+    Ok(())
+}
+
+fn extract_zip(file: fs::File, dest: &Path) -> Result<()> {
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| zip_error(&e))?;
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index).map_err(|e| zip_error(&e))?;
+        if !entry.is_file() {
+            continue;
+        }
+        let Some(relative) = entry
+            .enclosed_name()
+            .and_then(|name| snapshot_relative_path(&name))
+        else {
+            continue;
+        };
+        let target = dest.join(relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        io::copy(&mut entry, &mut fs::File::create(target)?)?;
+    }
+    Ok(())
+}
+
+/// 从归档内的路径中取出快照需要的部分（索引文件和歌词文件），并去掉外层目录。
+///
+/// 含有 `..` 或绝对路径的条目会被忽略。
+fn snapshot_relative_path(path: &Path) -> Option<PathBuf> {
+    let components: Vec<&str> = path
+        .components()
+        .map(|component| match component {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+
+    let index_components: Vec<&str> = INDEX_FILE_PATH_IN_REPO.split('/').collect();
+    if components.ends_with(&index_components) {
+        return Some(PathBuf::from(INDEX_FILE_PATH_IN_REPO));
+    }
+    match components.as_slice() {
+        [.., dir, filename] if *dir == RAW_LYRICS_DIR => {
+            Some(Path::new(RAW_LYRICS_DIR).join(filename))
+        }
+        _ => None,
+    }
+}
+
+fn zip_error(e: &zip::result::ZipError) -> LyricsHelperError {
+    LyricsHelperError::Internal(format!("读取 ZIP 归档失败: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            Self(std::env::temp_dir().join(format!("amll-snapshot-{}", uuid::Uuid::new_v4())))
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn index_line(filename: &str, title: &str) -> String {
+        format!(r#"{{"metadata":[["musicName",["{title}"]]],"rawLyricFile":"{filename}"}}"#)
+    }
+
+    fn write_snapshot(root: &Path, files: &[(&str, &str)]) {
+        fs::create_dir_all(root.join("metadata")).unwrap();
+        fs::create_dir_all(root.join(RAW_LYRICS_DIR)).unwrap();
+        let index: Vec<String> = files
+            .iter()
+            .map(|(filename, title)| index_line(filename, title))
+            .collect();
+        fs::write(root.join(INDEX_FILE_PATH_IN_REPO), index.join("\n")).unwrap();
+        for (filename, title) in files {
+            fs::write(root.join(RAW_LYRICS_DIR).join(filename), *title).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_open_archive_and_apply_update() {
+        let temp = TempDir::new();
+        let old_root = temp.0.join("old");
+        let new_root = temp.0.join("new/amll-ttml-db-main");
+        write_snapshot(&old_root, &[("1-a.ttml", "A"), ("2-b.ttml", "B")]);
+        write_snapshot(&new_root, &[("1-a.ttml", "A2"), ("3-c.ttml", "C")]);
+
+        // 把新快照打包成 GitHub 风格的 tar.gz，带一层外层目录
+        let archive_path = temp.0.join("snapshot.tar.gz");
+        let encoder = flate2::write::GzEncoder::new(
+            fs::File::create(&archive_path).unwrap(),
+            flate2::Compression::fast(),
+        );
+        let mut builder = tar::Builder::new(encoder);
+        builder
+            .append_dir_all("amll-ttml-db-main", &new_root)
+            .unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let extract_dir = temp.0.join("extracted");
+        let newer = LocalSnapshot::open_with_extract_dir(&archive_path, &extract_dir).unwrap();
+        assert_eq!(newer.read_lyric("3-c.ttml").unwrap(), "C");
+        assert!(matches!(
+            newer.read_lyric("../3-c.ttml"),
+            Err(LyricsHelperError::LyricNotFound)
+        ));
+
+        let local = LocalSnapshot::open(&old_root).unwrap();
+        let diff = local.apply_update(&newer).unwrap();
+        assert_eq!(diff.added, vec!["3-c.ttml"]);
+        assert_eq!(diff.removed, vec!["2-b.ttml"]);
+        assert_eq!(diff.metadata_changed, vec!["1-a.ttml"]);
+
+        assert_eq!(local.read_lyric("3-c.ttml").unwrap(), "C");
+        assert!(matches!(
+            local.read_lyric("2-b.ttml"),
+            Err(LyricsHelperError::LyricNotFound)
+        ));
+        assert_eq!(
+            index_head(&local.read_index().unwrap()),
+            index_head(&newer.read_index().unwrap())
+        );
+        assert!(local.diff(&newer).unwrap().is_empty());
+    }
+}
@@ -1,3 +1,4 @@
+use lyrics_helper_core::ReferencePlatform;
 use serde::{Deserialize, Deserializer};
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
@@ -19,6 +20,7 @@ pub struct Metadata {
     pub qq_music_ids: Vec<String>,
     pub apple_music_ids: Vec<String>,
     pub spotify_music_ids: Vec<String>,
+    pub isrcs: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -75,6 +77,10 @@ where
                 val.sort();
                 meta.spotify_music_ids = val;
             }
+            "isrc" => {
+                val.sort();
+                meta.isrcs = val;
+            }
             _ => {}
         }
     }
@@ -90,6 +96,18 @@ pub struct IndexEntry {
 }
 
 impl IndexEntry {
+    /// 返回该条目在指定平台上的所有 ID。
+    pub fn platform_ids(&self, platform: ReferencePlatform) -> &[String] {
+        match platform {
+            ReferencePlatform::Qq => &self.metadata.qq_music_ids,
+            ReferencePlatform::Netease => &self.metadata.ncm_music_ids,
+            ReferencePlatform::AppleMusic => &self.metadata.apple_music_ids,
+            ReferencePlatform::Spotify => &self.metadata.spotify_music_ids,
+            ReferencePlatform::Isrc => &self.metadata.isrcs,
+            ReferencePlatform::Kugou => &[],
+        }
+    }
+
     pub fn get_dedup_key(&self) -> DedupKey {
         if let Some(id) = self.metadata.ncm_music_ids.first() {
             return DedupKey::Ncm(id.clone());
@@ -30,8 +30,9 @@ use crate::{
 };
 
 use lyrics_helper_core::{
-    ConversionInput, ConversionOptions, CoverSize, FullLyricsResult, InputFile, Language,
-    LyricFormat, RawLyrics, SearchResult, Track, model::generic,
+    CanonicalMetadataKey, ContributorRole, ConversionInput, ConversionOptions, CoverSize,
+    FullLyricsResult, InputFile, Language, LyricContributor, LyricFormat, RawLyrics, SearchResult,
+    Track, model::generic,
 };
 
 pub mod decrypter;
@@ -311,6 +312,51 @@ impl KugouMusic {
 
         serde_json::from_str(&response_text).map_err(Into::into)
     }
+
+    /// 请求歌曲详情接口，返回第一条结果。
+    /// API: /`v2/get_res_privilege/lite`
+    async fn get_song_detail(&self, song_hash: &str) -> Result<models::SongDetailData> {
+        let payload = models::SongDetailRequestPayload {
+            appid: APP_ID,
+            clientver: CLIENT_VER,
+            area_code: "1",
+            behavior: "play",
+            need_hash_offset: 1,
+            relate: 1,
+            support_verify: 1,
+            resource: vec![models::SongDetailResource {
+                resource_type: "audio",
+                page_id: 0,
+                hash: song_hash,
+                album_id: 0,
+            }],
+            qualities: [
+                "128",
+                "320",
+                "flac",
+                "high",
+                "viper_atmos",
+                "viper_tape",
+                "viper_clear",
+            ],
+        };
+
+        let resp: models::SongDetailResponse = self
+            .execute_signed_post(API_URL_SONG_DETAIL, &payload, Some(X_ROUTER_MEDIA_STORE))
+            .await?;
+
+        if resp.status != 1 || resp.error_code != 0 {
+            return Err(LyricsHelperError::ApiError(format!(
+                "酷狗歌曲详情 API 错误 (status: {}, error_code: {})",
+                resp.status, resp.error_code
+            )));
+        }
+
+        resp.data
+            .into_iter()
+            .next()
+            .ok_or(LyricsHelperError::LyricNotFound)
+    }
 }
 
 #[async_trait]
@@ -510,18 +556,44 @@ impl Provider for KugouMusic {
         let options = ConversionOptions::default();
         let mut parsed_data = converter::parse_and_merge(&conversion_input, &options)?;
         parsed_data.source_name = self.name().to_string();
+        super::record_metadata(
+            &mut parsed_data,
+            &CanonicalMetadataKey::KugouHash,
+            song_hash,
+        );
+        let uploader_tags = super::take_uploader_tags(&mut parsed_data);
 
         let raw_lyrics = RawLyrics {
             format: "krc".to_string(),
             content: krc_decrypted,
             translation: None,  // 内嵌在 KRC 的 language 标签中，由解析器自己处理
             romanization: None, // 内嵌在 KRC 的 language 标签中，由解析器自己处理
+            contributors: Vec::new(),
         };
 
-        Ok(FullLyricsResult {
+        let mut result = FullLyricsResult {
             parsed: parsed_data,
             raw: raw_lyrics,
-        })
+        };
+        let lyrics_uploaders = if best_candidate.nickname.trim().is_empty() {
+            uploader_tags
+        } else {
+            vec![best_candidate.nickname.clone()]
+        };
+        for name in lyrics_uploaders {
+            result.add_contributor(LyricContributor {
+                role: ContributorRole::Lyrics,
+                name,
+                user_id: None,
+            });
+        }
+        result.add_contributor(LyricContributor {
+            role: ContributorRole::Translation,
+            name: best_candidate.transname.clone(),
+            user_id: None,
+        });
+
+        Ok(result)
     }
 
     #[instrument(skip(self, album_id))]
@@ -799,47 +871,7 @@ impl Provider for KugouMusic {
     /// API: /`v2/get_res_privilege/lite`
     #[instrument(skip(self))]
     async fn get_song_info(&self, song_id: &str) -> Result<generic::Song> {
-        let payload = models::SongDetailRequestPayload {
-            appid: APP_ID,
-            clientver: CLIENT_VER,
-            area_code: "1",
-            behavior: "play",
-            need_hash_offset: 1,
-            relate: 1,
-            support_verify: 1,
-            resource: vec![models::SongDetailResource {
-                resource_type: "audio",
-                page_id: 0,
-                hash: song_id,
-                album_id: 0,
-            }],
-            qualities: [
-                "128",
-                "320",
-                "flac",
-                "high",
-                "viper_atmos",
-                "viper_tape",
-                "viper_clear",
-            ],
-        };
-
-        let resp: models::SongDetailResponse = self
-            .execute_signed_post(API_URL_SONG_DETAIL, &payload, Some(X_ROUTER_MEDIA_STORE))
-            .await?;
-
-        if resp.status != 1 || resp.error_code != 0 {
-            return Err(LyricsHelperError::ApiError(format!(
-                "酷狗歌曲详情 API 错误 (status: {}, error_code: {})",
-                resp.status, resp.error_code
-            )));
-        }
-
-        let song_data = resp
-            .data
-            .into_iter()
-            .next()
-            .ok_or(LyricsHelperError::LyricNotFound)?;
+        let song_data = self.get_song_detail(song_id).await?;
 
         let artists = song_data
             .singername
@@ -861,7 +893,7 @@ impl Provider for KugouMusic {
             album: None,
             cover_url: Some(song_data.info.image.replace("{size}", "")),
             provider_id: song_data.hash,
-            album_id: None,
+            album_id: song_data.album_id.as_ref().and_then(json_id),
         })
     }
 
@@ -886,6 +918,16 @@ impl Provider for KugouMusic {
     }
 }
 
+/// 酷狗接口中的 ID 有时是数字，有时是字符串，`0` 或空值表示没有。
+fn json_id(value: &serde_json::Value) -> Option<String> {
+    let id = match value {
+        serde_json::Value::Number(n) => n.to_string(),
+        serde_json::Value::String(s) => s.trim().to_string(),
+        _ => return None,
+    };
+    (!id.is_empty() && id != "0").then_some(id)
+}
+
 fn map_language(lang_str: &str) -> Language {
     match lang_str {
         "国语" => Language::Chinese,
@@ -898,51 +940,43 @@ fn map_language(lang_str: &str) -> Language {
 
 #[cfg(test)]
 mod tests {
-    use tokio::sync::OnceCell;
-
     use super::*;
-
-    static KUGOU_PROVIDER: OnceCell<KugouMusic> = OnceCell::const_new();
-
-    static TEST_DFID: OnceCell<String> = OnceCell::const_new();
-
-    async fn get_dfid() -> &'static str {
-        TEST_DFID
-            .get_or_init(|| async {
-                let http_client =
-                    Arc::new(crate::http::WreqClient::new().expect("Failed to create HTTP client"));
-                let new_instance = KugouMusic::register_via_network(http_client)
-                    .await
-                    .expect("获取 DFID 失败");
-
-                let dfid = new_instance.dfid;
-
-                info!("成功获取 DFID: {}", dfid);
-                dfid
-            })
+    use crate::http::{MatchRules, replay::fixture_client};
+
+    /// 通过设备注册创建使用 `kugou/{fixture}.json` 夹具的提供商。
+    async fn test_provider(fixture: &str) -> KugouMusic {
+        // 签名和 `key` 都依赖请求时的秒级时间戳
+        let rules = MatchRules::default()
+            .ignore_query_param("clienttime")
+            .ignore_query_param("signature")
+            .ignore_body_field("clienttime")
+            .ignore_body_field("key");
+        KugouMusic::register_via_network(fixture_client(&format!("kugou/{fixture}"), rules))
             .await
+            .expect("注册酷狗设备失败")
     }
 
-    async fn get_test_provider() -> KugouMusic {
-        let dfid = get_dfid().await;
-        let http_client =
-            Arc::new(crate::http::WreqClient::new().expect("Failed to create HTTP client"));
-        KugouMusic::from_dfid(dfid.to_string(), http_client)
+    #[test]
+    fn test_song_detail_album_ids() {
+        let detail: models::SongDetailData = serde_json::from_value(json!({
+            "hash": "HASH",
+            "name": "歌手 - 歌曲",
+            "singername": "歌手",
+            "album_id": "12345",
+            "info": { "duration": 180_000, "image": "" }
+        }))
+        .unwrap();
+        assert_eq!(
+            detail.album_id.as_ref().and_then(json_id).as_deref(),
+            Some("12345")
+        );
+        assert_eq!(json_id(&json!("0")), None);
+        assert_eq!(json_id(&json!(null)), None);
     }
 
     const TEST_SONG_NAME: &str = "目及皆是你";
     const TEST_SINGER_NAME: &str = "小蓝背心";
 
-    async fn get_provider() -> &'static KugouMusic {
-        KUGOU_PROVIDER
-            .get_or_init(|| async {
-                KugouMusic::new()
-                    .await
-                    .expect("初始化 KugouMusic Provider 失败")
-            })
-            .await
-    }
-
     fn init_tracing() {
         use tracing_subscriber::{EnvFilter, FmtSubscriber};
         let filter = EnvFilter::try_from_default_env()
@@ -954,10 +988,9 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore]
     async fn test_full_flow_kugou() {
         init_tracing();
-        let provider = get_provider().await;
+        let provider = test_provider("full_flow_kugou").await;
 
         info!(
             "[INFO] 正在搜索歌曲 '{}({})'",
@@ -968,6 +1001,7 @@ mod tests {
             artists: Some(&[TEST_SINGER_NAME]),
             album: Some(TEST_SONG_NAME),
             duration: Some(231_000),
+            version_hints: None,
         };
         let search_results = provider.search_songs(&search_track).await.unwrap();
         assert!(!search_results.is_empty(), "搜索应返回结果。");
@@ -1005,10 +1039,9 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore]
     async fn test_integration_get_full_lyrics() {
         init_tracing();
-        let provider = get_test_provider().await;
+        let provider = test_provider("get_full_lyrics").await;
 
         let song_hash = "FDCE75F4195C7650681D5BE6ADF5973F";
 
@@ -1028,10 +1061,9 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore]
     async fn test_integration_get_album_info() {
         init_tracing();
-        let provider = get_test_provider().await;
+        let provider = test_provider("get_album_info").await;
 
         let album_id = "146986426";
 
@@ -1049,10 +1081,9 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore]
     async fn test_integration_get_album_info_invalid_id() {
         init_tracing();
-        let provider = get_test_provider().await;
+        let provider = test_provider("get_album_info_invalid_id").await;
 
         let invalid_album_id = "000000000";
 
@@ -1068,10 +1099,9 @@ mod tests {
         }
     }
     #[tokio::test]
-    #[ignore]
     async fn test_integration_get_album_songs() {
         init_tracing();
-        let provider = get_test_provider().await;
+        let provider = test_provider("get_album_songs").await;
 
         let album_id = "146986426";
 
@@ -1087,10 +1117,9 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore]
     async fn test_integration_get_singer_songs() {
         init_tracing();
-        let provider = get_test_provider().await;
+        let provider = test_provider("get_singer_songs").await;
 
         let singer_id = "5579497";
 
@@ -1114,10 +1143,9 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore]
     async fn test_integration_get_playlist() {
         init_tracing();
-        let provider = get_test_provider().await;
+        let provider = test_provider("get_playlist").await;
 
         let playlist_id = "collection_3_2132040296_8_0";
 
@@ -1141,10 +1169,9 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore]
     async fn test_integration_get_song_info() {
         init_tracing();
-        let provider = get_test_provider().await;
+        let provider = test_provider("get_song_info").await;
 
         let song_hash = "DBE68B72F69025954B1E2EC0D06D7C9E";
 
@@ -1160,10 +1187,9 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore]
     async fn test_integration_get_album_cover_url() {
         init_tracing();
-        let provider = get_test_provider().await;
+        let provider = test_provider("get_album_cover_url").await;
         let album_id = "146986426";
 
         let large_cover_url = provider
@@ -120,6 +120,12 @@ pub struct Candidate {
     pub id: String,
     /// 下载歌词所需的访问密钥 (access key)。
     pub accesskey: String,
+    /// 上传歌词的用户昵称。
+    #[serde(default)]
+    pub nickname: String,
+    /// 上传翻译的用户昵称。
+    #[serde(default)]
+    pub transname: String,
 }
 
 // =================================================================
@@ -492,6 +498,9 @@ pub struct SongDetailData {
     pub name: String,
     /// 单独的歌手名字段。
     pub singername: String,
+    /// 专辑 ID，可能是数字或字符串。
+    #[serde(default)]
+    pub album_id: Option<serde_json::Value>,
     /// 包含时长和封面等信息的嵌套对象。
     pub info: SongDetailInfo,
 }
@@ -0,0 +1,136 @@
+//! 本地歌词库的磁盘索引。
+//!
+//! 索引由两部分组成：`entries.json` 保存每个文件的元数据、修改时间和大小，
+//! 用于增量更新；`terms.fst` 是从标题、艺术家的检索词映射到条目编号的 FST，
+//! 用于在搜索时快速找出候选项。
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::Path,
+};
+
+use fst::{Automaton, IntoStreamer, Map, MapBuilder, Streamer, automaton::Str};
+use lyrics_helper_core::LyricFormat;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{LyricsHelperError, Result},
+    search::terms::search_terms,
+};
+
+pub(super) const ENTRIES_FILENAME: &str = "entries.json";
+pub(super) const TERMS_FILENAME: &str = "terms.fst";
+
+/// 检索词和条目编号之间的分隔符，检索词本身不会包含它。
+const TERM_SEPARATOR: u8 = 0;
+
+/// 本地歌词库中的一个歌词文件。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LibraryEntry {
+    /// 相对于歌词库根目录的路径，使用 `/` 分隔，同时作为歌曲 ID。
+    pub path: String,
+    /// 歌词文件的格式。
+    pub format: LyricFormat,
+    /// 歌曲标题。
+    pub title: String,
+    /// 艺术家列表。
+    pub artists: Vec<String>,
+    /// 专辑名。
+    pub album: Option<String>,
+    /// 根据最后一行歌词的结束时间估算的时长（毫秒）。
+    pub duration: Option<u64>,
+    /// 文件的修改时间（Unix 毫秒时间戳）。
+    pub(super) modified_ms: u64,
+    /// 文件大小（字节）。
+    pub(super) size: u64,
+}
+
+/// 内存中的索引。
+#[derive(Default)]
+pub(super) struct LibraryIndex {
+    pub(super) entries: Vec<LibraryEntry>,
+    terms: Map<Vec<u8>>,
+}
+
+impl LibraryIndex {
+    /// 根据条目列表构建索引。
+    pub(super) fn build(mut entries: Vec<LibraryEntry>) -> Result<Self> {
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+
+        // FST 要求键按字节序插入且不重复
+        let mut keys: BTreeMap<Vec<u8>, u64> = BTreeMap::new();
+        for (id, entry) in entries.iter().enumerate() {
+            let texts = std::iter::once(&entry.title).chain(&entry.artists);
+            for term in texts.flat_map(|text| search_terms(text)) {
+                let mut key = term.into_bytes();
+                key.push(TERM_SEPARATOR);
+                key.extend_from_slice(&(id as u32).to_be_bytes());
+                keys.insert(key, id as u64);
+            }
+        }
+
+        let mut builder = MapBuilder::memory();
+        for (key, id) in keys {
+            builder.insert(key, id).map_err(|e| fst_error(&e))?;
+        }
+        let terms = Map::new(builder.into_inner().map_err(|e| fst_error(&e))?)
+            .map_err(|e| fst_error(&e))?;
+
+        Ok(Self { entries, terms })
+    }
+
+    /// 从索引目录加载索引。FST 文件缺失或损坏时会根据条目列表重新构建。
+    pub(super) fn load(dir: &Path) -> Result<Self> {
+        let content = fs::read_to_string(dir.join(ENTRIES_FILENAME))?;
+        let entries: Vec<LibraryEntry> = serde_json::from_str(&content)?;
+
+        let terms = fs::read(dir.join(TERMS_FILENAME))
+            .ok()
+            .and_then(|bytes| Map::new(bytes).ok());
+        match terms {
+            Some(terms) if !terms.is_empty() || entries.is_empty() => Ok(Self { entries, terms }),
+            _ => {
+                tracing::warn!("[LocalLibrary] FST 索引不可用，将重新构建。");
+                Self::build(entries)
+            }
+        }
+    }
+
+    /// 将索引写入索引目录。
+    pub(super) fn save(&self, dir: &Path) -> Result<()> {
+        fs::create_dir_all(dir)?;
+        fs::write(
+            dir.join(ENTRIES_FILENAME),
+            serde_json::to_string(&self.entries)?,
+        )?;
+        fs::write(dir.join(TERMS_FILENAME), self.terms.as_fst().as_bytes())?;
+        Ok(())
+    }
+
+    /// 返回检索词能以前缀形式命中的所有条目。
+    pub(super) fn candidates(&self, query: &str) -> Vec<&LibraryEntry> {
+        let mut ids = BTreeSet::new();
+        for term in search_terms(query) {
+            let automaton = Str::new(&term).starts_with();
+            let mut stream = self.terms.search(automaton).into_stream();
+            while let Some((_, id)) = stream.next() {
+                ids.insert(id as usize);
+            }
+        }
+        ids.into_iter()
+            .filter_map(|id| self.entries.get(id))
+            .collect()
+    }
+
+    pub(super) fn get(&self, path: &str) -> Option<&LibraryEntry> {
+        self.entries
+            .binary_search_by(|entry| entry.path.as_str().cmp(path))
+            .ok()
+            .map(|i| &self.entries[i])
+    }
+}
+
+fn fst_error(e: &fst::Error) -> LyricsHelperError {
+    LyricsHelperError::Internal(format!("构建本地歌词库索引失败: {e}"))
+}
@@ -0,0 +1,567 @@
+//! 此模块实现了基于本地歌词文件夹的 `Provider`。
+//!
+//! `LocalLibraryProvider` 会递归扫描一个目录中的歌词文件，从文件内嵌的元数据标签
+//! （如 LRC 的 `[ti:]`、`[ar:]`，TTML 的 `musicName`、`artists`）和
+//! `艺术家 - 标题.扩展名` 形式的文件名中提取歌曲信息，并建立基于 FST 的磁盘索引。
+//! 重新索引时只会解析新增或修改过的文件。
+//!
+//! 搜索结果同样使用 `compare_track` 评分。注册到 `LyricsHelper` 时为它设置更高的优先级，
+//! 可以让本地整理的歌词在匹配度相同时优先于在线来源。
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::UNIX_EPOCH,
+};
+
+use async_trait::async_trait;
+use md5::{Digest, Md5};
+use parking_lot::RwLock;
+
+use crate::{
+    converter,
+    error::{LyricsHelperError, Result},
+    http::HttpClient,
+    model::match_type::MatchScorable,
+    providers::Provider,
+    search::matcher::compare_track,
+};
+
+use lyrics_helper_core::{
+    CanonicalMetadataKey, ConversionInput, ConversionOptions, CoverSize, FullLyricsResult,
+    InputFile, LyricFormat, MatchType, MetadataStore, ParsedSourceData, RawLyrics, SearchResult,
+    Track, model::generic,
+};
+
+mod index;
+pub use index::LibraryEntry;
+use index::LibraryIndex;
+
+const PROVIDER_NAME: &str = "local-library";
+const MAX_SEARCH_RESULTS: usize = 20;
+
+/// 一次重新索引的统计结果。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReindexSummary {
+    /// 新增的文件数。
+    pub added: usize,
+    /// 内容有变化、被重新解析的文件数。
+    pub updated: usize,
+    /// 已被删除的文件数。
+    pub removed: usize,
+    /// 未变化、直接复用索引的文件数。
+    pub unchanged: usize,
+}
+
+/// 本地歌词库提供商的实现。
+pub struct LocalLibraryProvider {
+    root: PathBuf,
+    index_dir: PathBuf,
+    index: RwLock<LibraryIndex>,
+}
+
+impl LocalLibraryProvider {
+    /// 打开一个本地歌词库，索引保存在用户缓存目录中。
+    ///
+    /// 如果已有索引，会先加载它，再增量地扫描目录中的变化。
+    pub fn open(root: impl Into<PathBuf>) -> Result<Self> {
+        let root = root.into();
+        let index_dir = default_index_dir(&root)?;
+        Self::open_with_index_dir(root, index_dir)
+    }
+
+    /// 打开一个本地歌词库，并把索引保存在指定的目录中。
+    pub fn open_with_index_dir(
+        root: impl Into<PathBuf>,
+        index_dir: impl Into<PathBuf>,
+    ) -> Result<Self> {
+        let root = root.into();
+        if !root.is_dir() {
+            return Err(LyricsHelperError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("歌词库目录不存在: {}", root.display()),
+            )));
+        }
+        let index_dir = index_dir.into();
+
+        let index = LibraryIndex::load(&index_dir).unwrap_or_else(|e| {
+            tracing::info!("[LocalLibrary] 未能加载已有索引 ({e})，将重新建立。");
+            LibraryIndex::default()
+        });
+
+        let provider = Self {
+            root,
+            index_dir,
+            index: RwLock::new(index),
+        };
+        provider.reindex()?;
+        Ok(provider)
+    }
+
+    /// 歌词库的根目录。
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// 已索引的歌词文件数量。
+    #[must_use]
+    pub fn entry_count(&self) -> usize {
+        self.index.read().entries.len()
+    }
+
+    /// 增量地重新索引歌词库。
+    ///
+    /// 只有修改时间或大小发生变化的文件会被重新解析，已删除的文件会从索引中移除。
+    /// 索引有变化时会写回磁盘。
+    pub fn reindex(&self) -> Result<ReindexSummary> {
+        let mut files = Vec::new();
+        collect_lyric_files(&self.root, &mut files)?;
+
+        let mut previous: HashMap<String, LibraryEntry> = self
+            .index
+            .read()
+            .entries
+            .iter()
+            .map(|entry| (entry.path.clone(), entry.clone()))
+            .collect();
+
+        let mut summary = ReindexSummary::default();
+        let mut entries = Vec::with_capacity(files.len());
+        for (path, format) in files {
+            let Some(relative) = relative_id(&self.root, &path) else {
+                continue;
+            };
+            let metadata = fs::metadata(&path)?;
+            let modified_ms = metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_millis() as u64);
+            let size = metadata.len();
+
+            match previous.remove(&relative) {
+                Some(entry) if entry.modified_ms == modified_ms && entry.size == size => {
+                    summary.unchanged += 1;
+                    entries.push(entry);
+                    continue;
+                }
+                Some(_) => summary.updated += 1,
+                None => summary.added += 1,
+            }
+
+            match index_file(&path, relative, format, modified_ms, size) {
+                Ok(entry) => entries.push(entry),
+                Err(e) => tracing::warn!("[LocalLibrary] 无法索引文件 {}: {e}", path.display()),
+            }
+        }
+        summary.removed = previous.len();
+
+        if summary.added + summary.updated + summary.removed > 0 || !self.index_dir.exists() {
+            let index = LibraryIndex::build(entries)?;
+            index.save(&self.index_dir)?;
+            *self.index.write() = index;
+        }
+
+        tracing::info!(
+            "[LocalLibrary] 索引完成: 新增 {}, 更新 {}, 删除 {}, 未变化 {}。",
+            summary.added,
+            summary.updated,
+            summary.removed,
+            summary.unchanged
+        );
+        Ok(summary)
+    }
+
+    fn score_entry(entry: &LibraryEntry, track: &Track<'_>) -> (MatchType, SearchResult) {
+        let result = SearchResult {
+            title: entry.title.clone(),
+            artists: entry
+                .artists
+                .iter()
+                .map(|name| generic::Artist {
+                    id: String::new(),
+                    name: name.clone(),
+                })
+                .collect(),
+            album: entry.album.clone(),
+            duration: entry.duration,
+            provider_id: entry.path.clone(),
+            provider_name: PROVIDER_NAME.to_string(),
+            ..Default::default()
+        };
+        (compare_track(track, &result), result)
+    }
+}
+
+#[async_trait]
+impl Provider for LocalLibraryProvider {
+    fn name(&self) -> &'static str {
+        PROVIDER_NAME
+    }
+
+    /// 本地歌词库需要一个目录，无法仅凭 HTTP 客户端创建。
+    async fn with_http_client(_http_client: Arc<dyn HttpClient>) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        Err(LyricsHelperError::ProviderNotSupported(
+            "local-library 需要通过 LocalLibraryProvider::open 创建".into(),
+        ))
+    }
+
+    async fn search_songs(&self, track: &Track<'_>) -> Result<Vec<SearchResult>> {
+        let query = match (track.title, track.artists) {
+            (Some(title), _) => title.to_string(),
+            (None, Some(artists)) => artists.join(" "),
+            (None, None) => return Ok(vec![]),
+        };
+
+        let index = self.index.read();
+        let mut scored: Vec<(MatchType, SearchResult)> = index
+            .candidates(&query)
+            .into_iter()
+            .map(|entry| Self::score_entry(entry, track))
+            .filter(|(match_type, _)| *match_type >= MatchType::VeryLow)
+            .collect();
+        drop(index);
+
+        scored.sort_by_key(|(match_type, _)| std::cmp::Reverse(match_type.get_score()));
+        Ok(scored
+            .into_iter()
+            .take(MAX_SEARCH_RESULTS)
+            .map(|(_, result)| result)
+            .collect())
+    }
+
+    /// 读取并解析歌词文件。`song_id` 是文件相对于歌词库根目录的路径。
+    async fn get_full_lyrics(&self, song_id: &str) -> Result<FullLyricsResult> {
+        // 只允许读取已被索引的文件，避免通过 ID 访问歌词库以外的路径
+        let format = self
+            .index
+            .read()
+            .get(song_id)
+            .map(|entry| entry.format)
+            .ok_or(LyricsHelperError::LyricNotFound)?;
+
+        let path = self.root.join(song_id);
+        let content = tokio::task::spawn_blocking(move || fs::read_to_string(path))
+            .await
+            .map_err(|e| LyricsHelperError::Internal(format!("读取歌词文件失败: {e}")))??;
+        let mut parsed = parse_file(&content, format, song_id)?;
+        parsed.source_name = PROVIDER_NAME.to_string();
+
+        Ok(FullLyricsResult {
+            parsed,
+            raw: RawLyrics {
+                format: format.to_extension_str().to_string(),
+                content,
+                translation: None,
+                romanization: None,
+                contributors: Vec::new(),
+            },
+        })
+    }
+
+    async fn get_lyrics(&self, song_id: &str) -> Result<ParsedSourceData> {
+        Ok(self.get_full_lyrics(song_id).await?.parsed)
+    }
+
+    async fn get_album_info(&self, _: &str) -> Result<generic::Album> {
+        Err(LyricsHelperError::ProviderNotSupported(
+            "local-library 不支持 get_album_info".into(),
+        ))
+    }
+
+    async fn get_album_songs(
+        &self,
+        _album_id: &str,
+        _page: u32,
+        _page_size: u32,
+    ) -> Result<Vec<generic::Song>> {
+        Err(LyricsHelperError::ProviderNotSupported(
+            "local-library 不支持 get_album_songs".to_string(),
+        ))
+    }
+
+    async fn get_singer_songs(
+        &self,
+        _singer_id: &str,
+        _page: u32,
+        _page_size: u32,
+    ) -> Result<Vec<generic::Song>> {
+        Err(LyricsHelperError::ProviderNotSupported(
+            "local-library 不支持 get_singer_songs".to_string(),
+        ))
+    }
+
+    async fn get_playlist(&self, _playlist_id: &str) -> Result<generic::Playlist> {
+        Err(LyricsHelperError::ProviderNotSupported(
+            "local-library 不支持 get_playlist".to_string(),
+        ))
+    }
+
+    async fn get_song_info(&self, _song_id: &str) -> Result<generic::Song> {
+        Err(LyricsHelperError::ProviderNotSupported(
+            "local-library 不支持 get_song_info".to_string(),
+        ))
+    }
+
+    async fn get_album_cover_url(&self, _album_id: &str, _size: CoverSize) -> Result<String> {
+        Err(LyricsHelperError::ProviderNotSupported(
+            "local-library 不支持 get_album_cover_url".into(),
+        ))
+    }
+}
+
+/// 默认的索引目录：缓存目录下以歌词库路径的哈希命名的子目录。
+fn default_index_dir(root: &Path) -> Result<PathBuf> {
+    let canonical = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    let hash = hex::encode(Md5::digest(canonical.to_string_lossy().as_bytes()));
+    Ok(crate::config::get_cache_file_path(&format!(
+        "local_library/{hash}"
+    ))?)
+}
+
+/// 递归收集目录中所有可识别的歌词文件，跳过隐藏文件和目录。
+fn collect_lyric_files(dir: &Path, files: &mut Vec<(PathBuf, LyricFormat)>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        if entry.file_type()?.is_dir() {
+            collect_lyric_files(&path, files)?;
+        } else if let Some(format) = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(LyricFormat::from_string)
+            // 任意 JSON 文件都可能出现在目录中，不把它们当作歌词
+            .filter(|format| *format != LyricFormat::AppleMusicJson)
+        {
+            files.push((path, format));
+        }
+    }
+    Ok(())
+}
+
+/// 计算文件相对于根目录的 ID，统一使用 `/` 作为分隔符。
+fn relative_id(root: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(root).ok()?;
+    let parts: Vec<&str> = relative
+        .components()
+        .map(|c| c.as_os_str().to_str())
+        .collect::<Option<_>>()?;
+    Some(parts.join("/"))
+}
+
+fn parse_file(content: &str, format: LyricFormat, filename: &str) -> Result<ParsedSourceData> {
+    let input = ConversionInput {
+        main_lyric: InputFile {
+            content: content.to_string(),
+            format,
+            language: None,
+            filename: Some(filename.to_string()),
+        },
+        translations: vec![],
+        romanizations: vec![],
+        target_format: LyricFormat::default(),
+        user_metadata_overrides: None,
+        additional_metadata: None,
+    };
+    converter::parse_and_merge(&input, &ConversionOptions::default())
+        .map_err(|e| LyricsHelperError::Parser(e.to_string()))
+}
+
+/// 解析单个文件并生成索引条目。内嵌标签优先于文件名中的信息。
+fn index_file(
+    path: &Path,
+    relative: String,
+    format: LyricFormat,
+    modified_ms: u64,
+    size: u64,
+) -> Result<LibraryEntry> {
+    let content = fs::read_to_string(path)?;
+    let parsed = parse_file(&content, format, &relative)?;
+    let metadata = MetadataStore::from(&parsed);
+
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let (filename_artists, filename_title) = parse_filename(&stem);
+
+    let title = metadata
+        .get_single_value(&CanonicalMetadataKey::Title)
+        .filter(|t| !t.trim().is_empty())
+        .cloned()
+        .unwrap_or(filename_title);
+    let artists = metadata
+        .get_multiple_values(&CanonicalMetadataKey::Artist)
+        .filter(|a| !a.is_empty())
+        .cloned()
+        .unwrap_or(filename_artists);
+    let album = metadata
+        .get_single_value(&CanonicalMetadataKey::Album)
+        .cloned();
+    let duration = parsed.lines.iter().map(|line| line.end_ms).max();
+
+    Ok(LibraryEntry {
+        path: relative,
+        format,
+        title,
+        artists,
+        album,
+        duration,
+        modified_ms,
+        size,
+    })
+}
+
+/// 从 `艺术家 - 标题` 形式的文件名中提取艺术家和标题。
+///
+/// 多位艺术家可以用 `、`、`/`、`&` 或 `,` 分隔。不符合该形式时，整个文件名作为标题。
+fn parse_filename(stem: &str) -> (Vec<String>, String) {
+    match stem.split_once(" - ") {
+        Some((artists, title)) if !artists.trim().is_empty() && !title.trim().is_empty() => (
+            artists
+                .split(['、', '/', '&', ','])
+                .map(str::trim)
+                .filter(|a| !a.is_empty())
+                .map(str::to_string)
+                .collect(),
+            title.trim().to_string(),
+        ),
+        _ => (Vec::new(), stem.trim().to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TempLibrary {
+        dir: PathBuf,
+    }
+
+    impl TempLibrary {
+        fn new() -> Self {
+            let dir = std::env::temp_dir().join(format!("local-library-{}", uuid::Uuid::new_v4()));
+            fs::create_dir_all(dir.join("lyrics/sub")).unwrap();
+            Self { dir }
+        }
+
+        fn root(&self) -> PathBuf {
+            self.dir.join("lyrics")
+        }
+
+        fn index_dir(&self) -> PathBuf {
+            self.dir.join("index")
+        }
+
+        fn write(&self, name: &str, content: &str) {
+            fs::write(self.root().join(name), content).unwrap();
+        }
+    }
+
+    impl Drop for TempLibrary {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    fn query<'a>(title: &'a str, artists: Option<&'a [&'a str]>) -> Track<'a> {
+        Track {
+            title: Some(title),
+            artists,
+            album: None,
+            duration: None,
+            version_hints: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_index_search_and_fetch() {
+        let library = TempLibrary::new();
+        library.write(
+            "Artist A - Song A.lrc",
+            "[00:01.00]First line\n[00:03.00]Second line\n",
+        );
+        library.write(
+            "sub/untitled.lrc",
+            "[ti:Evening Song]\n[ar:Artist B]\n[al:Album B]\n[00:01.00]Hello\n",
+        );
+        library.write("notes.txt", "not lyrics");
+
+        let provider =
+            LocalLibraryProvider::open_with_index_dir(library.root(), library.index_dir()).unwrap();
+        assert_eq!(provider.entry_count(), 2);
+
+        let results = provider
+            .search_songs(&query("Song A", Some(&["Artist A"])))
+            .await
+            .unwrap();
+        assert_eq!(results[0].provider_id, "Artist A - Song A.lrc");
+        assert_eq!(results[0].artists[0].name, "Artist A");
+
+        let results = provider
+            .search_songs(&query("evening", None))
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].provider_id, "sub/untitled.lrc");
+        assert_eq!(results[0].album.as_deref(), Some("Album B"));
+
+        let lyrics = provider
+            .get_full_lyrics("Artist A - Song A.lrc")
+            .await
+            .unwrap();
+        assert_eq!(lyrics.parsed.lines.len(), 2);
+        assert_eq!(lyrics.parsed.source_name, "local-library");
+        assert!(matches!(
+            provider.get_full_lyrics("../outside.lrc").await,
+            Err(LyricsHelperError::LyricNotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_incremental_reindex() {
+        let library = TempLibrary::new();
+        library.write("Artist A - Song A.lrc", "[00:01.00]First line\n");
+        library.write("Artist B - Song B.lrc", "[00:01.00]Another line\n");
+
+        let provider =
+            LocalLibraryProvider::open_with_index_dir(library.root(), library.index_dir()).unwrap();
+
+        library.write(
+            "Artist A - Song A.lrc",
+            "[ti:Renamed Song]\n[00:01.00]First line\n",
+        );
+        fs::remove_file(library.root().join("Artist B - Song B.lrc")).unwrap();
+        library.write("sub/Artist C - Song C.ttml", "<tt></tt>");
+        library.write("Artist D - Song D.qrc", "");
+
+        let summary = provider.reindex().unwrap();
+        assert_eq!(summary.updated, 1);
+        assert_eq!(summary.removed, 1);
+        assert_eq!(summary.added, 2);
+
+        let results = provider
+            .search_songs(&query("Renamed Song", None))
+            .await
+            .unwrap();
+        assert_eq!(results[0].provider_id, "Artist A - Song A.lrc");
+
+        // 重新打开时应直接复用磁盘上的索引
+        let reopened =
+            LocalLibraryProvider::open_with_index_dir(library.root(), library.index_dir()).unwrap();
+        assert_eq!(reopened.entry_count(), provider.entry_count());
+        assert_eq!(
+            reopened.reindex().unwrap().unchanged,
+            reopened.entry_count()
+        );
+    }
+}
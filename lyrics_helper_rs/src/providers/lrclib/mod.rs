@@ -0,0 +1,676 @@
+//! 此模块实现了与 LRCLIB 兼容的歌词服务进行交互的 `Provider`。
+//!
+//! 除了公共的 <https://lrclib.net>，也可以通过 [`LrclibProvider::with_base_url`]
+//! 指向自建的兼容服务器。支持搜索、按歌曲签名精确查找，以及通过工作量证明发布歌词。
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    converter,
+    error::{LyricsHelperError, Result},
+    http::{HttpClient, HttpMethod, HttpResponse},
+    providers::Provider,
+};
+
+use lyrics_helper_core::{
+    ContentType, ConversionInput, ConversionOptions, CoverSize, FullLyricsResult, InputFile,
+    LyricFormat, LyricLine, ParsedSourceData, RawLyrics, SearchResult, Track, model::generic,
+};
+
+mod models;
+
+/// 公共 LRCLIB 服务的地址。
+pub const DEFAULT_BASE_URL: &str = "https://lrclib.net";
+
+/// LRCLIB 建议客户端通过该请求头标识自己，格式为 `名称 v版本 (项目地址)`。
+const CLIENT_HEADER: (&str, &str) = (
+    "Lrclib-Client",
+    concat!(
+        env!("CARGO_PKG_NAME"),
+        " v",
+        env!("CARGO_PKG_VERSION"),
+        " (",
+        env!("CARGO_PKG_REPOSITORY"),
+        ")"
+    ),
+);
+
+/// 选择使用哪一种歌词。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LyricsSelection {
+    /// 优先使用带时间轴的歌词，没有时回退到纯文本歌词。
+    #[default]
+    PreferSynced,
+    /// 只使用带时间轴的歌词。
+    SyncedOnly,
+    /// 只使用纯文本歌词。
+    PlainOnly,
+}
+
+/// 发布到 LRCLIB 的歌词。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LrclibPublishRequest {
+    /// 歌曲标题。
+    pub track_name: String,
+    /// 艺术家名。
+    pub artist_name: String,
+    /// 专辑名。
+    pub album_name: String,
+    /// 歌曲时长（秒）。
+    pub duration: f64,
+    /// 不带时间轴的纯文本歌词。
+    pub plain_lyrics: String,
+    /// 逐行的 LRC 歌词。
+    pub synced_lyrics: String,
+}
+
+impl LrclibPublishRequest {
+    /// 根据 LRC 歌词创建发布请求，纯文本歌词由 LRC 去掉时间标签和元数据标签后得到。
+    #[must_use]
+    pub fn from_lrc(
+        track_name: impl Into<String>,
+        artist_name: impl Into<String>,
+        album_name: impl Into<String>,
+        duration_ms: u64,
+        synced_lrc: &str,
+    ) -> Self {
+        Self {
+            track_name: track_name.into(),
+            artist_name: artist_name.into(),
+            album_name: album_name.into(),
+            duration: duration_ms as f64 / 1000.0,
+            plain_lyrics: plain_text_from_lrc(synced_lrc),
+            synced_lyrics: synced_lrc.to_string(),
+        }
+    }
+}
+
+/// LRCLIB 兼容服务的提供商实现。
+pub struct LrclibProvider {
+    http_client: Arc<dyn HttpClient>,
+    base_url: String,
+    selection: LyricsSelection,
+}
+
+impl LrclibProvider {
+    /// 创建一个指向指定服务器的提供商，例如 `http://192.168.1.10:3300`。
+    pub fn with_base_url(http_client: Arc<dyn HttpClient>, base_url: impl Into<String>) -> Self {
+        Self {
+            http_client,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            selection: LyricsSelection::default(),
+        }
+    }
+
+    /// 设置获取歌词时选择哪一种歌词。
+    #[must_use]
+    pub const fn with_lyrics_selection(mut self, selection: LyricsSelection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// 服务器地址。
+    #[must_use]
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// 按歌曲签名（标题、艺术家、专辑、时长）精确查找歌词。
+    ///
+    /// 服务器没有对应记录时返回 `LyricNotFound`。
+    pub async fn get_by_signature(&self, track: &Track<'_>) -> Result<FullLyricsResult> {
+        let (Some(title), Some(artists)) = (track.title, track.artists) else {
+            return Err(LyricsHelperError::Internal(
+                "按签名查找歌词需要提供标题和艺术家".into(),
+            ));
+        };
+        let artist_name = artists.join(", ");
+        let duration = track.duration.map(|ms| (ms / 1000).to_string());
+
+        let mut params = vec![("track_name", title), ("artist_name", artist_name.as_str())];
+        if let Some(album) = track.album {
+            params.push(("album_name", album));
+        }
+        if let Some(duration) = &duration {
+            params.push(("duration", duration.as_str()));
+        }
+
+        let response = self
+            .http_client
+            .get_with_params_and_headers(&self.url("/api/get"), &params, &[CLIENT_HEADER])
+            .await?;
+        let record: models::LyricsRecord = parse_response(&response)?;
+        self.build_full_lyrics_result(record)
+    }
+
+    /// 把歌词发布到服务器。
+    ///
+    /// 会先请求一个工作量证明挑战并在阻塞线程中求解，然后携带发布令牌提交歌词。
+    pub async fn publish(&self, request: &LrclibPublishRequest) -> Result<()> {
+        let response = self
+            .http_client
+            .request_with_headers(
+                HttpMethod::Post,
+                &self.url("/api/request-challenge"),
+                &[CLIENT_HEADER],
+                None,
+            )
+            .await?;
+        let challenge: models::PublishChallenge = parse_response(&response)?;
+
+        let nonce = tokio::task::spawn_blocking(move || {
+            solve_challenge(&challenge.prefix, &challenge.target)
+                .map(|nonce| format!("{}:{nonce}", challenge.prefix))
+        })
+        .await
+        .map_err(|e| LyricsHelperError::Internal(format!("求解发布挑战失败: {e}")))??;
+
+        let body = serde_json::to_vec(request)?;
+        let response = self
+            .http_client
+            .request_with_headers(
+                HttpMethod::Post,
+                &self.url("/api/publish"),
+                &[
+                    CLIENT_HEADER,
+                    ("Content-Type", "application/json"),
+                    ("X-Publish-Token", &nonce),
+                ],
+                Some(&body),
+            )
+            .await?;
+
+        if response.status >= 400 {
+            return Err(api_error(&response));
+        }
+        tracing::info!(
+            "[LRCLIB] 已发布歌词: {} - {}",
+            request.artist_name,
+            request.track_name
+        );
+        Ok(())
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.base_url)
+    }
+
+    fn build_full_lyrics_result(&self, record: models::LyricsRecord) -> Result<FullLyricsResult> {
+        if record.instrumental {
+            return Err(LyricsHelperError::LyricNotFound);
+        }
+        let synced = record.synced_lyrics.filter(|s| !s.trim().is_empty());
+        let plain = record.plain_lyrics.filter(|s| !s.trim().is_empty());
+
+        let (mut parsed, raw) = match (self.selection, synced, plain) {
+            (LyricsSelection::PreferSynced | LyricsSelection::SyncedOnly, Some(synced), _) => {
+                let input = ConversionInput {
+                    main_lyric: InputFile {
+                        content: synced.clone(),
+                        format: LyricFormat::Lrc,
+                        language: None,
+                        filename: None,
+                    },
+                    translations: vec![],
+                    romanizations: vec![],
+                    target_format: LyricFormat::Lrc,
+                    user_metadata_overrides: None,
+                    additional_metadata: None,
+                };
+                let parsed = converter::parse_and_merge(&input, &ConversionOptions::default())?;
+                (parsed, ("lrc", synced))
+            }
+            (LyricsSelection::PreferSynced | LyricsSelection::PlainOnly, _, Some(plain)) => {
+                (parse_plain_lyrics(&plain), ("txt", plain))
+            }
+            _ => return Err(LyricsHelperError::LyricNotFound),
+        };
+
+        parsed.source_name = self.name().to_string();
+        let metadata = [
+            ("title", Some(record.track_name)),
+            ("artist", Some(record.artist_name)),
+            ("album", record.album_name),
+            ("lrclibId", Some(record.id.to_string())),
+        ];
+        for (key, value) in metadata {
+            if let Some(value) = value.filter(|v| !v.is_empty()) {
+                parsed
+                    .raw_metadata
+                    .entry(key.to_string())
+                    .or_insert_with(|| vec![value]);
+            }
+        }
+
+        Ok(FullLyricsResult {
+            parsed,
+            raw: RawLyrics {
+                format: raw.0.to_string(),
+                content: raw.1,
+                translation: None,
+                romanization: None,
+                contributors: Vec::new(),
+            },
+        })
+    }
+}
+
+#[async_trait]
+impl Provider for LrclibProvider {
+    fn name(&self) -> &'static str {
+        "lrclib"
+    }
+
+    /// 使用公共 LRCLIB 服务创建提供商。
+    async fn with_http_client(http_client: Arc<dyn HttpClient>) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        Ok(Self::with_base_url(http_client, DEFAULT_BASE_URL))
+    }
+
+    async fn search_songs(&self, track: &Track<'_>) -> Result<Vec<SearchResult>> {
+        let Some(title) = track.title else {
+            return Ok(vec![]);
+        };
+        let artist_name = track.artists.map(|artists| artists.join(" "));
+
+        let mut params = vec![("track_name", title)];
+        if let Some(artist_name) = &artist_name {
+            params.push(("artist_name", artist_name.as_str()));
+        }
+        if let Some(album) = track.album {
+            params.push(("album_name", album));
+        }
+
+        let response = self
+            .http_client
+            .get_with_params_and_headers(&self.url("/api/search"), &params, &[CLIENT_HEADER])
+            .await?;
+        let records: Vec<models::LyricsRecord> = parse_response(&response)?;
+
+        let results = records
+            .into_iter()
+            .filter(|record| !record.instrumental)
+            .map(|record| SearchResult {
+                title: record.track_name,
+                artists: vec![generic::Artist {
+                    id: String::new(),
+                    name: record.artist_name,
+                }],
+                album: record.album_name,
+                duration: record.duration.map(|secs| (secs * 1000.0).round() as u64),
+                provider_id: record.id.to_string(),
+                provider_name: self.name().to_string(),
+                ..Default::default()
+            })
+            .collect();
+        Ok(results)
+    }
+
+    async fn get_full_lyrics(&self, song_id: &str) -> Result<FullLyricsResult> {
+        let response = self
+            .http_client
+            .request_with_headers(
+                HttpMethod::Get,
+                &self.url(&format!("/api/get/{song_id}")),
+                &[CLIENT_HEADER],
+                None,
+            )
+            .await?;
+        let record: models::LyricsRecord = parse_response(&response)?;
+        self.build_full_lyrics_result(record)
+    }
+
+    async fn get_lyrics(&self, song_id: &str) -> Result<ParsedSourceData> {
+        Ok(self.get_full_lyrics(song_id).await?.parsed)
+    }
+
+    async fn get_album_info(&self, _: &str) -> Result<generic::Album> {
+        Err(LyricsHelperError::ProviderNotSupported(
+            "lrclib 不支持 get_album_info".into(),
+        ))
+    }
+
+    async fn get_album_songs(
+        &self,
+        _album_id: &str,
+        _page: u32,
+        _page_size: u32,
+    ) -> Result<Vec<generic::Song>> {
+        Err(LyricsHelperError::ProviderNotSupported(
+            "lrclib 不支持 get_album_songs".to_string(),
+        ))
+    }
+
+    async fn get_singer_songs(
+        &self,
+        _singer_id: &str,
+        _page: u32,
+        _page_size: u32,
+    ) -> Result<Vec<generic::Song>> {
+        Err(LyricsHelperError::ProviderNotSupported(
+            "lrclib 不支持 get_singer_songs".to_string(),
+        ))
+    }
+
+    async fn get_playlist(&self, _playlist_id: &str) -> Result<generic::Playlist> {
+        Err(LyricsHelperError::ProviderNotSupported(
+            "lrclib 不支持 get_playlist".to_string(),
+        ))
+    }
+
+    async fn get_song_info(&self, _song_id: &str) -> Result<generic::Song> {
+        Err(LyricsHelperError::ProviderNotSupported(
+            "lrclib 不支持 get_song_info".to_string(),
+        ))
+    }
+
+    async fn get_album_cover_url(&self, _album_id: &str, _size: CoverSize) -> Result<String> {
+        Err(LyricsHelperError::ProviderNotSupported(
+            "lrclib 不支持 get_album_cover_url".into(),
+        ))
+    }
+}
+
+/// 检查状态码并解析 JSON 响应。404 视为没有找到歌词。
+fn parse_response<T: serde::de::DeserializeOwned>(response: &HttpResponse) -> Result<T> {
+    match response.status {
+        404 => Err(LyricsHelperError::LyricNotFound),
+        400.. => Err(api_error(response)),
+        _ => response.json(),
+    }
+}
+
+fn api_error(response: &HttpResponse) -> LyricsHelperError {
+    let detail = response
+        .json::<models::ErrorResponse>()
+        .ok()
+        .and_then(|e| e.message.or(e.name))
+        .unwrap_or_default();
+    LyricsHelperError::ApiError(format!(
+        "LRCLIB 返回错误 (状态码 {}): {detail}",
+        response.status
+    ))
+}
+
+/// 求解发布挑战时最多尝试的 nonce 数量，约为 LRCLIB 当前难度期望次数的 16 倍。
+const MAX_CHALLENGE_ATTEMPTS: u64 = 1 << 28;
+
+/// 寻找一个 nonce，使 `SHA256(prefix + nonce)` 按字节比较不大于目标值。
+///
+/// 目标值必须是 32 字节的十六进制串，超过 [`MAX_CHALLENGE_ATTEMPTS`] 次仍未找到时返回错误。
+fn solve_challenge(prefix: &str, target: &str) -> Result<String> {
+    let target = hex::decode(target)
+        .map_err(|e| LyricsHelperError::ApiError(format!("无效的发布挑战目标值: {e}")))?;
+    if target.len() != 32 {
+        return Err(LyricsHelperError::ApiError(format!(
+            "发布挑战目标值应为 32 字节，实际为 {} 字节",
+            target.len()
+        )));
+    }
+
+    (0..MAX_CHALLENGE_ATTEMPTS)
+        .find(|nonce| Sha256::digest(format!("{prefix}{nonce}"))[..] <= target[..])
+        .map(|nonce| nonce.to_string())
+        .ok_or_else(|| {
+            LyricsHelperError::ApiError(format!(
+                "尝试 {MAX_CHALLENGE_ATTEMPTS} 次后仍未解出发布挑战"
+            ))
+        })
+}
+
+/// 把纯文本歌词转换为不带时间信息的歌词行。
+fn parse_plain_lyrics(content: &str) -> ParsedSourceData {
+    let lines = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|text| {
+            let mut line = LyricLine::new(0, 0);
+            line.add_content_track(ContentType::Main, text);
+            line
+        })
+        .collect();
+
+    ParsedSourceData {
+        lines,
+        source_format: LyricFormat::Lrc,
+        is_line_timed_source: true,
+        ..Default::default()
+    }
+}
+
+/// 去掉 LRC 中的时间标签和元数据标签，得到纯文本歌词。
+fn plain_text_from_lrc(lrc: &str) -> String {
+    lrc.lines()
+        .filter_map(|line| {
+            let mut rest = line.trim();
+            let mut had_tag = false;
+            while rest.starts_with('[')
+                && let Some(end) = rest.find(']')
+            {
+                rest = rest[end + 1..].trim_start();
+                had_tag = true;
+            }
+            // 只有标签的行是元数据，丢弃；没有标签的空行保留为段落分隔
+            (!had_tag || !rest.is_empty()).then(|| rest.to_string())
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+
+    /// 收到的请求：URL、请求头和请求体。
+    type RecordedRequest = (String, Vec<(String, String)>, Option<Vec<u8>>);
+
+    /// 按路径返回预设响应，并记录收到的请求。
+    #[derive(Debug, Default)]
+    struct MockHttpClient {
+        routes: Vec<(&'static str, u16, &'static str)>,
+        requests: Mutex<Vec<RecordedRequest>>,
+    }
+
+    impl MockHttpClient {
+        fn respond(
+            &self,
+            url: &str,
+            headers: &[(&str, &str)],
+            body: Option<&[u8]>,
+        ) -> HttpResponse {
+            self.requests.lock().push((
+                url.to_string(),
+                headers
+                    .iter()
+                    .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                    .collect(),
+                body.map(<[u8]>::to_vec),
+            ));
+            let path = url
+                .split_once("://")
+                .map_or(url, |(_, rest)| rest.find('/').map_or("", |i| &rest[i..]));
+            let path = path.split('?').next().unwrap_or_default();
+            let (status, body) = self
+                .routes
+                .iter()
+                .find(|(route, _, _)| *route == path)
+                .map_or((404, "{}"), |(_, status, body)| (*status, *body));
+            HttpResponse {
+                status,
+                headers: vec![],
+                body: body.as_bytes().to_vec(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl HttpClient for MockHttpClient {
+        async fn get(&self, url: &str) -> Result<HttpResponse> {
+            Ok(self.respond(url, &[], None))
+        }
+        async fn post_json(&self, url: &str, json: &serde_json::Value) -> Result<HttpResponse> {
+            Ok(self.respond(url, &[], Some(json.to_string().as_bytes())))
+        }
+        async fn post_form(&self, url: &str, _form: &[(&str, &str)]) -> Result<HttpResponse> {
+            Ok(self.respond(url, &[], None))
+        }
+        async fn request_with_headers(
+            &self,
+            _method: HttpMethod,
+            url: &str,
+            headers: &[(&str, &str)],
+            body: Option<&[u8]>,
+        ) -> Result<HttpResponse> {
+            Ok(self.respond(url, headers, body))
+        }
+        fn get_cookies(&self) -> Result<String> {
+            Ok(String::new())
+        }
+        fn set_cookies(&self, _cookies_json: &str) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    const RECORD: &str = r#"{"id":42,"trackName":"Song A","artistName":"Artist A","albumName":"Album A","duration":215.5,"instrumental":false,"plainLyrics":"First line\nSecond line","syncedLyrics":"[00:01.00]First line\n[00:03.00]Second line"}"#;
+
+    const SEARCH_BODY: &str = const_format::concatcp!("[", RECORD, "]");
+
+    fn provider(
+        routes: Vec<(&'static str, u16, &'static str)>,
+    ) -> (LrclibProvider, Arc<MockHttpClient>) {
+        let client = Arc::new(MockHttpClient {
+            routes,
+            ..Default::default()
+        });
+        (
+            LrclibProvider::with_base_url(client.clone(), "http://127.0.0.1:3300/"),
+            client,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_search_and_signature_lookup() {
+        let (provider, client) = provider(vec![
+            ("/api/search", 200, SEARCH_BODY),
+            ("/api/get", 200, RECORD),
+        ]);
+
+        let track = Track {
+            title: Some("Song A"),
+            artists: Some(&["Artist A"]),
+            album: Some("Album A"),
+            duration: Some(215_500),
+            version_hints: None,
+        };
+        let results = provider.search_songs(&track).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].provider_id, "42");
+        assert_eq!(results[0].duration, Some(215_500));
+
+        let lyrics = provider.get_by_signature(&track).await.unwrap();
+        assert_eq!(lyrics.raw.format, "lrc");
+        assert_eq!(lyrics.parsed.lines.len(), 2);
+        assert_eq!(lyrics.parsed.lines[1].start_ms, 3000);
+
+        let requests = client.requests.lock().clone();
+        assert!(requests[1].0.starts_with("http://127.0.0.1:3300/api/get?"));
+        assert!(requests[1].0.contains("duration=215"));
+        assert!(requests[1].1.iter().any(|(k, v)| k == "Lrclib-Client"
+            && v.ends_with("(https://github.com/apoint123/lyrics-helper-rs)")));
+    }
+
+    #[tokio::test]
+    async fn test_lyrics_selection_and_not_found() {
+        let (provider, _) = provider(vec![("/api/get/42", 200, RECORD)]);
+        let provider = provider.with_lyrics_selection(LyricsSelection::PlainOnly);
+        let lyrics = provider.get_full_lyrics("42").await.unwrap();
+        assert_eq!(lyrics.raw.format, "txt");
+        assert_eq!(
+            lyrics.parsed.lines[1].main_text().as_deref(),
+            Some("Second line")
+        );
+        assert!(matches!(
+            provider.get_full_lyrics("7").await,
+            Err(LyricsHelperError::LyricNotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_offline_replay_fixture() {
+        let fixture: crate::http::replay::HttpFixture = serde_json::from_str(include_str!(
+            "../../../tests/test_data/fixtures/lrclib.json"
+        ))
+        .unwrap();
+        let client = Arc::new(crate::http::ReplayHttpClient::new(fixture));
+        let provider = LrclibProvider::with_http_client(client).await.unwrap();
+
+        let track = Track {
+            title: Some("Song A"),
+            artists: Some(&["Artist A"]),
+            album: None,
+            duration: None,
+            version_hints: None,
+        };
+        let results = provider.search_songs(&track).await.unwrap();
+        assert_eq!(results[0].provider_id, "42");
+
+        let lyrics = provider.get_full_lyrics("42").await.unwrap();
+        assert_eq!(lyrics.parsed.lines.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_publish_solves_challenge() {
+        let (provider, client) = provider(vec![
+            (
+                "/api/request-challenge",
+                200,
+                r#"{"prefix":"abc","target":"00ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"}"#,
+            ),
+            ("/api/publish", 201, ""),
+        ]);
+
+        let request = LrclibPublishRequest::from_lrc(
+            "Song A",
+            "Artist A",
+            "Album A",
+            215_500,
+            "[ti:Song A]\n[00:01.00]First line\n[00:03.00]Second line",
+        );
+        assert_eq!(request.plain_lyrics, "First line\nSecond line");
+        provider.publish(&request).await.unwrap();
+
+        let requests = client.requests.lock().clone();
+        let (url, headers, body) = &requests[1];
+        assert!(url.ends_with("/api/publish"));
+        let token = &headers
+            .iter()
+            .find(|(k, _)| k == "X-Publish-Token")
+            .unwrap()
+            .1;
+        let (prefix, nonce) = token.split_once(':').unwrap();
+        assert_eq!(prefix, "abc");
+        assert_eq!(Sha256::digest(format!("abc{nonce}"))[0], 0);
+        let body: serde_json::Value = serde_json::from_slice(body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["trackName"], "Song A");
+        assert_eq!(body["duration"], 215.5);
+    }
+
+    #[test]
+    fn test_solve_challenge_rejects_invalid_target() {
+        assert!(solve_challenge("abc", "").is_err());
+        assert!(solve_challenge("abc", "00ff").is_err());
+        assert!(solve_challenge("abc", "zz").is_err());
+        assert!(solve_challenge("abc", &"ff".repeat(32)).is_ok());
+    }
+}
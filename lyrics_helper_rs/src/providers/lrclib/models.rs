@@ -0,0 +1,51 @@
+//! 此模块定义了用于反序列化 LRCLIB API 响应的数据结构。
+//! API 文档: <https://lrclib.net/docs>
+
+use serde::Deserialize;
+
+/// `/api/get`、`/api/get/{id}` 返回的歌词记录，`/api/search` 返回它的数组。
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LyricsRecord {
+    /// 记录 ID。
+    pub id: i64,
+    /// 歌曲标题。
+    pub track_name: String,
+    /// 艺术家名，多位艺术家会合并在同一个字符串中。
+    pub artist_name: String,
+    /// 专辑名。
+    #[serde(default)]
+    pub album_name: Option<String>,
+    /// 歌曲时长（秒）。
+    #[serde(default)]
+    pub duration: Option<f64>,
+    /// 是否为纯音乐。
+    #[serde(default)]
+    pub instrumental: bool,
+    /// 不带时间轴的纯文本歌词。
+    #[serde(default)]
+    pub plain_lyrics: Option<String>,
+    /// 逐行的 LRC 歌词。
+    #[serde(default)]
+    pub synced_lyrics: Option<String>,
+}
+
+/// `/api/request-challenge` 的响应。
+#[derive(Debug, Deserialize)]
+pub struct PublishChallenge {
+    /// 计算时需要拼接在 nonce 前面的前缀。
+    pub prefix: String,
+    /// 十六进制表示的目标值，哈希值不大于它时即为有效答案。
+    pub target: String,
+}
+
+/// API 返回的错误信息。
+#[derive(Debug, Deserialize)]
+pub struct ErrorResponse {
+    /// 错误名称，例如 `TrackNotFound`。
+    #[serde(default)]
+    pub name: Option<String>,
+    /// 错误描述。
+    #[serde(default)]
+    pub message: Option<String>,
+}
@@ -0,0 +1,176 @@
+//! 测试用的可配置提供商。
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use lyrics_helper_core::{
+    Artist, CoverSize, FullLyricsResult, ParsedSourceData, SearchResult, Track, model::generic,
+};
+
+use crate::{
+    error::{LyricsHelperError, Result},
+    http::HttpClient,
+    providers::Provider,
+};
+
+type SearchFn = Box<dyn Fn(&Track<'_>) -> Vec<SearchResult> + Send + Sync>;
+type LyricsFn = Box<dyn Fn(&str) -> Result<FullLyricsResult> + Send + Sync>;
+
+/// 行为可以按测试需要配置的提供商。
+///
+/// 默认搜索不到任何结果，获取歌词时返回 `LyricNotFound`，专辑中没有歌曲，其余方法没有实现。
+pub struct MockProvider {
+    name: &'static str,
+    search: SearchFn,
+    lyrics: LyricsFn,
+    album_songs: Vec<generic::Song>,
+    delay: Duration,
+    fail: bool,
+    calls: AtomicUsize,
+}
+
+impl MockProvider {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            search: Box::new(|_| Vec::new()),
+            lyrics: Box::new(|_| Err(LyricsHelperError::LyricNotFound)),
+            album_songs: Vec::new(),
+            delay: Duration::ZERO,
+            fail: false,
+            calls: AtomicUsize::new(0),
+        }
+    }
+
+    /// 搜索和获取歌词的调用次数。
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    /// 每次搜索都返回这些结果。
+    pub fn with_results(self, results: Vec<SearchResult>) -> Self {
+        self.with_search(move |_| results.clone())
+    }
+
+    /// 根据搜索条件生成结果。
+    pub fn with_search(
+        mut self,
+        search: impl Fn(&Track<'_>) -> Vec<SearchResult> + Send + Sync + 'static,
+    ) -> Self {
+        self.search = Box::new(search);
+        self
+    }
+
+    /// 根据歌曲 ID 生成歌词。
+    pub fn with_lyrics(
+        mut self,
+        lyrics: impl Fn(&str) -> Result<FullLyricsResult> + Send + Sync + 'static,
+    ) -> Self {
+        self.lyrics = Box::new(lyrics);
+        self
+    }
+
+    /// 任意专辑都分页返回这些歌曲。
+    pub fn with_album_songs(mut self, songs: Vec<generic::Song>) -> Self {
+        self.album_songs = songs;
+        self
+    }
+
+    /// 搜索前先等待一段时间。
+    pub const fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// 搜索时返回网络错误。
+    pub const fn failing(mut self) -> Self {
+        self.fail = true;
+        self
+    }
+}
+
+/// 创建一个只有标题、艺术家和 ID 的搜索结果。
+pub fn search_result(provider: &str, id: &str, title: &str, artist: &str) -> SearchResult {
+    SearchResult {
+        title: title.to_string(),
+        artists: vec![Artist {
+            id: String::new(),
+            name: artist.to_string(),
+        }],
+        provider_name: provider.to_string(),
+        provider_id: id.to_string(),
+        ..Default::default()
+    }
+}
+
+#[async_trait]
+impl Provider for MockProvider {
+    async fn with_http_client(_http_client: Arc<dyn HttpClient>) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        Ok(Self::new("mock"))
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    async fn search_songs(&self, track: &Track<'_>) -> Result<Vec<SearchResult>> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(self.delay).await;
+        if self.fail {
+            return Err(LyricsHelperError::Http("connection refused".into()));
+        }
+        Ok((self.search)(track))
+    }
+
+    async fn get_full_lyrics(&self, song_id: &str) -> Result<FullLyricsResult> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        (self.lyrics)(song_id)
+    }
+    async fn get_lyrics(&self, _song_id: &str) -> Result<ParsedSourceData> {
+        unimplemented!()
+    }
+    async fn get_album_info(&self, _album_id: &str) -> Result<generic::Album> {
+        unimplemented!()
+    }
+    async fn get_album_songs(
+        &self,
+        _album_id: &str,
+        page: u32,
+        page_size: u32,
+    ) -> Result<Vec<generic::Song>> {
+        let start = (page as usize - 1) * page_size as usize;
+        Ok(self
+            .album_songs
+            .iter()
+            .skip(start)
+            .take(page_size as usize)
+            .cloned()
+            .collect())
+    }
+    async fn get_singer_songs(
+        &self,
+        _singer_id: &str,
+        _page: u32,
+        _page_size: u32,
+    ) -> Result<Vec<generic::Song>> {
+        unimplemented!()
+    }
+    async fn get_playlist(&self, _playlist_id: &str) -> Result<generic::Playlist> {
+        unimplemented!()
+    }
+    async fn get_song_info(&self, _song_id: &str) -> Result<generic::Song> {
+        unimplemented!()
+    }
+    async fn get_album_cover_url(&self, _album_id: &str, _size: CoverSize) -> Result<String> {
+        unimplemented!()
+    }
+}
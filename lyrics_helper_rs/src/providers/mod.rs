@@ -4,6 +4,10 @@
 
 pub mod amll_ttml_database;
 pub mod kugou;
+pub mod local_library;
+pub mod lrclib;
+#[cfg(test)]
+pub(crate) mod mock;
 pub mod netease;
 pub mod qq;
 
@@ -14,9 +18,73 @@ use crate::http::{HttpClient, WreqClient};
 
 use async_trait::async_trait;
 use lyrics_helper_core::{
-    CoverSize, FullLyricsResult, ParsedSourceData, SearchResult, Track, model::generic,
+    CanonicalMetadataKey, CoverSize, FullLyricsResult, ParsedSourceData, SearchResult,
+    SongReference, Track, model::generic,
 };
 
+/// 把歌曲或专辑在提供商平台上的 ID 记录到原始元数据中，空值和已有的值会被跳过。
+pub(crate) fn record_metadata(
+    parsed: &mut ParsedSourceData,
+    key: &CanonicalMetadataKey,
+    value: &str,
+) {
+    let value = value.trim();
+    if value.is_empty() {
+        return;
+    }
+    let values = parsed.raw_metadata.entry(key.to_string()).or_default();
+    if !values.iter().any(|v| v == value) {
+        values.push(value.to_string());
+    }
+}
+
+/// 把搜索结果或歌曲列表中已有的歌曲和专辑 ID 记录到歌词的原始元数据中，
+/// 歌词接口大多不返回专辑信息，这样无需为此再请求一次歌曲详情。
+pub(crate) fn record_source_ids(
+    parsed: &mut ParsedSourceData,
+    provider_name: &str,
+    song_id: &str,
+    album_id: Option<&str>,
+) {
+    let (song_key, album_key) = match provider_name {
+        "qq" => (
+            CanonicalMetadataKey::QqMusicId,
+            Some(CanonicalMetadataKey::QqAlbumId),
+        ),
+        "netease" => (
+            CanonicalMetadataKey::NcmMusicId,
+            Some(CanonicalMetadataKey::NcmAlbumId),
+        ),
+        "kugou" => (CanonicalMetadataKey::KugouHash, None),
+        _ => return,
+    };
+    // QQ 音乐的数字 ID 不是 AMLL 等处使用的歌曲 MID
+    if song_key != CanonicalMetadataKey::QqMusicId || song_id.parse::<u64>().is_err() {
+        record_metadata(parsed, &song_key, song_id);
+    }
+    if let (Some(key), Some(album_id)) = (album_key, album_id) {
+        record_metadata(parsed, &key, album_id);
+    }
+}
+
+/// 取出平台歌词头部 `[by:]` 标签中的值。
+///
+/// 平台歌词的 `[by:]` 记录的是上传歌词的用户，而不是逐词歌词作者，
+/// 留在原始元数据中会被规范化为 `TtmlAuthorGithubLogin`。
+pub(crate) fn take_uploader_tags(parsed: &mut ParsedSourceData) -> Vec<String> {
+    let keys: Vec<String> = parsed
+        .raw_metadata
+        .keys()
+        .filter(|key| key.eq_ignore_ascii_case("by"))
+        .cloned()
+        .collect();
+    keys.iter()
+        .filter_map(|key| parsed.raw_metadata.remove(key))
+        .flatten()
+        .filter(|value| !value.trim().is_empty())
+        .collect()
+}
+
 /// 定义了所有音乐平台提供商需要实现的通用接口。
 #[async_trait]
 pub trait Provider: Send + Sync {
@@ -51,6 +119,20 @@ pub trait Provider: Send + Sync {
     ///
     async fn search_songs(&self, track: &Track<'_>) -> Result<Vec<SearchResult>>;
 
+    /// 根据其它平台的 ID（如 Apple Music ID、ISRC）查找该提供商中对应的歌曲。
+    ///
+    /// 只有维护了跨平台 ID 映射的提供商（例如 AMLL TTML Database）需要实现，默认返回空列表。
+    ///
+    /// # 参数
+    /// * `reference` - 歌曲引用。
+    ///
+    /// # 返回
+    /// 一个 `Result`，成功时包含该提供商中对应的歌曲列表。
+    async fn find_by_reference(&self, reference: &SongReference) -> Result<Vec<SearchResult>> {
+        let _ = reference;
+        Ok(vec![])
+    }
+
     ///
     /// 根据歌曲 ID 获取已解析的的歌词，包括 `ParsedSourceData` 数据和原始副本。
     ///
@@ -159,3 +241,51 @@ pub trait Provider: Send + Sync {
     /// 如果提供商不支持此功能或找不到封面，返回错误。
     async fn get_album_cover_url(&self, album_id: &str, size: CoverSize) -> Result<String>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_source_ids() {
+        let mut parsed = ParsedSourceData::default();
+        record_source_ids(&mut parsed, "qq", "002DuMJE0E9YSa", Some("000MkMni19ClKG"));
+        // 数字 ID 不是歌曲 MID，不应记录
+        record_source_ids(&mut parsed, "qq", "102065756", None);
+        assert_eq!(
+            parsed
+                .raw_metadata
+                .get(&CanonicalMetadataKey::QqMusicId.to_string()),
+            Some(&vec!["002DuMJE0E9YSa".to_string()])
+        );
+        assert_eq!(
+            parsed
+                .raw_metadata
+                .get(&CanonicalMetadataKey::QqAlbumId.to_string()),
+            Some(&vec!["000MkMni19ClKG".to_string()])
+        );
+
+        let mut parsed = ParsedSourceData::default();
+        record_source_ids(&mut parsed, "netease", "1934240993", Some("147979163"));
+        assert_eq!(
+            parsed
+                .raw_metadata
+                .get(&CanonicalMetadataKey::NcmAlbumId.to_string()),
+            Some(&vec!["147979163".to_string()])
+        );
+
+        let mut parsed = ParsedSourceData::default();
+        record_source_ids(&mut parsed, "kugou", "ABCDEF", Some("12345"));
+        assert_eq!(
+            parsed
+                .raw_metadata
+                .get(&CanonicalMetadataKey::KugouHash.to_string()),
+            Some(&vec!["ABCDEF".to_string()])
+        );
+        assert_eq!(parsed.raw_metadata.len(), 1);
+
+        let mut parsed = ParsedSourceData::default();
+        record_source_ids(&mut parsed, "amll-ttml-database", "1", Some("2"));
+        assert!(parsed.raw_metadata.is_empty());
+    }
+}
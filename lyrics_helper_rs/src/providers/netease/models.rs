@@ -39,6 +39,23 @@ pub struct LyricResult {
     pub romalrc: Option<LyricData>,
     /// 逐字 YRC 歌词。
     pub yrc: Option<LyricData>,
+    /// 上传原文歌词的用户。
+    #[serde(rename = "lyricUser")]
+    pub lyric_user: Option<LyricUser>,
+    /// 上传翻译的用户。
+    #[serde(rename = "transUser")]
+    pub trans_user: Option<LyricUser>,
+}
+
+/// 歌词贡献者的信息。
+#[derive(Debug, Deserialize)]
+pub struct LyricUser {
+    /// 用户 ID。
+    #[serde(default)]
+    pub userid: i64,
+    /// 用户昵称。
+    #[serde(default)]
+    pub nickname: String,
 }
 
 /// 单一歌词内容的数据结构。
@@ -13,8 +13,9 @@ use rand::Rng;
 use regex::Regex;
 
 use lyrics_helper_core::{
-    Artist, ConversionInput, ConversionOptions, CoverSize, FullLyricsResult, InputFile, Language,
-    LyricFormat, ParsedSourceData, RawLyrics, SearchResult, Track, model::generic,
+    Artist, CanonicalMetadataKey, ContributorRole, ConversionInput, ConversionOptions, CoverSize,
+    FullLyricsResult, InputFile, Language, LyricContributor, LyricFormat, ParsedSourceData,
+    RawLyrics, SearchResult, Track, model::generic,
 };
 use quick_xml::{Reader, events::Event};
 use serde_json::json;
@@ -190,48 +191,16 @@ impl Provider for QQMusic {
 
     #[instrument(skip(self), fields(song_id = %song_id))]
     async fn get_full_lyrics(&self, song_id: &str) -> Result<FullLyricsResult> {
-        let main_api_result = self.try_get_lyrics_internal(song_id).await;
-
-        match main_api_result {
-            Ok(lyrics) => Ok(lyrics),
-            Err(e) => {
-                if matches!(e, LyricsHelperError::LyricNotFound) {
-                    return Err(e);
-                }
-
-                warn!(
-                    song_id = %song_id,
-                    error = ?e,
-                    "主接口调用失败，尝试备用接口"
-                );
-
-                let numerical_id = match self.get_numerical_song_id(song_id).await {
-                    Ok(id) => id,
-                    Err(id_err) => {
-                        warn!(
-                            song_id = %song_id,
-                            error = ?id_err,
-                            "获取歌曲数字 ID 失败，尝试调用仅 LRC 接口"
-                        );
-                        return self.try_get_lyrics_lrc_only(song_id).await;
-                    }
-                };
-
-                // 调用备用接口
-                match self.try_get_lyrics_fallback(numerical_id).await {
-                    Ok(lyrics) => Ok(lyrics),
-                    Err(fallback_err) => {
-                        warn!(
-                            song_id = %song_id,
-                            numerical_id,
-                            error = ?fallback_err,
-                            "备用接口失败，尝试调用仅 LRC 接口"
-                        );
-                        self.try_get_lyrics_lrc_only(song_id).await
-                    }
-                }
-            }
+        let mut result = self.fetch_lyrics_with_fallback(song_id).await?;
+        // 数字 ID 不是 AMLL 等处使用的歌曲 MID，只记录 MID
+        if song_id.parse::<u64>().is_err() {
+            super::record_metadata(
+                &mut result.parsed,
+                &CanonicalMetadataKey::QqMusicId,
+                song_id,
+            );
         }
+        Ok(result)
     }
 
     /// 根据专辑 MID 获取专辑的详细信息。
@@ -464,6 +433,52 @@ impl Provider for QQMusic {
 }
 
 impl QQMusic {
+    /// 依次尝试主接口、备用接口和仅 LRC 接口获取歌词。
+    async fn fetch_lyrics_with_fallback(&self, song_id: &str) -> Result<FullLyricsResult> {
+        let main_api_result = self.try_get_lyrics_internal(song_id).await;
+
+        match main_api_result {
+            Ok(lyrics) => Ok(lyrics),
+            Err(e) => {
+                if matches!(e, LyricsHelperError::LyricNotFound) {
+                    return Err(e);
+                }
+
+                warn!(
+                    song_id = %song_id,
+                    error = ?e,
+                    "主接口调用失败，尝试备用接口"
+                );
+
+                let numerical_id = match self.get_numerical_song_id(song_id).await {
+                    Ok(id) => id,
+                    Err(id_err) => {
+                        warn!(
+                            song_id = %song_id,
+                            error = ?id_err,
+                            "获取歌曲数字 ID 失败，尝试调用仅 LRC 接口"
+                        );
+                        return self.try_get_lyrics_lrc_only(song_id).await;
+                    }
+                };
+
+                // 调用备用接口
+                match self.try_get_lyrics_fallback(numerical_id).await {
+                    Ok(lyrics) => Ok(lyrics),
+                    Err(fallback_err) => {
+                        warn!(
+                            song_id = %song_id,
+                            numerical_id,
+                            error = ?fallback_err,
+                            "备用接口失败，尝试调用仅 LRC 接口"
+                        );
+                        self.try_get_lyrics_lrc_only(song_id).await
+                    }
+                }
+            }
+        }
+    }
+
     fn build_comm(&self) -> serde_json::Value {
         let comm_map = serde_json::Map::from_iter(vec![
             ("cv".to_string(), json!(13_020_508)),
@@ -865,6 +880,7 @@ impl QQMusic {
                 } else {
                     Some(roma_lyrics_decrypted.to_string())
                 },
+                contributors: Vec::new(),
             };
             return Ok(FullLyricsResult {
                 parsed: parsed_data,
@@ -884,10 +900,13 @@ impl QQMusic {
             main_lyrics_decrypted
         };
 
+        let trans_lyrics_content = Self::extract_from_qrc_wrapper(&trans_lyrics_decrypted);
+        let contributors = Self::extract_contributors(&main_lyrics_content, &trans_lyrics_content);
+
         let mut translations = Vec::new();
-        if !trans_lyrics_decrypted.is_empty() {
+        if !trans_lyrics_content.is_empty() {
             translations.push(InputFile {
-                content: Self::extract_from_qrc_wrapper(&trans_lyrics_decrypted),
+                content: trans_lyrics_content,
                 format: LyricFormat::Lrc,
                 language: Some("zh-Hans".to_string()),
                 filename: None,
@@ -916,6 +935,8 @@ impl QQMusic {
         let mut parsed_data =
             converter::parse_and_merge(&conversion_input, &ConversionOptions::default())?;
         parsed_data.source_name = source_name.to_string();
+        // 主歌词和翻译的 `[by:]` 在合并元数据时会互相覆盖，改为直接从歌词文本中读取
+        super::take_uploader_tags(&mut parsed_data);
 
         let raw_lyrics = RawLyrics {
             format: main_lyric_format.to_string(),
@@ -930,12 +951,45 @@ impl QQMusic {
             } else {
                 Some(roma_lyrics_decrypted.to_string())
             },
+            contributors: Vec::new(),
         };
 
-        Ok(FullLyricsResult {
+        let mut result = FullLyricsResult {
             parsed: parsed_data,
             raw: raw_lyrics,
+        };
+        for contributor in contributors {
+            result.add_contributor(contributor);
+        }
+
+        Ok(result)
+    }
+
+    /// 从主歌词和翻译的 `[by:]` 标签中读取上传者。
+    fn extract_contributors(main_lyrics: &str, trans_lyrics: &str) -> Vec<LyricContributor> {
+        [
+            (ContributorRole::Lyrics, main_lyrics),
+            (ContributorRole::Translation, trans_lyrics),
+        ]
+        .into_iter()
+        .filter_map(|(role, content)| {
+            Self::extract_uploader(content).map(|name| LyricContributor {
+                role,
+                name,
+                user_id: None,
+            })
         })
+        .collect()
+    }
+
+    /// 读取歌词头部 `[by:]` 标签中的上传者。
+    fn extract_uploader(content: &str) -> Option<String> {
+        content
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("[by:")?.strip_suffix(']'))
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
     }
 
     #[instrument(skip(self), fields(song_mid = %song_mid, method = "lrc_only"))]
@@ -1178,6 +1232,7 @@ impl From<models::PlaylistDetailData> for generic::Playlist {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::http::{MatchRules, replay::fixture_client};
 
     const TEST_SONG_NAME: &str = "目及皆是你";
     const TEST_SINGER_NAME: &str = "小蓝背心";
@@ -1192,6 +1247,20 @@ mod tests {
     // 周杰伦的即兴曲，主歌词包含了纯文本介绍内容
     // const SPECIAL_INSTRUMENTAL_SONG_ID: &str = "582359862";
 
+    // 录制夹具时使用的 Qimei，固定下来以免设备注册请求中的随机内容影响回放
+    const TEST_QIMEI: &str = "6c9d3cd110abca9b16311cee10001e717614";
+
+    /// 使用 `qq/{fixture}.json` 夹具的提供商。
+    fn test_provider(fixture: &str) -> QQMusic {
+        let rules = MatchRules::default()
+            .ignore_query_param("pcachetime")
+            .ignore_body_field("searchid");
+        QQMusic {
+            http_client: fixture_client(&format!("qq/{fixture}"), rules),
+            qimei: TEST_QIMEI.to_string(),
+        }
+    }
+
     fn init_tracing() {
         use tracing_subscriber::{EnvFilter, FmtSubscriber};
         let filter = EnvFilter::try_from_default_env()
@@ -1202,16 +1271,42 @@ mod tests {
             .try_init();
     }
 
+    #[test]
+    fn test_build_full_lyrics_result_reads_contributors() {
+        let main = "[ti:歌曲]\n[by:歌词上传者]\n[00:01.00]第一行\n[00:02.00]第二行".to_string();
+        let trans = "[by:翻译上传者]\n[00:01.00]line one\n[00:02.00]line two".to_string();
+
+        let result = QQMusic::build_full_lyrics_result(main, trans, "", "qq").unwrap();
+
+        let metadata = &result.parsed.raw_metadata;
+        assert!(
+            !metadata.contains_key("by"),
+            "平台上传者不应被当作逐词歌词作者"
+        );
+        assert_eq!(
+            metadata.get("LyricsContributor"),
+            Some(&vec!["歌词上传者".to_string()])
+        );
+        assert_eq!(
+            metadata.get("TranslationContributor"),
+            Some(&vec!["翻译上传者".to_string()])
+        );
+        assert_eq!(result.raw.contributors.len(), 2);
+
+        let lrc = lyrics_helper_core::MetadataStore::from(&result.parsed).generate_lrc_header();
+        assert!(lrc.contains("[by:歌词上传者/翻译上传者]"), "{lrc}");
+    }
+
     #[tokio::test]
-    #[ignore]
     async fn test_search_songs() {
         init_tracing();
-        let provider = QQMusic::new().await.unwrap();
+        let provider = test_provider("search_songs");
         let track = Track {
             title: Some(TEST_SONG_NAME),
             artists: Some(&[TEST_SINGER_NAME]),
             album: None,
             duration: None,
+            version_hints: None,
         };
 
         let results = provider.search_songs(&track).await.unwrap();
@@ -1221,10 +1316,9 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore]
     async fn test_get_lyrics() {
         init_tracing();
-        let provider = QQMusic::new().await.unwrap();
+        let provider = test_provider("get_lyrics");
 
         // 一首包含了主歌词、翻译和罗马音的歌曲：002DuMJE0E9YSa，可用于测试
         let lyrics = provider.get_lyrics(TEST_SONG_ID).await.unwrap();
@@ -1251,10 +1345,9 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore]
     async fn test_get_lyrics_for_instrumental_song() {
         init_tracing();
-        let provider = QQMusic::new().await.unwrap();
+        let provider = test_provider("get_lyrics_for_instrumental_song");
 
         let result = provider.get_full_lyrics(INSTRUMENTAL_SONG_ID).await;
 
@@ -1284,10 +1377,9 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore]
     async fn test_get_album_info() {
         init_tracing();
-        let provider = QQMusic::new().await.unwrap();
+        let provider = test_provider("get_album_info");
         let album_info = provider.get_album_info(TEST_ALBUM_MID).await.unwrap();
 
         assert_eq!(album_info.name, TEST_SONG_NAME);
@@ -1299,10 +1391,9 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore]
     async fn test_get_album_songs() {
         init_tracing();
-        let provider = QQMusic::new().await.unwrap();
+        let provider = test_provider("get_album_songs");
         let songs = provider
             .get_album_songs(TEST_ALBUM_MID, 1, 5)
             .await
@@ -1312,10 +1403,9 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore]
     async fn test_get_singer_songs() {
         init_tracing();
-        let provider = QQMusic::new().await.unwrap();
+        let provider = test_provider("get_singer_songs");
         let songs = provider
             .get_singer_songs(TEST_SINGER_MID, 1, 5)
             .await
@@ -1325,10 +1415,9 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore]
     async fn test_get_playlist() {
         init_tracing();
-        let provider = QQMusic::new().await.unwrap();
+        let provider = test_provider("get_playlist");
         let playlist = provider.get_playlist(TEST_PLAYLIST_ID).await.unwrap();
 
         assert!(!playlist.name.is_empty(), "歌单名称不应为空");
@@ -1339,10 +1428,9 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore]
     async fn test_get_song_info() {
         init_tracing();
-        let provider = QQMusic::new().await.unwrap();
+        let provider = test_provider("get_song_info");
         let song = provider.get_song_info(TEST_SONG_MID).await.unwrap();
 
         assert_eq!(song.name, TEST_SONG_NAME);
@@ -1351,10 +1439,9 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore]
     async fn test_get_album_cover_url() {
         init_tracing();
-        let provider = QQMusic::new().await.unwrap();
+        let provider = test_provider("get_album_cover_url");
         let album_mid = TEST_ALBUM_MID;
 
         info!("[QQ音乐测试] 正在获取大尺寸封面...");
@@ -1384,10 +1471,9 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore]
     async fn test_get_lyrics_full() {
         init_tracing();
-        let provider = QQMusic::new().await.unwrap();
+        let provider = test_provider("get_lyrics_full");
         let song_mid = "002DuMJE0E9YSa";
 
         let result = provider.get_full_lyrics(song_mid).await;
@@ -1401,10 +1487,9 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore]
     async fn test_get_lyrics_fallback() {
         init_tracing();
-        let provider = QQMusic::new().await.unwrap();
+        let provider = test_provider("get_lyrics_fallback");
 
         let result = provider
             .try_get_lyrics_fallback(TEST_SONG_NUMERICAL_ID)
@@ -1426,10 +1511,9 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore]
     async fn test_try_get_lyrics_lrc_only() {
         init_tracing();
-        let provider = QQMusic::new().await.unwrap();
+        let provider = test_provider("try_get_lyrics_lrc_only");
 
         let result = provider.try_get_lyrics_lrc_only(TEST_SONG_MID).await;
 
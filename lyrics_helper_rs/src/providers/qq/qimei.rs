@@ -285,15 +285,21 @@ pub async fn get_qimei(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{http::WreqClient, providers::qq::device::Device};
+    use crate::{
+        http::{MatchRules, replay::fixture_client},
+        providers::qq::device::Device,
+    };
 
     #[tokio::test]
-    #[ignore]
-    async fn test_get_qimei_online() {
+    async fn test_get_qimei() {
         let device = Device::new();
         let api_version = "13.2.5.8";
-        let http_client = WreqClient::new().unwrap();
-        let qimei_result = get_qimei(&http_client, &device, api_version).await;
+        // 请求体使用随机密钥加密，只能按 URL 回放
+        let http_client = fixture_client(
+            "qq/get_qimei",
+            MatchRules::default().with_body_matching(false),
+        );
+        let qimei_result = get_qimei(http_client.as_ref(), &device, api_version).await;
 
         assert!(
             qimei_result.is_ok(),
@@ -303,6 +309,10 @@ mod tests {
 
         let result = qimei_result.unwrap();
 
+        assert!(
+            !result.q16.is_empty(),
+            "应使用接口返回的 Qimei，而不是默认值"
+        );
         assert!(!result.q36.is_empty(), "返回的 q36 字段不应为空");
         assert_eq!(result.q36.len(), 36, "q36 应为 36 个字符的十六进制字符串");
 
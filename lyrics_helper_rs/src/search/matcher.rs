@@ -3,7 +3,12 @@
 use crate::converter::processors::chinese_conversion_processor::convert;
 use crate::model::match_type::MatchScorable;
 use crate::model::match_type::{ArtistMatchType, DurationMatchType, NameMatchType};
-use lyrics_helper_core::{ChineseConversionConfig, MatchType, SearchResult, Track};
+use crate::search::normalizer::{detect_version_hints, strip_version_qualifiers};
+use crate::search::transliteration::{is_cross_script, phonetic_key, transliteration_matches};
+use lyrics_helper_core::{
+    AliasTable, ChineseConversionConfig, MatchBreakdown, MatchScoringConfig, MatchType,
+    SearchResult, Track,
+};
 use std::collections::HashSet;
 
 /// 计算两个字符串的归一化 Levenshtein 相似度，并转换为百分比。
@@ -25,58 +30,109 @@ fn normalize_name_for_comparison(name: &str) -> String {
 
 /// 比较用户查询和搜索结果，返回一个综合的匹配等级。
 pub fn compare_track(track: &Track, result: &SearchResult) -> MatchType {
-    const TITLE_WEIGHT: f64 = 1.0;
-    const ARTIST_WEIGHT: f64 = 1.0;
-    const ALBUM_WEIGHT: f64 = 0.4;
-    const DURATION_WEIGHT: f64 = 1.0;
-    const MAX_SINGLE_SCORE: f64 = 7.0;
-
-    const SCORE_THRESHOLDS: &[(f64, MatchType)] = &[
-        (21.0, MatchType::Perfect),
-        (19.0, MatchType::VeryHigh),
-        (17.0, MatchType::High),
-        (15.0, MatchType::PrettyHigh),
-        (11.0, MatchType::Medium),
-        (6.5, MatchType::Low),
-        (2.5, MatchType::VeryLow),
-    ];
+    score_track(track, result, &MatchScoringConfig::default()).0
+}
 
-    let title_match = compare_name(track.title, Some(&result.title));
+/// 按给定的权重和阈值比较用户查询和搜索结果，返回匹配等级和评分明细。
+pub fn score_track(
+    track: &Track,
+    result: &SearchResult,
+    config: &MatchScoringConfig,
+) -> (MatchType, MatchBreakdown) {
+    let weights = config.weights;
+    let max_single_score = config.max_single_score;
+
+    // 查询的版本类型已知时，版本单独比较，标题只比较去掉版本信息后的部分；
+    // 否则保持原有的整体标题比较
+    let query_hints = track.version_hints.map_or_else(
+        || {
+            track
+                .title
+                .map(detect_version_hints)
+                .filter(|hints| !hints.is_empty())
+        },
+        |hints| {
+            let mut hints = hints.to_vec();
+            hints.sort_unstable();
+            hints.dedup();
+            Some(hints)
+        },
+    );
+    let (query_title, result_title, version_mismatch) = query_hints.as_ref().map_or_else(
+        || (track.title.map(String::from), result.title.clone(), false),
+        |hints| {
+            (
+                track.title.map(strip_version_qualifiers),
+                strip_version_qualifiers(&result.title),
+                *hints != detect_version_hints(&result.title),
+            )
+        },
+    );
+    let aliases = &config.aliases;
+    let title_match =
+        compare_name_across_scripts(query_title.as_deref(), Some(&result_title), aliases);
     let result_artist_names: Vec<String> = result.artists.iter().map(|a| a.name.clone()).collect();
-    let artist_match = compare_artists(track.artists, Some(&result_artist_names));
-    let album_match = compare_name(track.album, result.album.as_deref());
+    let artist_match = compare_artists_across_scripts(track.artists, &result_artist_names, aliases);
+    let album_match = compare_name_across_scripts(track.album, result.album.as_deref(), aliases);
     let duration_match = compare_duration(track.duration, result.duration);
 
-    let mut total_score = f64::from(duration_match.get_score()) * DURATION_WEIGHT;
-    total_score = f64::from(album_match.get_score()).mul_add(ALBUM_WEIGHT, total_score);
-    total_score = f64::from(artist_match.get_score()).mul_add(ARTIST_WEIGHT, total_score);
-    total_score = f64::from(title_match.get_score()).mul_add(TITLE_WEIGHT, total_score);
+    let mut total_score = f64::from(duration_match.get_score()) * weights.duration;
+    total_score = f64::from(album_match.get_score()).mul_add(weights.album, total_score);
+    total_score = f64::from(artist_match.get_score()).mul_add(weights.artist, total_score);
+    total_score = f64::from(title_match.get_score()).mul_add(weights.title, total_score);
+    if version_mismatch {
+        total_score = (total_score - config.version_mismatch_penalty).max(0.0);
+    }
 
     // 计算理论最高分
-    let mut possible_score = MAX_SINGLE_SCORE * (TITLE_WEIGHT + ARTIST_WEIGHT);
+    let mut possible_score = max_single_score * (weights.title + weights.artist);
     if album_match.is_some() {
-        possible_score += MAX_SINGLE_SCORE * ALBUM_WEIGHT;
+        possible_score += max_single_score * weights.album;
     }
     if duration_match.is_some() {
-        possible_score += MAX_SINGLE_SCORE * DURATION_WEIGHT;
+        possible_score += max_single_score * weights.duration;
     }
 
     // 如果查询信息不完整，按比例放大总分
     let full_score_base =
-        MAX_SINGLE_SCORE * (TITLE_WEIGHT + ARTIST_WEIGHT + ALBUM_WEIGHT + DURATION_WEIGHT);
+        max_single_score * (weights.title + weights.artist + weights.album + weights.duration);
     let normalized_score = if possible_score > 0.0 && possible_score < full_score_base {
         total_score * (full_score_base / possible_score)
     } else {
         total_score
     };
 
-    for &(threshold, match_type) in SCORE_THRESHOLDS {
-        if normalized_score > threshold {
-            return match_type;
-        }
-    }
+    let crossed = config
+        .thresholds
+        .iter()
+        .filter(|(threshold, _)| normalized_score > *threshold)
+        .max_by(|a, b| a.0.total_cmp(&b.0));
+
+    let breakdown = MatchBreakdown {
+        title: title_match,
+        artist: artist_match,
+        album: album_match,
+        duration: duration_match,
+        duration_delta_ms: duration_delta(track.duration, result.duration),
+        weights,
+        raw_score: total_score,
+        possible_score,
+        normalized_score,
+        version_mismatch,
+        threshold: crossed.map(|&(threshold, _)| threshold),
+    };
+
+    (
+        crossed.map_or(MatchType::None, |&(_, match_type)| match_type),
+        breakdown,
+    )
+}
 
-    MatchType::None
+/// 结果时长与查询时长之差（毫秒），任一方缺失或为 0 时返回 `None`。
+fn duration_delta(query: Option<u64>, result: Option<u64>) -> Option<i64> {
+    let query = i64::try_from(query.filter(|&d| d > 0)?).ok()?;
+    let result = i64::try_from(result.filter(|&d| d > 0)?).ok()?;
+    Some(result - query)
 }
 
 fn check_dash_paren_equivalence(s_dash: &str, s_paren: &str) -> bool {
@@ -271,6 +327,81 @@ where
     Some(ArtistMatchType::NoMatch)
 }
 
+/// 先按 [`compare_name`] 比较，不够接近时再按别名表和跨文字转写比较。
+fn compare_name_across_scripts(
+    name1_opt: Option<&str>,
+    name2_opt: Option<&str>,
+    aliases: &AliasTable,
+) -> Option<NameMatchType> {
+    let direct = compare_name(name1_opt, name2_opt)?;
+    if direct >= NameMatchType::Transliterated {
+        return Some(direct);
+    }
+    let (name1, name2) = (name1_opt?, name2_opt?);
+
+    // 只有两边使用不同文字体系时才比较读音，同一文字体系内的同音词不算匹配
+    let is_equivalent = aliases.are_aliases(name1, name2)
+        || aliases.are_aliases(
+            &convert(name1, ChineseConversionConfig::T2s),
+            &convert(name2, ChineseConversionConfig::T2s),
+        )
+        || transliteration_matches(name1, name2);
+
+    Some(if is_equivalent {
+        NameMatchType::Transliterated
+    } else {
+        direct
+    })
+}
+
+/// 先按 [`compare_artists`] 比较，不够接近时再把艺术家替换为别名组或读音键后比较。
+fn compare_artists_across_scripts(
+    artists1: Option<&[&str]>,
+    artists2: &[String],
+    aliases: &AliasTable,
+) -> Option<ArtistMatchType> {
+    let direct = compare_artists(artists1, Some(artists2))?;
+    if direct >= ArtistMatchType::Transliterated {
+        return Some(direct);
+    }
+    let artists1 = artists1?;
+
+    let alias_group = |name: &str| {
+        aliases
+            .group_of(name)
+            .or_else(|| aliases.group_of(&convert(name, ChineseConversionConfig::T2s)))
+    };
+    let has_alias = artists1.iter().any(|a| alias_group(a).is_some())
+        || artists2.iter().any(|a| alias_group(a).is_some());
+    let cross_script = is_cross_script(&artists1.concat(), &artists2.concat());
+    if !has_alias && !cross_script {
+        return Some(direct);
+    }
+
+    // 同一文字体系内只按别名组比较，避免同音的不同艺术家被当作同一人
+    let key = |name: &str| {
+        alias_group(name).map_or_else(
+            || {
+                if cross_script {
+                    phonetic_key(name)
+                } else {
+                    name.to_lowercase()
+                }
+            },
+            |group| format!("alias#{group}#"),
+        )
+    };
+    let keys1: Vec<String> = artists1.iter().map(|a| key(a)).collect();
+    let keys2: Vec<String> = artists2.iter().map(|a| key(a)).collect();
+    let keyed = compare_artists(Some(&keys1), Some(&keys2))?;
+
+    Some(if keyed >= ArtistMatchType::High {
+        ArtistMatchType::Transliterated
+    } else {
+        direct
+    })
+}
+
 fn compare_duration(duration1: Option<u64>, duration2: Option<u64>) -> Option<DurationMatchType> {
     const DURATION_THRESHOLDS: &[(f64, DurationMatchType)] = &[
         (6.95, DurationMatchType::Perfect), // 差异 < 50ms
@@ -306,7 +437,7 @@ fn compare_duration(duration1: Option<u64>, duration2: Option<u64>) -> Option<Du
 
 #[cfg(test)]
 mod tests {
-    use lyrics_helper_core::Artist;
+    use lyrics_helper_core::{Artist, MatchWeights, VersionHint};
 
     use super::*;
 
@@ -500,6 +631,7 @@ mod tests {
             artists: Some(&["Artist A"]),
             album: Some("Perfect Album"),
             duration: Some(180_000),
+            version_hints: None,
         };
         let perfect_result = SearchResult {
             title: "Perfect Song".to_string(),
@@ -538,6 +670,7 @@ mod tests {
             artists: Some(&["Artist A"]),
             album: None,
             duration: None,
+            version_hints: None,
         };
         assert_eq!(
             compare_track(&incomplete_track, &perfect_result),
@@ -591,4 +724,172 @@ mod tests {
             "Track: No match"
         );
     }
+    #[test]
+    fn test_score_track_breakdown_and_custom_config() {
+        let track = Track {
+            title: Some("Perfect Song"),
+            artists: Some(&["Artist A"]),
+            album: None,
+            duration: Some(180_000),
+            version_hints: None,
+        };
+        let live_result = SearchResult {
+            title: "Perfect Song (Live)".to_string(),
+            artists: vec![Artist {
+                name: "Artist A".to_string(),
+                ..Default::default()
+            }],
+            duration: Some(183_000),
+            ..Default::default()
+        };
+
+        let (match_type, breakdown) =
+            score_track(&track, &live_result, &MatchScoringConfig::default());
+        assert_eq!(breakdown.title, Some(NameMatchType::Low));
+        assert_eq!(breakdown.artist, Some(ArtistMatchType::Perfect));
+        assert_eq!(breakdown.album, None);
+        assert_eq!(breakdown.duration, Some(DurationMatchType::NoMatch));
+        assert_eq!(breakdown.duration_delta_ms, Some(3000));
+        assert!((breakdown.raw_score - 9.0).abs() < f64::EPSILON);
+        assert!((breakdown.possible_score - 21.0).abs() < f64::EPSILON);
+        assert!(breakdown.normalized_score > breakdown.raw_score);
+        assert_eq!(breakdown.threshold, Some(6.5));
+        assert_eq!(match_type, MatchType::Low);
+
+        // 忽略时长并降低阈值后，同一结果应得到更高的等级
+        let config = MatchScoringConfig {
+            weights: MatchWeights {
+                duration: 0.0,
+                ..Default::default()
+            },
+            thresholds: vec![(8.0, MatchType::Medium), (12.0, MatchType::High)],
+            ..Default::default()
+        };
+        let (match_type, breakdown) = score_track(&track, &live_result, &config);
+        assert_eq!(breakdown.weights, config.weights);
+        assert_eq!(breakdown.threshold, Some(8.0));
+        assert_eq!(match_type, MatchType::Medium);
+    }
+
+    #[test]
+    fn test_compare_track_with_version_hints() {
+        let studio_result = SearchResult {
+            title: "Song".to_string(),
+            artists: vec![Artist {
+                name: "Artist A".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let live_result = SearchResult {
+            title: "Song (Live at Budokan)".to_string(),
+            ..studio_result.clone()
+        };
+
+        // 版本未知的查询保持原有的整体标题比较
+        let unknown_query = Track {
+            title: Some("Song"),
+            artists: Some(&["Artist A"]),
+            ..Default::default()
+        };
+        let (_, breakdown) =
+            score_track(&unknown_query, &live_result, &MatchScoringConfig::default());
+        assert!(!breakdown.version_mismatch);
+        assert_eq!(breakdown.title, Some(NameMatchType::Low));
+
+        let studio_query = Track {
+            version_hints: Some(&[]),
+            ..unknown_query.clone()
+        };
+        assert_eq!(
+            compare_track(&studio_query, &studio_result),
+            MatchType::Perfect
+        );
+        assert!(compare_track(&studio_query, &live_result) <= MatchType::Medium);
+
+        let live_query = Track {
+            version_hints: Some(&[VersionHint::Live]),
+            ..studio_query.clone()
+        };
+        assert_eq!(compare_track(&live_query, &live_result), MatchType::Perfect);
+        assert!(compare_track(&live_query, &studio_result) <= MatchType::Medium);
+    }
+
+    #[test]
+    fn test_compare_track_across_scripts() {
+        let mut config = MatchScoringConfig::default();
+        config.aliases.add_group(["米津玄師", "Kenshi Yonezu"]);
+
+        let track = Track {
+            title: Some("Lemon"),
+            artists: Some(&["Kenshi Yonezu"]),
+            ..Default::default()
+        };
+        let result = SearchResult {
+            title: "レモン".to_string(),
+            artists: vec![Artist {
+                name: "米津玄師".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let (match_type, breakdown) = score_track(&track, &result, &config);
+        assert_eq!(breakdown.title, Some(NameMatchType::Transliterated));
+        assert_eq!(breakdown.artist, Some(ArtistMatchType::Transliterated));
+        assert!(match_type > MatchType::Medium);
+
+        // 没有别名时艺术家无法匹配
+        let (_, breakdown) = score_track(&track, &result, &MatchScoringConfig::default());
+        assert_eq!(breakdown.artist, Some(ArtistMatchType::NoMatch));
+
+        assert_eq!(
+            compare_name_across_scripts(
+                Some("Yoru ni Kakeru"),
+                Some("よるにかける"),
+                &AliasTable::default()
+            ),
+            Some(NameMatchType::Transliterated)
+        );
+        assert_eq!(
+            compare_name_across_scripts(
+                Some("Yoru ni Kakeru"),
+                Some("夜に駆ける"),
+                &AliasTable::default()
+            ),
+            Some(NameMatchType::Transliterated)
+        );
+        assert_eq!(
+            compare_name_across_scripts(Some("Lemon"), Some("Melon"), &AliasTable::default()),
+            compare_name(Some("Lemon"), Some("Melon"))
+        );
+    }
+
+    #[test]
+    fn test_compare_name_ignores_same_script_homophones() {
+        // 「他说」与「她说」读音相同，但同为汉字，不应当作转写匹配
+        assert_eq!(
+            compare_name_across_scripts(Some("他说"), Some("她说"), &AliasTable::default()),
+            compare_name(Some("他说"), Some("她说"))
+        );
+        assert_ne!(
+            compare_name_across_scripts(Some("他说"), Some("她说"), &AliasTable::default()),
+            Some(NameMatchType::Transliterated)
+        );
+
+        let track = Track {
+            title: Some("他说"),
+            artists: Some(&["林俊杰"]),
+            ..Default::default()
+        };
+        let result = SearchResult {
+            title: "她说".to_string(),
+            artists: vec![Artist {
+                name: "林俊杰".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let (_, breakdown) = score_track(&track, &result, &MatchScoringConfig::default());
+        assert_ne!(breakdown.title, Some(NameMatchType::Transliterated));
+    }
 }
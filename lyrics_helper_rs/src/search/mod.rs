@@ -3,13 +3,19 @@
 use std::collections::HashMap;
 
 use futures::future;
-use lyrics_helper_core::{MatchType, SearchResult, Track};
+use lyrics_helper_core::{MatchScoringConfig, MatchType, SearchResult, Track};
 use tracing::{debug, info, warn};
 
 use crate::{error::Result, providers::Provider};
+use normalizer::{NormalizedQuery, QueryNormalizer};
 
 pub(crate) mod matcher;
-use matcher::compare_track;
+pub mod normalizer;
+pub mod quality;
+pub mod reference;
+pub(crate) mod terms;
+pub mod transliteration;
+use matcher::score_track;
 
 /// 在多个提供商中并发搜索歌曲。
 ///
@@ -100,20 +106,41 @@ fn finalize_multi_provider_results(results: Vec<SearchResult>) -> Vec<SearchResu
 ///
 /// # 返回
 /// 一个 `Result`，成功时包含一个 `Vec<SearchResult>`，该列表已按匹配度从高到低排序并去重。
+/// 只有当执行过的所有搜索级别都失败时才会返回最后一个错误。
 pub async fn search_track(
     provider: &dyn Provider,
     track: &Track<'_>,
     full_search: bool,
 ) -> Result<Vec<SearchResult>> {
+    search_track_with_scoring(provider, track, full_search, &MatchScoringConfig::default()).await
+}
+
+/// 与 [`search_track`] 相同，但使用指定的权重和阈值为结果评分。
+///
+/// 每个返回的结果都带有 `match_breakdown`，说明其匹配等级的由来。
+/// 查询会先经过 [`QueryNormalizer`] 整理，参见 [`normalize_track`]。
+pub async fn search_track_with_scoring(
+    provider: &dyn Provider,
+    track: &Track<'_>,
+    full_search: bool,
+    scoring: &MatchScoringConfig,
+) -> Result<Vec<SearchResult>> {
+    let normalized = normalize_track(track);
+    let artists = normalized.artist_names();
+    let track = &normalized.to_track(&artists);
+
     info!(
-        "开始对歌曲 '{}' by {:?} 进行搜索 (提供商: {}, 全面搜索: {})",
+        "开始对歌曲 '{}' by {:?} 进行搜索 (提供商: {}, 全面搜索: {}, 版本: {:?})",
         track.title.unwrap_or("未知标题"),
         track.artists.unwrap_or(&["未知艺术家"]),
         provider.name(),
-        full_search
+        full_search,
+        track.version_hints.unwrap_or_default()
     );
 
     let mut all_results: Vec<SearchResult> = Vec::new();
+    let mut any_level_succeeded = false;
+    let mut last_error = None;
 
     if track.title.is_some() && track.artists.is_some() {
         let precise_query = Track {
@@ -121,8 +148,13 @@ pub async fn search_track(
             artists: track.artists,
             album: track.album,
             duration: track.duration,
+            version_hints: track.version_hints,
         };
-        execute_search_level(provider, &precise_query, track, &mut all_results).await;
+        match execute_search_level(provider, scoring, &precise_query, track, &mut all_results).await
+        {
+            Ok(()) => any_level_succeeded = true,
+            Err(e) => last_error = Some(e),
+        }
     }
 
     let has_perfect_match = all_results
@@ -138,36 +170,85 @@ pub async fn search_track(
             artists: None,
             album: track.album,
             duration: track.duration,
+            version_hints: track.version_hints,
         };
-        execute_search_level(provider, &title_only_query, track, &mut all_results).await;
+        match execute_search_level(
+            provider,
+            scoring,
+            &title_only_query,
+            track,
+            &mut all_results,
+        )
+        .await
+        {
+            Ok(()) => any_level_succeeded = true,
+            Err(e) => last_error = Some(e),
+        }
     }
 
+    if !any_level_succeeded && let Some(e) = last_error {
+        return Err(e);
+    }
     Ok(finalize_single_provider_results(all_results))
 }
 
+/// 用 [`QueryNormalizer`] 整理播放器或标签提供的查询。
+///
+/// 只有一个艺术家时，会按分隔符拆分并分离客串艺术家；已经拆好的多个艺术家保持不变。
+/// 调用方已经给出版本类型时沿用，否则使用从标题中识别出的版本类型。
+fn normalize_track(track: &Track<'_>) -> NormalizedQuery {
+    let single_artist = match track.artists {
+        Some([artist]) => Some(*artist),
+        _ => None,
+    };
+    let mut query = QueryNormalizer::new().normalize(
+        track.title.unwrap_or_default(),
+        single_artist,
+        track.album,
+        track.duration,
+    );
+    if let Some(artists) = track.artists
+        && artists.len() > 1
+    {
+        query.artists = artists.iter().map(|a| (*a).to_string()).collect();
+        query
+            .featured_artists
+            .retain(|f| !query.artists.iter().any(|a| a.eq_ignore_ascii_case(f)));
+    }
+    if let Some(hints) = track.version_hints {
+        query.version_hints = hints.to_vec();
+    }
+    query
+}
+
 /// 执行单个级别的搜索，处理结果并将其添加到总结果列表中。
 async fn execute_search_level(
     provider: &dyn Provider,
+    scoring: &MatchScoringConfig,
     search_query: &Track<'_>,
     original_track: &Track<'_>,
     all_results: &mut Vec<SearchResult>,
-) {
+) -> Result<()> {
     match provider.search_songs(search_query).await {
         Ok(mut results) => {
             if !results.is_empty() {
                 debug!("搜索级别命中，找到 {} 个结果。", results.len());
                 // 为这批次的结果计算并设置匹配度
                 for result in &mut results {
-                    result.match_type = compare_track(original_track, result);
+                    let (match_type, breakdown) = score_track(original_track, result, scoring);
+                    result.match_type = match_type;
+                    result.match_breakdown = Some(breakdown);
                 }
                 all_results.extend(results);
             }
+            Ok(())
         }
         Err(e) => {
             warn!(
                 "某个搜索级别执行失败 (查询: {:?})，错误: {}。继续执行下一级别。",
                 search_query, e
             );
+            Err(e)
         }
     }
 }
@@ -199,154 +280,52 @@ fn finalize_single_provider_results(results: Vec<SearchResult>) -> Vec<SearchRes
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
-    use std::sync::Arc;
 
     use super::*;
-    use crate::http::HttpClient;
     use crate::providers::Provider;
+    use crate::providers::mock::{MockProvider, search_result};
     use crate::providers::qq::QQMusic;
-    use async_trait::async_trait;
-    use lyrics_helper_core::model::generic;
-    use lyrics_helper_core::{Artist, FullLyricsResult, ParsedSourceData};
 
-    #[derive(Clone)]
-    struct MockProvider {
-        name: &'static str,
+    /// 返回一个完全匹配、一个较高匹配和一个重复结果的提供商。
+    fn provider_a() -> MockProvider {
+        let perfect = SearchResult {
+            album: Some("Album A".to_string()),
+            duration: Some(240_000), // 4:00
+            ..search_result("provider_a", "pa_perfect", "Song A", "Artist A")
+        };
+        let high = SearchResult {
+            album: Some("Wrong Album".to_string()),
+            duration: Some(300_000), // 5:00
+            ..search_result("provider_a", "pa_high", "Song A", "Artist A")
+        };
+        // 测试 finalize_single_provider_results 的去重
+        MockProvider::new("provider_a").with_results(vec![perfect.clone(), high, perfect])
     }
 
-    #[async_trait]
-    impl Provider for MockProvider {
-        async fn with_http_client(_http_client: Arc<dyn HttpClient>) -> Result<Self>
-        where
-            Self: Sized,
-        {
-            Ok(Self { name: "mock" })
-        }
-
-        fn name(&self) -> &'static str {
-            self.name
-        }
-
-        async fn search_songs(&self, _: &Track<'_>) -> Result<Vec<SearchResult>> {
-            let mut results = Vec::new();
-            if self.name == "provider_a" {
-                // Perfect
-                results.push(SearchResult {
-                    title: "Song A".to_string(),
-                    artists: vec![Artist {
-                        id: String::new(),
-                        name: "Artist A".to_string(),
-                    }],
-                    album: Some("Album A".to_string()),
-                    duration: Some(240_000), // 4:00
-                    provider_name: "provider_a".to_string(),
-                    provider_id: "pa_perfect".to_string(),
-                    ..Default::default()
-                });
-                // High or PrettyHigh
-                results.push(SearchResult {
-                    title: "Song A".to_string(),
-                    artists: vec![Artist {
-                        id: String::new(),
-                        name: "Artist A".to_string(),
-                    }],
-                    album: Some("Wrong Album".to_string()),
-                    duration: Some(300_000), // 5:00
-                    provider_name: "provider_a".to_string(),
-                    provider_id: "pa_high".to_string(),
-                    ..Default::default()
-                });
-                // 测试 finalize_single_provider_results 的去重
-                results.push(SearchResult {
-                    title: "Song A".to_string(),
-                    artists: vec![Artist {
-                        id: String::new(),
-                        name: "Artist A".to_string(),
-                    }],
-                    album: Some("Album A".to_string()),
-                    duration: Some(240_000),
-                    provider_name: "provider_a".to_string(),
-                    provider_id: "pa_perfect".to_string(),
-                    ..Default::default()
-                });
-            }
-            if self.name == "provider_b" {
-                // Low
-                results.push(SearchResult {
-                    title: "Song A".to_string(),
-                    artists: vec![Artist {
-                        id: String::new(),
-                        name: "Unknown Artist".to_string(),
-                    }],
-                    album: Some("Unknown Album".to_string()),
-                    duration: Some(180_000), // 3:00
-                    provider_name: "provider_b".to_string(),
-                    provider_id: "pb_low".to_string(),
-                    ..Default::default()
-                });
-                // None
-                results.push(SearchResult {
-                    title: "Different Song".to_string(),
-                    artists: vec![Artist {
-                        id: String::new(),
-                        name: "Different Artist".to_string(),
-                    }],
-                    album: Some("Different Album".to_string()),
-                    duration: Some(240_000),
-                    provider_name: "provider_b".to_string(),
-                    provider_id: "pb_none".to_string(),
-                    ..Default::default()
-                });
-            }
-            Ok(results)
-        }
-
-        async fn get_full_lyrics(&self, _song_id: &str) -> Result<FullLyricsResult> {
-            unimplemented!()
-        }
-        async fn get_lyrics(&self, _song_id: &str) -> Result<ParsedSourceData> {
-            unimplemented!()
-        }
-        async fn get_album_info(&self, _album_id: &str) -> Result<generic::Album> {
-            unimplemented!()
-        }
-        async fn get_album_songs(
-            &self,
-            _album_id: &str,
-            _page: u32,
-            _page_size: u32,
-        ) -> Result<Vec<generic::Song>> {
-            unimplemented!()
-        }
-        async fn get_singer_songs(
-            &self,
-            _singer_id: &str,
-            _page: u32,
-            _page_size: u32,
-        ) -> Result<Vec<generic::Song>> {
-            unimplemented!()
-        }
-        async fn get_playlist(&self, _playlist_id: &str) -> Result<generic::Playlist> {
-            unimplemented!()
-        }
-        async fn get_song_info(&self, _song_id: &str) -> Result<generic::Song> {
-            unimplemented!()
-        }
-        async fn get_album_cover_url(
-            &self,
-            _album_id: &str,
-            _size: generic::CoverSize,
-        ) -> Result<String> {
-            unimplemented!()
-        }
+    /// 返回一个低匹配和一个不匹配结果的提供商。
+    fn provider_b() -> MockProvider {
+        let low = SearchResult {
+            album: Some("Unknown Album".to_string()),
+            duration: Some(180_000), // 3:00
+            ..search_result("provider_b", "pb_low", "Song A", "Unknown Artist")
+        };
+        let none = SearchResult {
+            album: Some("Different Album".to_string()),
+            duration: Some(240_000),
+            ..search_result(
+                "provider_b",
+                "pb_none",
+                "Different Song",
+                "Different Artist",
+            )
+        };
+        MockProvider::new("provider_b").with_results(vec![low, none])
     }
 
     #[tokio::test]
     async fn test_search_track_in_multiple_providers() {
-        let providers: Vec<Box<dyn Provider>> = vec![
-            Box::new(MockProvider { name: "provider_a" }),
-            Box::new(MockProvider { name: "provider_b" }),
-        ];
+        let providers: Vec<Box<dyn Provider>> =
+            vec![Box::new(provider_a()), Box::new(provider_b())];
 
         // 这个 track 将被用于和 Mock 返回的数据进行比较
         let track = Track {
@@ -354,6 +333,7 @@ mod tests {
             artists: Some(&["Artist A"]),
             album: Some("Album A"),
             duration: None,
+            version_hints: None,
         };
 
         // 我们只对 `search_track_in_providers` 的聚合、排序和去重逻辑感兴趣，
@@ -391,6 +371,39 @@ mod tests {
         assert_eq!(results[3].match_type, MatchType::None, "应为不匹配");
     }
 
+    #[tokio::test]
+    async fn test_search_track_normalizes_now_playing_query() {
+        // 只响应规范化后的查询，同时提供录音室版和现场版
+        let provider = MockProvider::new("versions").with_search(|track| {
+            if track.title != Some("Song") || track.artists != Some(&["Artist A"][..]) {
+                return vec![];
+            }
+            vec![
+                search_result("versions", "studio", "Song", "Artist A"),
+                search_result("versions", "live", "Song (Live at Budokan)", "Artist A"),
+            ]
+        });
+        // 播放器常见的形式：艺术家写在标题里，标题带有版本和修饰信息
+        let track = Track {
+            title: Some("Artist A - Song (Live at Budokan) [Remastered 2011]"),
+            ..Default::default()
+        };
+
+        let results = search_track(&provider, &track, false).await.unwrap();
+
+        assert_eq!(results.len(), 2, "提供商应收到规范化后的标题和艺术家");
+        assert_eq!(results[0].provider_id, "live");
+        let breakdown = |id: &str| {
+            results
+                .iter()
+                .find(|r| r.provider_id == id)
+                .and_then(|r| r.match_breakdown.clone())
+                .unwrap()
+        };
+        assert!(!breakdown("live").version_mismatch);
+        assert!(breakdown("studio").version_mismatch);
+    }
+
     #[tokio::test]
     #[ignore]
     async fn test_search_track_flow() {
@@ -402,6 +415,7 @@ mod tests {
             artists: Some(&["小蓝背心"]),
             album: Some("我怕来者不是你"),
             duration: Some(211_000),
+            version_hints: None,
         };
 
         let results = search_track(provider, &track, true).await.unwrap();
@@ -0,0 +1,406 @@
+//! 查询规范化模块。
+//!
+//! 播放器（SMTC）和音频标签提供的元数据经常是 `Artist - Title`、
+//! `Title (feat. X) [Remastered 2011]`、`Title - Live at Budokan` 这样的形式，
+//! 艺术家也常用 `/`、`;`、`、`、`&`、`x` 连接。直接拿去搜索效果很差。
+//! [`QueryNormalizer`] 把这些字符串整理为干净的标题和艺术家列表，
+//! 分离出客串艺术家，并把现场版、混音版等版本信息保留为 [`VersionHint`]。
+
+use std::sync::LazyLock;
+
+use lyrics_helper_core::{Track, VersionHint};
+use regex::Regex;
+
+/// 匹配一对括号及其内容。
+static BRACKET_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\s*[\(\[（【]([^\(\)\[\]（）【】]*)[\)\]）】]").unwrap());
+
+/// 匹配标题或艺术家末尾不带括号的客串信息，例如 `Title feat. X`。
+static TRAILING_FEAT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\s+(?:feat\.?|ft\.?|featuring)\s+(.+)$").unwrap());
+
+/// 匹配括号内的客串信息，例如 `(feat. X)`、`(with X)`。
+static BRACKET_FEAT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)^\s*(?:feat\.?|ft\.?|featuring|with)\s+(.+)$").unwrap());
+
+/// 匹配 ` - ` 形式的后缀（包括全角破折号）。
+static DASH_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\s+[-–—]\s+").unwrap());
+
+/// 对搜索没有帮助的修饰信息，例如重制版、脏标等。
+static NOISE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\bremaster(?:ed)?\b|\bexplicit\b|\bclean\b|\bdeluxe\b|\bbonus track\b|\b(?:single|album) version\b|\bradio edit\b|\bmono\b|\bstereo\b|\bofficial (?:audio|video|mv)\b|重制版",
+    )
+    .unwrap()
+});
+
+/// 各种版本类型的识别规则。
+static VERSION_RULES: LazyLock<Vec<(Regex, VersionHint)>> = LazyLock::new(|| {
+    [
+        (r"(?i)\blive\b|现场|ライブ", VersionHint::Live),
+        (
+            r"(?i)\bre-?mix(?:ed)?\b|\brmx\b|\b(?:extended|club) mix\b|混音",
+            VersionHint::Remix,
+        ),
+        (
+            r"(?i)\bacoustic\b|\bunplugged\b|不插电",
+            VersionHint::Acoustic,
+        ),
+        (
+            r"(?i)\binstrumental\b|\boff[ -]?vocal\b|\bkaraoke\b|伴奏|纯音乐",
+            VersionHint::Instrumental,
+        ),
+        (
+            r"(?i)\btv\s*(?:size|ver|edit)|tv\s*サイズ",
+            VersionHint::TvSize,
+        ),
+    ]
+    .into_iter()
+    .map(|(pattern, hint)| (Regex::new(pattern).unwrap(), hint))
+    .collect()
+});
+
+/// 规范化后的查询。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NormalizedQuery {
+    /// 去除版本信息、客串信息和修饰信息后的标题。
+    pub title: String,
+    /// 主要艺术家。
+    pub artists: Vec<String>,
+    /// 客串艺术家。
+    pub featured_artists: Vec<String>,
+    /// 专辑名。
+    pub album: Option<String>,
+    /// 歌曲时长（毫秒）。
+    pub duration: Option<u64>,
+    /// 从标题中识别出的版本类型，已排序去重。
+    pub version_hints: Vec<VersionHint>,
+}
+
+impl NormalizedQuery {
+    /// 主要艺术家和客串艺术家的名称，用于构建 [`Track`]。
+    #[must_use]
+    pub fn artist_names(&self) -> Vec<&str> {
+        self.artists
+            .iter()
+            .chain(&self.featured_artists)
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// 以此查询构建用于搜索的 [`Track`]。
+    ///
+    /// `artists` 通常来自 [`Self::artist_names`]，为空时不设置艺术家。
+    ///
+    /// ```ignore
+    /// let query = QueryNormalizer::new().normalize("Artist - Title (Live)", None, None, None);
+    /// let artists = query.artist_names();
+    /// let track = query.to_track(&artists);
+    /// ```
+    #[must_use]
+    pub fn to_track<'a>(&'a self, artists: &'a [&'a str]) -> Track<'a> {
+        Track {
+            title: Some(self.title.as_str()).filter(|t| !t.is_empty()),
+            artists: Some(artists).filter(|a| !a.is_empty()),
+            album: self.album.as_deref(),
+            duration: self.duration,
+            version_hints: Some(&self.version_hints),
+        }
+    }
+}
+
+/// 将杂乱的元数据整理为干净搜索条件的规范化器。
+#[derive(Debug, Clone)]
+pub struct QueryNormalizer {
+    artist_separators: Vec<String>,
+}
+
+impl Default for QueryNormalizer {
+    fn default() -> Self {
+        Self {
+            artist_separators: ["/", "／", ";", "；", "、", "&", "＆", " x ", " X ", " × "]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        }
+    }
+}
+
+impl QueryNormalizer {
+    /// 使用默认的艺术家分隔符创建规范化器。
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 替换用于拆分艺术家的分隔符。
+    ///
+    /// 如果需要保留 `Simon & Garfunkel` 这样的组合名，可以去掉 `&`。
+    #[must_use]
+    pub fn with_artist_separators<I, S>(mut self, separators: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.artist_separators = separators.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// 规范化一组元数据。
+    ///
+    /// 如果没有提供艺术家，会尝试把 `Artist - Title` 形式的标题拆开；
+    /// 如果标题以已知的艺术家开头，也会去掉这一前缀。
+    #[must_use]
+    pub fn normalize(
+        &self,
+        title: &str,
+        artist: Option<&str>,
+        album: Option<&str>,
+        duration: Option<u64>,
+    ) -> NormalizedQuery {
+        let mut title = title.trim().to_string();
+        let mut artist = artist
+            .map(str::trim)
+            .filter(|a| !a.is_empty())
+            .map(String::from);
+
+        if let Some(m) = DASH_REGEX.find(&title) {
+            let (left, right) = (title[..m.start()].trim(), title[m.end()..].trim());
+            if !is_qualifier(right) {
+                match &artist {
+                    None => {
+                        artist = Some(left.to_string());
+                        title = right.to_string();
+                    }
+                    Some(known) if known.eq_ignore_ascii_case(left) => {
+                        title = right.to_string();
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        let parts = parse_title(&title, true);
+
+        let mut featured_artists = Vec::new();
+        for feat in &parts.featured {
+            featured_artists.extend(self.split_artists(feat));
+        }
+
+        let mut artists = Vec::new();
+        if let Some(artist) = &artist {
+            let (main, feat) = split_trailing_feat(artist);
+            artists = self.split_artists(main);
+            if let Some(feat) = feat {
+                featured_artists.extend(self.split_artists(feat));
+            }
+        }
+        featured_artists.retain(|f| !artists.iter().any(|a| a.eq_ignore_ascii_case(f)));
+        featured_artists.dedup();
+
+        NormalizedQuery {
+            title: parts.title,
+            artists,
+            featured_artists,
+            album: album
+                .map(|a| parse_title(a, true).title)
+                .filter(|a| !a.is_empty()),
+            duration,
+            version_hints: parts.hints,
+        }
+    }
+
+    /// 按分隔符拆分艺术家字符串，去掉空白和空项。
+    #[must_use]
+    pub fn split_artists(&self, artists: &str) -> Vec<String> {
+        let mut parts = vec![artists.to_string()];
+        for separator in &self.artist_separators {
+            parts = parts
+                .iter()
+                .flat_map(|part| part.split(separator.as_str()))
+                .map(String::from)
+                .collect();
+        }
+        parts
+            .into_iter()
+            .map(|part| part.trim().to_string())
+            .filter(|part| !part.is_empty())
+            .collect()
+    }
+}
+
+/// 识别标题中的版本类型。
+///
+/// 只检查括号内和 ` - ` 之后的部分，因此 `Live Forever` 这样的标题不会被误认为现场版。
+#[must_use]
+pub fn detect_version_hints(title: &str) -> Vec<VersionHint> {
+    parse_title(title, false).hints
+}
+
+/// 去掉标题中表示版本类型的括号和后缀，保留其它内容。
+#[must_use]
+pub fn strip_version_qualifiers(title: &str) -> String {
+    parse_title(title, false).title
+}
+
+/// 拆分标题的结果。
+struct TitleParts {
+    title: String,
+    featured: Vec<String>,
+    hints: Vec<VersionHint>,
+}
+
+/// 拆分标题中的版本信息。`clean` 为 `true` 时同时去掉客串信息和修饰信息。
+fn parse_title(title: &str, clean: bool) -> TitleParts {
+    let mut featured = Vec::new();
+    let mut hints = Vec::new();
+
+    let mut rest = BRACKET_REGEX
+        .replace_all(title, |caps: &regex::Captures| {
+            let content = &caps[1];
+            let segment_hints = version_hints_of(content);
+            if !segment_hints.is_empty() {
+                hints.extend(segment_hints);
+                return String::new();
+            }
+            if clean {
+                if let Some(feat) = BRACKET_FEAT_REGEX.captures(content) {
+                    featured.push(feat[1].trim().to_string());
+                    return String::new();
+                }
+                if NOISE_REGEX.is_match(content) {
+                    return String::new();
+                }
+            }
+            caps[0].to_string()
+        })
+        .into_owned();
+
+    // 处理 `Title - Live at Budokan`、`Title - 2011 Remaster` 这样的后缀
+    while let Some(m) = DASH_REGEX.find_iter(&rest).last() {
+        let suffix = rest[m.end()..].trim();
+        let segment_hints = version_hints_of(suffix);
+        if !segment_hints.is_empty() {
+            hints.extend(segment_hints);
+        } else if !(clean && NOISE_REGEX.is_match(suffix)) {
+            break;
+        }
+        rest.truncate(m.start());
+    }
+
+    if clean {
+        let (main, feat) = split_trailing_feat(&rest);
+        if let Some(feat) = feat {
+            featured.push(feat.to_string());
+            rest = main.to_string();
+        }
+    }
+
+    hints.sort_unstable();
+    hints.dedup();
+
+    TitleParts {
+        title: rest.split_whitespace().collect::<Vec<_>>().join(" "),
+        featured,
+        hints,
+    }
+}
+
+/// 拆出末尾的 `feat. X`，返回主体部分和客串部分。
+fn split_trailing_feat(text: &str) -> (&str, Option<&str>) {
+    TRAILING_FEAT_REGEX
+        .captures(text)
+        .map_or((text, None), |caps| {
+            let whole = caps.get(0).map_or(text.len(), |m| m.start());
+            (text[..whole].trim(), caps.get(1).map(|m| m.as_str().trim()))
+        })
+}
+
+fn version_hints_of(segment: &str) -> Vec<VersionHint> {
+    VERSION_RULES
+        .iter()
+        .filter(|(regex, _)| regex.is_match(segment))
+        .map(|&(_, hint)| hint)
+        .collect()
+}
+
+/// 判断 ` - ` 之后的部分是否只是版本或修饰信息，而不是真正的标题。
+///
+/// 只检查括号以外的部分，`Artist - Song (Live)` 中的 `Song (Live)` 是标题而不是版本信息。
+fn is_qualifier(segment: &str) -> bool {
+    let outside = BRACKET_REGEX.replace_all(segment, "");
+    let outside = outside.trim();
+    outside.is_empty() || !version_hints_of(outside).is_empty() || NOISE_REGEX.is_match(outside)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_messy_metadata() {
+        let normalizer = QueryNormalizer::new();
+
+        let query = normalizer.normalize("Artist A - Song", None, None, None);
+        assert_eq!(query.title, "Song");
+        assert_eq!(query.artists, vec!["Artist A"]);
+        assert!(query.version_hints.is_empty());
+
+        let query = normalizer.normalize(
+            "Song (feat. Guest B) [Remastered 2011]",
+            Some("Artist A / Artist C"),
+            Some("Album (Deluxe Edition)"),
+            Some(180_000),
+        );
+        assert_eq!(query.title, "Song");
+        assert_eq!(query.artists, vec!["Artist A", "Artist C"]);
+        assert_eq!(query.featured_artists, vec!["Guest B"]);
+        assert_eq!(query.album.as_deref(), Some("Album"));
+        assert_eq!(
+            query.artist_names(),
+            vec!["Artist A", "Artist C", "Guest B"]
+        );
+
+        let query = normalizer.normalize("Artist A - Song (Live at Budokan)", None, None, None);
+        assert_eq!(query.title, "Song");
+        assert_eq!(query.artists, vec!["Artist A"]);
+        assert_eq!(query.version_hints, vec![VersionHint::Live]);
+
+        let query = normalizer.normalize("Song - Live at Budokan", Some("Artist A"), None, None);
+        assert_eq!(query.title, "Song");
+        assert_eq!(query.artists, vec!["Artist A"]);
+        assert_eq!(query.version_hints, vec![VersionHint::Live]);
+
+        let query = normalizer.normalize(
+            "歌曲 (TV Size)",
+            Some("歌手甲、歌手乙 x 歌手丙"),
+            None,
+            None,
+        );
+        assert_eq!(query.title, "歌曲");
+        assert_eq!(query.artists, vec!["歌手甲", "歌手乙", "歌手丙"]);
+        assert_eq!(query.version_hints, vec![VersionHint::TvSize]);
+
+        let query = normalizer.normalize("Song (Acoustic Remix)", Some("A feat. B; C"), None, None);
+        assert_eq!(query.artists, vec!["A"]);
+        assert_eq!(query.featured_artists, vec!["B", "C"]);
+        assert_eq!(
+            query.version_hints,
+            vec![VersionHint::Remix, VersionHint::Acoustic]
+        );
+    }
+
+    #[test]
+    fn test_detect_version_hints() {
+        assert!(detect_version_hints("Live Forever").is_empty());
+        assert_eq!(detect_version_hints("Song (Live)"), vec![VersionHint::Live]);
+        assert_eq!(
+            detect_version_hints("Song - Instrumental"),
+            vec![VersionHint::Instrumental]
+        );
+        assert_eq!(
+            strip_version_qualifiers("Song (Live) (feat. X)"),
+            "Song (feat. X)"
+        );
+    }
+}
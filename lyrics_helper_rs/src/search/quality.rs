@@ -0,0 +1,227 @@
+//! 歌词质量评估模块。
+//!
+//! 搜索结果的匹配度只说明元数据是否吻合，不能说明歌词本身好不好。
+//! 这里根据解析后的歌词计算 [`LyricsQuality`]，用于在多个提供商的歌词之间挑选最完整的一份。
+
+use std::collections::HashSet;
+
+use lyrics_helper_core::{
+    ContentType, LyricLine, LyricTrack, LyricsQuality, MetadataStripperOptions, ParsedSourceData,
+};
+
+use crate::converter::processors::metadata_stripper::strip_descriptive_metadata_lines;
+
+// 各项在综合得分中所占的分值，总和为 100。
+const WORD_TIMING_POINTS: f64 = 35.0;
+const TRANSLATION_POINTS: f64 = 15.0;
+const ROMANIZATION_POINTS: f64 = 10.0;
+const BACKGROUND_VOCALS_POINTS: f64 = 5.0;
+const AGENTS_POINTS: f64 = 5.0;
+const LINE_DENSITY_POINTS: f64 = 20.0;
+const CLEANLINESS_POINTS: f64 = 10.0;
+
+/// 合理的歌词密度范围（行/分钟）。
+const PLAUSIBLE_LINES_PER_MINUTE: (f64, f64) = (3.0, 40.0);
+
+/// 评估一份歌词的质量。
+///
+/// # 参数
+/// * `parsed` - 解析后的歌词。
+/// * `duration_ms` - 歌曲时长（毫秒），用于判断行数是否合理；未知时传 `None`。
+#[must_use]
+pub fn assess_lyrics_quality(parsed: &ParsedSourceData, duration_ms: Option<u64>) -> LyricsQuality {
+    let lines: Vec<&LyricLine> = parsed
+        .lines
+        .iter()
+        .filter(|line| main_track(line).is_some_and(|track| !track.text().is_empty()))
+        .collect();
+    let line_count = lines.len();
+    if line_count == 0 {
+        return LyricsQuality::default();
+    }
+
+    let word_timed = if parsed.is_line_timed_source {
+        0
+    } else {
+        lines
+            .iter()
+            .filter(|line| main_track(line).is_some_and(is_word_timed))
+            .count()
+    };
+    let translated = lines
+        .iter()
+        .filter(|line| {
+            line.tracks
+                .iter()
+                .any(|track| has_text(&track.translations))
+        })
+        .count();
+    let romanized = lines
+        .iter()
+        .filter(|line| {
+            line.tracks
+                .iter()
+                .any(|track| has_text(&track.romanizations))
+        })
+        .count();
+    let has_background_vocals = lines.iter().any(|line| {
+        line.tracks
+            .iter()
+            .any(|track| track.content_type == ContentType::Background)
+    });
+    let line_agents: HashSet<&str> = lines
+        .iter()
+        .filter_map(|line| line.agent.as_deref())
+        .collect();
+    let agent_count = parsed.agents.agents_by_id.len().max(line_agents.len());
+
+    let lines_per_minute = duration_ms.filter(|&d| d > 0).map(|duration| {
+        let lines = f64::from(u32::try_from(line_count).unwrap_or(u32::MAX));
+        lines * 60_000.0 / f64::from(u32::try_from(duration).unwrap_or(u32::MAX))
+    });
+
+    let mut stripped = parsed.lines.clone();
+    strip_descriptive_metadata_lines(&mut stripped, &MetadataStripperOptions::default());
+    let metadata_noise_ratio = ratio(
+        parsed.lines.len().saturating_sub(stripped.len()),
+        parsed.lines.len(),
+    );
+
+    let mut quality = LyricsQuality {
+        line_count,
+        word_timed_coverage: ratio(word_timed, line_count),
+        translation_coverage: ratio(translated, line_count),
+        romanization_coverage: ratio(romanized, line_count),
+        has_background_vocals,
+        agent_count,
+        lines_per_minute,
+        metadata_noise_ratio,
+        score: 0.0,
+    };
+    quality.score = score(&quality);
+    quality
+}
+
+fn score(quality: &LyricsQuality) -> f64 {
+    let (min_density, max_density) = PLAUSIBLE_LINES_PER_MINUTE;
+    let density = quality.lines_per_minute.map_or(0.5, |lpm| {
+        if lpm < min_density {
+            lpm / min_density
+        } else if lpm > max_density {
+            max_density / lpm
+        } else {
+            1.0
+        }
+    });
+
+    let mut score = WORD_TIMING_POINTS * quality.word_timed_coverage;
+    score = TRANSLATION_POINTS.mul_add(quality.translation_coverage, score);
+    score = ROMANIZATION_POINTS.mul_add(quality.romanization_coverage, score);
+    score = LINE_DENSITY_POINTS.mul_add(density, score);
+    score = CLEANLINESS_POINTS.mul_add(1.0 - quality.metadata_noise_ratio, score);
+    if quality.has_background_vocals {
+        score += BACKGROUND_VOCALS_POINTS;
+    }
+    if quality.agent_count >= 2 {
+        score += AGENTS_POINTS;
+    }
+    score
+}
+
+fn main_track(line: &LyricLine) -> Option<&LyricTrack> {
+    line.tracks
+        .iter()
+        .find(|track| track.content_type == ContentType::Main)
+        .map(|track| &track.content)
+}
+
+/// 每个音节都有独立的时间戳时视为逐字计时。
+fn is_word_timed(track: &LyricTrack) -> bool {
+    let mut syllables = track.syllables().peekable();
+    syllables.peek().is_some() && syllables.all(|syl| syl.end_ms > syl.start_ms)
+}
+
+fn has_text(tracks: &[LyricTrack]) -> bool {
+    tracks.iter().any(|track| !track.text().is_empty())
+}
+
+fn ratio(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    f64::from(u32::try_from(part).unwrap_or(u32::MAX))
+        / f64::from(u32::try_from(total).unwrap_or(u32::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use lyrics_helper_core::{AnnotatedTrack, LyricSyllable, Word};
+
+    use super::*;
+
+    fn line(texts: &[&str], word_timed: bool, translation: Option<&str>) -> LyricLine {
+        let syllables = texts
+            .iter()
+            .enumerate()
+            .map(|(i, text)| {
+                let start = u64::try_from(i).unwrap() * 500;
+                LyricSyllable {
+                    text: (*text).to_string(),
+                    start_ms: start,
+                    end_ms: if word_timed { start + 500 } else { start },
+                    ends_with_space: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+        let mut track = AnnotatedTrack {
+            content: LyricTrack {
+                words: vec![Word {
+                    syllables,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        if let Some(translation) = translation {
+            track.add_translation(translation, "zh-Hans");
+        }
+        LyricLine {
+            tracks: vec![track],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_word_timed_translated_lyrics_score_higher() {
+        let line_timed = ParsedSourceData {
+            lines: (0..20)
+                .map(|_| line(&["hello world"], false, None))
+                .collect(),
+            is_line_timed_source: true,
+            ..Default::default()
+        };
+        let word_timed = ParsedSourceData {
+            lines: (0..20)
+                .map(|_| line(&["hello", "world"], true, Some("你好世界")))
+                .collect(),
+            ..Default::default()
+        };
+
+        let plain = assess_lyrics_quality(&line_timed, Some(180_000));
+        let rich = assess_lyrics_quality(&word_timed, Some(180_000));
+
+        assert_eq!(plain.line_count, 20);
+        assert!(plain.word_timed_coverage.abs() < f64::EPSILON);
+        assert!((rich.word_timed_coverage - 1.0).abs() < f64::EPSILON);
+        assert!((rich.translation_coverage - 1.0).abs() < f64::EPSILON);
+        assert!(rich.score > plain.score);
+        assert!(rich.score <= 100.0);
+
+        assert_eq!(
+            assess_lyrics_quality(&ParsedSourceData::default(), None),
+            LyricsQuality::default()
+        );
+    }
+}
@@ -0,0 +1,476 @@
+//! 歌曲引用解析模块。
+//!
+//! 把分享链接和已知格式的 ID 解析为 [`SongReference`]，以便跳过模糊搜索直接获取歌词。
+//!
+//! 支持的输入：
+//! - QQ音乐：`https://y.qq.com/n/ryqq/songDetail/<mid>`、`https://y.qq.com/n/yqq/song/<mid>.html`、
+//!   带 `songmid` 参数的移动端链接。
+//! - 网易云音乐：`https://music.163.com/song?id=<id>`（包括 `#/song?id=` 和 `/m/song?id=`）。
+//! - 酷狗音乐：带 `hash` 参数的链接，参数可以在查询串或 `#` 之后。
+//! - Apple Music：`https://music.apple.com/<地区>/song/<名称>/<id>`，或带 `?i=<id>` 的专辑链接。
+//! - Spotify：`https://open.spotify.com/track/<id>` 和 `spotify:track:<id>`。
+//! - ISRC，例如 `CNUM72400589` 或 `CN-UM7-24-00589`。
+//! - 带平台前缀的 ID，例如 `qq:<mid>`、`netease:<id>`、`kugou:<hash>`、`apple:<id>`、`isrc:<code>`。
+//!
+//! 需要跳转才能得到真实地址的短链接（如 `163cn.tv`、`t1.kugou.com`）无法离线解析。
+//! 不带前缀的纯数字 ID 在多个平台间有歧义，也不会被识别。
+//!
+//! 专辑、歌手和歌单由 [`parse_collection_reference`] 解析，支持QQ音乐和网易云音乐的分享链接，
+//! 以及 `<平台>:<类型>:<ID>` 形式的 ID，例如 `netease:album:123`、`kugou:playlist:<id>`。
+
+use std::sync::LazyLock;
+
+use lyrics_helper_core::{CollectionKind, CollectionReference, ReferencePlatform, SongReference};
+use regex::Regex;
+use url::Url;
+
+static ISRC_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?i)([A-Z]{2})-?([A-Z0-9]{3})-?(\d{2})-?(\d{5})$").expect("ISRC 正则表达式无效")
+});
+static SPOTIFY_ID_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[0-9A-Za-z]{22}$").expect("Spotify ID 正则表达式无效"));
+static QQ_MID_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[0-9A-Za-z]{14}$").expect("QQ音乐 mid 正则表达式无效"));
+static KUGOU_HASH_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[0-9A-Fa-f]{32}$").expect("酷狗 hash 正则表达式无效"));
+
+/// 将分享链接或 ID 解析为歌曲引用。
+///
+/// 无法识别时返回 `None`。
+#[must_use]
+pub fn parse_reference(input: &str) -> Option<SongReference> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+
+    if let Some(url) = parse_url(input) {
+        return parse_share_url(&url);
+    }
+    if let Some(id) = input.strip_prefix("spotify:track:") {
+        return reference(ReferencePlatform::Spotify, id, &SPOTIFY_ID_REGEX);
+    }
+    if let Some((prefix, id)) = input.split_once(':') {
+        return parse_prefixed_id(prefix, id.trim());
+    }
+    if let Some(isrc) = normalize_isrc(input) {
+        return Some(SongReference {
+            platform: ReferencePlatform::Isrc,
+            id: isrc,
+        });
+    }
+    reference(ReferencePlatform::Spotify, input, &SPOTIFY_ID_REGEX)
+}
+
+/// 将专辑、歌手或歌单的分享链接或 ID 解析为集合引用。
+///
+/// 无法识别时返回 `None`。
+#[must_use]
+pub fn parse_collection_reference(input: &str) -> Option<CollectionReference> {
+    let input = input.trim();
+    if let Some(url) = parse_url(input) {
+        return parse_collection_url(&url);
+    }
+
+    let mut parts = input.splitn(3, ':').map(str::trim);
+    let platform = match parts.next()?.to_ascii_lowercase().as_str() {
+        "qq" | "qqmusic" => ReferencePlatform::Qq,
+        "netease" | "ncm" | "163" => ReferencePlatform::Netease,
+        "kugou" => ReferencePlatform::Kugou,
+        _ => return None,
+    };
+    let kind = match parts.next()?.to_ascii_lowercase().as_str() {
+        "album" => CollectionKind::Album,
+        "artist" | "singer" => CollectionKind::Artist,
+        "playlist" => CollectionKind::Playlist,
+        _ => return None,
+    };
+    collection_reference(platform, kind, parts.next()?)
+}
+
+fn parse_collection_url(url: &Url) -> Option<CollectionReference> {
+    let host = url.host_str()?.to_ascii_lowercase();
+    let routed = url
+        .fragment()
+        .filter(|fragment| fragment.starts_with('/'))
+        .and_then(|fragment| url.join(fragment).ok());
+    let url = routed.as_ref().unwrap_or(url);
+    let segments: Vec<&str> = url
+        .path_segments()
+        .map(|segments| segments.filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default();
+    let segment_after = |names: &[&str]| {
+        segments
+            .iter()
+            .position(|s| names.contains(s))
+            .and_then(|i| segments.get(i + 1))
+            .map(|s| s.trim_end_matches(".html").to_string())
+    };
+
+    if host == "y.qq.com" || host.ends_with(".y.qq.com") {
+        let (kind, id) = if let Some(mid) = query_param(url, &["albummid", "albumMid"])
+            .or_else(|| segment_after(&["albumDetail", "album"]))
+        {
+            (CollectionKind::Album, mid)
+        } else if let Some(mid) =
+            query_param(url, &["singermid", "singerMid"]).or_else(|| segment_after(&["singer"]))
+        {
+            (CollectionKind::Artist, mid)
+        } else if let Some(id) = segment_after(&["playlist"]).or_else(|| {
+            segments
+                .last()
+                .is_some_and(|s| s.starts_with("taoge"))
+                .then(|| query_param(url, &["id"]))
+                .flatten()
+        }) {
+            (CollectionKind::Playlist, id)
+        } else {
+            return None;
+        };
+        return collection_reference(ReferencePlatform::Qq, kind, &id);
+    }
+    if host == "music.163.com" || host.ends_with(".music.163.com") {
+        let kind = segments.iter().rev().find_map(|s| match *s {
+            "album" => Some(CollectionKind::Album),
+            "artist" => Some(CollectionKind::Artist),
+            "playlist" => Some(CollectionKind::Playlist),
+            _ => None,
+        })?;
+        let id = query_param(url, &["id"])
+            .or_else(|| segment_after(&["album", "artist", "playlist"]))?;
+        return collection_reference(ReferencePlatform::Netease, kind, &id);
+    }
+    None
+}
+
+fn collection_reference(
+    platform: ReferencePlatform,
+    kind: CollectionKind,
+    id: &str,
+) -> Option<CollectionReference> {
+    let is_valid = match (platform, kind) {
+        (ReferencePlatform::Qq, CollectionKind::Album | CollectionKind::Artist) => {
+            QQ_MID_REGEX.is_match(id)
+        }
+        (ReferencePlatform::Qq | ReferencePlatform::Netease, _) => {
+            !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit())
+        }
+        _ => {
+            !id.is_empty()
+                && id
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+        }
+    };
+    is_valid.then(|| CollectionReference {
+        platform,
+        kind,
+        id: id.to_string(),
+    })
+}
+
+/// 解析 URL，允许省略协议头（例如 `music.163.com/song?id=1`）。
+fn parse_url(input: &str) -> Option<Url> {
+    if input.starts_with("http://") || input.starts_with("https://") {
+        return Url::parse(input).ok();
+    }
+    let host = input.split('/').next()?;
+    if input.contains('/') && host.contains('.') && !host.contains(':') {
+        return Url::parse(&format!("https://{input}")).ok();
+    }
+    None
+}
+
+fn parse_share_url(url: &Url) -> Option<SongReference> {
+    let host = url.host_str()?.to_ascii_lowercase();
+    // 部分站点把路由放在 `#` 之后，例如 `music.163.com/#/song?id=1`
+    let routed = url
+        .fragment()
+        .filter(|fragment| fragment.starts_with('/'))
+        .and_then(|fragment| url.join(fragment).ok());
+    let url = routed.as_ref().unwrap_or(url);
+    let segments: Vec<&str> = url
+        .path_segments()
+        .map(|segments| segments.filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default();
+
+    if host == "y.qq.com" || host.ends_with(".y.qq.com") {
+        let mid = query_param(url, &["songmid", "songMid", "mid"]).or_else(|| {
+            segments
+                .iter()
+                .position(|s| *s == "songDetail" || *s == "song")
+                .and_then(|i| segments.get(i + 1))
+                .map(|s| s.trim_end_matches(".html").to_string())
+        })?;
+        return reference(ReferencePlatform::Qq, &mid, &QQ_MID_REGEX);
+    }
+    if host == "music.163.com" || host.ends_with(".music.163.com") {
+        let id = query_param(url, &["id"])
+            .filter(|_| segments.last() == Some(&"song"))
+            .or_else(|| {
+                segments
+                    .iter()
+                    .position(|s| *s == "song")
+                    .and_then(|i| segments.get(i + 1))
+                    .map(ToString::to_string)
+            })?;
+        return numeric_reference(ReferencePlatform::Netease, &id);
+    }
+    if host == "kugou.com" || host.ends_with(".kugou.com") {
+        let hash = query_param(url, &["hash"]).or_else(|| fragment_param(url, "hash"))?;
+        return kugou_reference(&hash);
+    }
+    if host == "music.apple.com" || host == "itunes.apple.com" {
+        let id = query_param(url, &["i"]).or_else(|| {
+            segments
+                .contains(&"song")
+                .then(|| segments.last().map(ToString::to_string))
+                .flatten()
+        })?;
+        return numeric_reference(ReferencePlatform::AppleMusic, &id);
+    }
+    if host == "open.spotify.com" {
+        let id = segments
+            .iter()
+            .position(|s| *s == "track")
+            .and_then(|i| segments.get(i + 1))?;
+        return reference(ReferencePlatform::Spotify, id, &SPOTIFY_ID_REGEX);
+    }
+    None
+}
+
+fn parse_prefixed_id(prefix: &str, id: &str) -> Option<SongReference> {
+    match prefix.to_ascii_lowercase().as_str() {
+        "qq" | "qqmusic" => reference(ReferencePlatform::Qq, id, &QQ_MID_REGEX),
+        "netease" | "ncm" | "163" => numeric_reference(ReferencePlatform::Netease, id),
+        "kugou" => kugou_reference(id),
+        "apple" | "am" | "applemusic" => numeric_reference(ReferencePlatform::AppleMusic, id),
+        "spotify" => reference(ReferencePlatform::Spotify, id, &SPOTIFY_ID_REGEX),
+        "isrc" => normalize_isrc(id).map(|id| SongReference {
+            platform: ReferencePlatform::Isrc,
+            id,
+        }),
+        _ => None,
+    }
+}
+
+fn reference(platform: ReferencePlatform, id: &str, pattern: &Regex) -> Option<SongReference> {
+    pattern.is_match(id).then(|| SongReference {
+        platform,
+        id: id.to_string(),
+    })
+}
+
+fn numeric_reference(platform: ReferencePlatform, id: &str) -> Option<SongReference> {
+    (!id.is_empty() && id.bytes().all(|b| b.is_ascii_digit())).then(|| SongReference {
+        platform,
+        id: id.to_string(),
+    })
+}
+
+/// 酷狗的 hash 统一使用大写。
+fn kugou_reference(hash: &str) -> Option<SongReference> {
+    reference(
+        ReferencePlatform::Kugou,
+        &hash.to_ascii_uppercase(),
+        &KUGOU_HASH_REGEX,
+    )
+}
+
+/// 将 ISRC 规范化为不带连字符的大写形式。
+fn normalize_isrc(input: &str) -> Option<String> {
+    let caps = ISRC_REGEX.captures(input)?;
+    Some(
+        (1..=4)
+            .map(|i| &caps[i])
+            .collect::<String>()
+            .to_ascii_uppercase(),
+    )
+}
+
+fn query_param(url: &Url, names: &[&str]) -> Option<String> {
+    url.query_pairs()
+        .find(|(key, _)| names.contains(&key.as_ref()))
+        .map(|(_, value)| value.into_owned())
+        .filter(|value| !value.is_empty())
+}
+
+/// 读取 `#key=value&...` 形式的片段参数。
+fn fragment_param(url: &Url, name: &str) -> Option<String> {
+    url.fragment()?
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value.to_string())
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(input: &str) -> Option<(ReferencePlatform, String)> {
+        parse_reference(input).map(|r| (r.platform, r.id))
+    }
+
+    #[test]
+    fn test_parse_share_urls_and_ids() {
+        let cases = [
+            (
+                "https://y.qq.com/n/ryqq/songDetail/000pF84f1Mqkf7",
+                ReferencePlatform::Qq,
+                "000pF84f1Mqkf7",
+            ),
+            (
+                "https://y.qq.com/n/yqq/song/000pF84f1Mqkf7.html",
+                ReferencePlatform::Qq,
+                "000pF84f1Mqkf7",
+            ),
+            (
+                "https://i.y.qq.com/v8/playsong.html?songmid=000pF84f1Mqkf7&type=0",
+                ReferencePlatform::Qq,
+                "000pF84f1Mqkf7",
+            ),
+            (
+                "https://music.163.com/song?id=2642164541&userid=1",
+                ReferencePlatform::Netease,
+                "2642164541",
+            ),
+            (
+                "https://music.163.com/#/song?id=2642164541",
+                ReferencePlatform::Netease,
+                "2642164541",
+            ),
+            (
+                "music.163.com/m/song?id=2642164541",
+                ReferencePlatform::Netease,
+                "2642164541",
+            ),
+            (
+                "https://www.kugou.com/song/#hash=0a0fb0810a0fb0810a0fb0810a0fb081&album_id=1",
+                ReferencePlatform::Kugou,
+                "0A0FB0810A0FB0810A0FB0810A0FB081",
+            ),
+            (
+                "https://music.apple.com/cn/album/some-album/1700000000?i=1700000001",
+                ReferencePlatform::AppleMusic,
+                "1700000001",
+            ),
+            (
+                "https://music.apple.com/us/song/some-song/1700000001",
+                ReferencePlatform::AppleMusic,
+                "1700000001",
+            ),
+            (
+                "https://open.spotify.com/intl-ja/track/29OlvJxVuNd8BJazjvaYpP?si=abc",
+                ReferencePlatform::Spotify,
+                "29OlvJxVuNd8BJazjvaYpP",
+            ),
+            (
+                "spotify:track:29OlvJxVuNd8BJazjvaYpP",
+                ReferencePlatform::Spotify,
+                "29OlvJxVuNd8BJazjvaYpP",
+            ),
+            ("CN-UM7-24-00589", ReferencePlatform::Isrc, "CNUM72400589"),
+            ("isrc:cnum72400589", ReferencePlatform::Isrc, "CNUM72400589"),
+            ("ncm: 2642164541", ReferencePlatform::Netease, "2642164541"),
+            (
+                "apple:1700000001",
+                ReferencePlatform::AppleMusic,
+                "1700000001",
+            ),
+        ];
+        for (input, platform, id) in cases {
+            assert_eq!(
+                parsed(input),
+                Some((platform, id.to_string())),
+                "输入: {input}"
+            );
+        }
+
+        for input in [
+            "",
+            "2642164541",
+            "https://music.163.com/album?id=1",
+            "https://music.apple.com/cn/album/some-album/1700000000",
+            "https://example.com/song?id=1",
+            "unknown:123",
+        ] {
+            assert_eq!(parsed(input), None, "输入: {input}");
+        }
+    }
+
+    #[test]
+    fn test_parse_collection_references() {
+        let cases = [
+            (
+                "https://y.qq.com/n/ryqq/albumDetail/003DFRzD192KKD",
+                ReferencePlatform::Qq,
+                CollectionKind::Album,
+                "003DFRzD192KKD",
+            ),
+            (
+                "https://y.qq.com/n/ryqq/singer/0025NhlN2yWrP4",
+                ReferencePlatform::Qq,
+                CollectionKind::Artist,
+                "0025NhlN2yWrP4",
+            ),
+            (
+                "https://y.qq.com/n/ryqq/playlist/7256912512",
+                ReferencePlatform::Qq,
+                CollectionKind::Playlist,
+                "7256912512",
+            ),
+            (
+                "https://music.163.com/#/album?id=182985259",
+                ReferencePlatform::Netease,
+                CollectionKind::Album,
+                "182985259",
+            ),
+            (
+                "music.163.com/artist?id=12138269",
+                ReferencePlatform::Netease,
+                CollectionKind::Artist,
+                "12138269",
+            ),
+            (
+                "https://music.163.com/playlist?id=3778678&userid=1",
+                ReferencePlatform::Netease,
+                CollectionKind::Playlist,
+                "3778678",
+            ),
+            (
+                "kugou:playlist:collection_3_1_2_0",
+                ReferencePlatform::Kugou,
+                CollectionKind::Playlist,
+                "collection_3_1_2_0",
+            ),
+            (
+                "ncm:singer:12138269",
+                ReferencePlatform::Netease,
+                CollectionKind::Artist,
+                "12138269",
+            ),
+        ];
+        for (input, platform, kind, id) in cases {
+            assert_eq!(
+                parse_collection_reference(input),
+                Some(CollectionReference {
+                    platform,
+                    kind,
+                    id: id.to_string(),
+                }),
+                "输入: {input}"
+            );
+        }
+
+        for input in [
+            "https://music.163.com/song?id=1",
+            "netease:album:abc",
+            "apple:album:1",
+            "qq:album:",
+        ] {
+            assert_eq!(parse_collection_reference(input), None, "输入: {input}");
+        }
+    }
+}
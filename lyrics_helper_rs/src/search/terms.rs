@@ -0,0 +1,80 @@
+//! 检索词的切分，供本地歌词库和 AMLL TTML Database 的 FST 索引共用。
+
+/// 为 CJK 检索词建立后缀索引时，后缀的最少字符数。
+const MIN_CJK_SUFFIX_CHARS: usize = 2;
+
+/// 把文本拆分为检索词：按非字母数字字符分词，并额外加入去掉所有分隔符后的完整文本，
+/// 以便 “明明 (深爱着你)” 既能被 “深爱” 命中，也能被 “明明深爱” 命中。
+pub fn search_terms(text: &str) -> Vec<String> {
+    let lowered = text.to_lowercase();
+    let mut terms: Vec<String> = lowered
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect();
+    let compact: String = lowered.chars().filter(|c| c.is_alphanumeric()).collect();
+    if !compact.is_empty() && !terms.contains(&compact) {
+        terms.push(compact);
+    }
+    terms
+}
+
+/// 生成写入索引的检索词：在 [`search_terms`] 的基础上，为包含 CJK 字符的词加入从每个 CJK 字符开始的后缀。
+///
+/// CJK 文本没有空格分词，“深爱着你” 是一个完整的词，只靠前缀匹配无法被 “爱着你” 命中。
+/// 索引后缀后，前缀匹配就能覆盖词中间的任意位置。
+pub fn index_terms(text: &str) -> Vec<String> {
+    let mut terms = search_terms(text);
+    let suffixes: Vec<String> = terms
+        .iter()
+        .flat_map(|term| {
+            let chars: Vec<(usize, char)> = term.char_indices().collect();
+            chars
+                .iter()
+                .enumerate()
+                .skip(1)
+                .filter(|&(i, &(_, c))| is_cjk(c) && chars.len() - i >= MIN_CJK_SUFFIX_CHARS)
+                .map(|(_, &(offset, _))| term[offset..].to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    for suffix in suffixes {
+        if !terms.contains(&suffix) {
+            terms.push(suffix);
+        }
+    }
+    terms
+}
+
+/// 判断字符是否属于不以空格分词的 CJK 文字（汉字、假名、谚文）。
+const fn is_cjk(c: char) -> bool {
+    matches!(
+        c,
+        '\u{3040}'..='\u{30FF}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{AC00}'..='\u{D7A3}'
+            | '\u{F900}'..='\u{FAFF}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_terms_include_cjk_suffixes() {
+        assert_eq!(
+            search_terms("明明 (深爱着你)"),
+            vec!["明明", "深爱着你", "明明深爱着你"]
+        );
+
+        let terms = index_terms("明明 (深爱着你)");
+        assert!(terms.contains(&"爱着你".to_string()));
+        assert!(terms.contains(&"着你".to_string()));
+        assert!(!terms.contains(&"你".to_string()), "单字后缀不应写入索引");
+
+        // 拉丁字母的词不生成后缀
+        assert_eq!(index_terms("Shape of You"), search_terms("Shape of You"));
+    }
+}
@@ -0,0 +1,419 @@
+//! 跨文字转写模块。
+//!
+//! 将汉字（拼音）、假名（平文式罗马字）和谚文（韩语罗马字标记法）统一转写为小写拉丁字母，
+//! 用于比较 `Lemon` 与 `レモン`、`Yoru ni Kakeru` 与 `よるにかける` 这样跨文字的名称。
+//!
+//! 日语汉字没有读音词典可用，因此假名与汉字混写的日语名称不会整体转写，
+//! 而是由 [`transliteration_matches`] 以假名部分为锚点、把汉字当作长度受限的通配符来比较，
+//! 使 `夜に駆ける` 也能与 `Yoru ni Kakeru` 匹配。
+
+use pinyin::ToPinyin;
+use std::collections::BTreeSet;
+
+/// 单个汉字读音转写为罗马字后的最大长度，绝大多数训读和音读不超过这个长度。
+const MAX_KANJI_READING_LEN: usize = 6;
+
+/// 平假名到罗马字的对照表，片假名先转换为平假名再查表。
+const KANA_TABLE: &[(char, &str)] = &[
+    ('あ', "a"),
+    ('い', "i"),
+    ('う', "u"),
+    ('え', "e"),
+    ('お', "o"),
+    ('か', "ka"),
+    ('き', "ki"),
+    ('く', "ku"),
+    ('け', "ke"),
+    ('こ', "ko"),
+    ('が', "ga"),
+    ('ぎ', "gi"),
+    ('ぐ', "gu"),
+    ('げ', "ge"),
+    ('ご', "go"),
+    ('さ', "sa"),
+    ('し', "shi"),
+    ('す', "su"),
+    ('せ', "se"),
+    ('そ', "so"),
+    ('ざ', "za"),
+    ('じ', "ji"),
+    ('ず', "zu"),
+    ('ぜ', "ze"),
+    ('ぞ', "zo"),
+    ('た', "ta"),
+    ('ち', "chi"),
+    ('つ', "tsu"),
+    ('て', "te"),
+    ('と', "to"),
+    ('だ', "da"),
+    ('ぢ', "ji"),
+    ('づ', "zu"),
+    ('で', "de"),
+    ('ど', "do"),
+    ('な', "na"),
+    ('に', "ni"),
+    ('ぬ', "nu"),
+    ('ね', "ne"),
+    ('の', "no"),
+    ('は', "ha"),
+    ('ひ', "hi"),
+    ('ふ', "fu"),
+    ('へ', "he"),
+    ('ほ', "ho"),
+    ('ば', "ba"),
+    ('び', "bi"),
+    ('ぶ', "bu"),
+    ('べ', "be"),
+    ('ぼ', "bo"),
+    ('ぱ', "pa"),
+    ('ぴ', "pi"),
+    ('ぷ', "pu"),
+    ('ぺ', "pe"),
+    ('ぽ', "po"),
+    ('ま', "ma"),
+    ('み', "mi"),
+    ('む', "mu"),
+    ('め', "me"),
+    ('も', "mo"),
+    ('や', "ya"),
+    ('ゆ', "yu"),
+    ('よ', "yo"),
+    ('ら', "ra"),
+    ('り', "ri"),
+    ('る', "ru"),
+    ('れ', "re"),
+    ('ろ', "ro"),
+    ('わ', "wa"),
+    ('ゐ', "i"),
+    ('ゑ', "e"),
+    ('を', "o"),
+    ('ん', "n"),
+    ('ゔ', "vu"),
+];
+
+/// 谚文的初声、中声和终声。
+const HANGUL_INITIALS: [&str; 19] = [
+    "g", "kk", "n", "d", "tt", "r", "m", "b", "pp", "s", "ss", "", "j", "jj", "ch", "k", "t", "p",
+    "h",
+];
+const HANGUL_MEDIALS: [&str; 21] = [
+    "a", "ae", "ya", "yae", "eo", "e", "yeo", "ye", "o", "wa", "wae", "oe", "yo", "u", "wo", "we",
+    "wi", "yu", "eu", "ui", "i",
+];
+const HANGUL_FINALS: [&str; 28] = [
+    "", "k", "k", "k", "n", "n", "n", "t", "l", "k", "m", "l", "l", "l", "p", "l", "m", "p", "p",
+    "t", "t", "ng", "t", "t", "k", "t", "p", "t",
+];
+
+/// 将文本转写为小写拉丁字母。
+///
+/// 拉丁字母和数字会保留（转为小写），汉字、假名和谚文会被转写，其余字符（空白、标点等）会被丢弃。
+#[must_use]
+pub fn romanize(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() * 2);
+    // 促音（っ）需要重复下一个音节的辅音
+    let mut pending_sokuon = false;
+
+    for (c, pinyin) in text.chars().zip(text.to_pinyin()) {
+        if let Some(pinyin) = pinyin {
+            out.push_str(pinyin.plain());
+            continue;
+        }
+
+        let c = katakana_to_hiragana(c);
+        match c {
+            'っ' => pending_sokuon = true,
+            'ゃ' | 'ゅ' | 'ょ' => apply_youon(&mut out, c),
+            'ぁ' | 'ぃ' | 'ぅ' | 'ぇ' | 'ぉ' => apply_small_vowel(&mut out, c),
+            'ー' => {}
+            _ => {
+                let Some(syllable) = kana_syllable(c)
+                    .map(str::to_string)
+                    .or_else(|| hangul_syllable(c))
+                    .or_else(|| c.is_alphanumeric().then(|| latin(c)))
+                else {
+                    continue;
+                };
+                if std::mem::take(&mut pending_sokuon)
+                    && let Some(first) = syllable.chars().next()
+                    && !"aeioun".contains(first)
+                {
+                    out.push(if syllable.starts_with("ch") {
+                        't'
+                    } else {
+                        first
+                    });
+                }
+                out.push_str(&syllable);
+            }
+        }
+    }
+    out
+}
+
+/// 生成用于跨文字比较的读音键。
+///
+/// 在 [`romanize`] 的基础上合并日语中无法区分的读音（l/r、v/b）和长音，
+/// 使 `Lemon` 与 `レモン`、`Tokyo` 与 `とうきょう` 得到相同的键。
+#[must_use]
+pub fn phonetic_key(text: &str) -> String {
+    let mut key: String = romanize(text)
+        .chars()
+        .map(|c| match c {
+            'l' => 'r',
+            'v' => 'b',
+            _ => c,
+        })
+        .collect();
+    for (long, short) in [
+        ("ou", "o"),
+        ("oo", "o"),
+        ("uu", "u"),
+        ("aa", "a"),
+        ("ii", "i"),
+    ] {
+        key = key.replace(long, short);
+    }
+    key
+}
+
+/// 名称中出现的文字体系。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Script {
+    Latin,
+    Han,
+    Kana,
+    Hangul,
+}
+
+const fn script_of(c: char) -> Option<Script> {
+    match c {
+        'a'..='z' | 'A'..='Z' | '\u{00C0}'..='\u{024F}' => Some(Script::Latin),
+        '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '\u{F900}'..='\u{FAFF}' | '々' => {
+            Some(Script::Han)
+        }
+        '\u{3041}'..='\u{309F}' | '\u{30A0}'..='\u{30FF}' | '\u{31F0}'..='\u{31FF}' => {
+            Some(Script::Kana)
+        }
+        '\u{1100}'..='\u{11FF}' | '\u{3130}'..='\u{318F}' | '\u{AC00}'..='\u{D7A3}' => {
+            Some(Script::Hangul)
+        }
+        _ => None,
+    }
+}
+
+fn scripts(text: &str) -> BTreeSet<Script> {
+    text.chars().filter_map(script_of).collect()
+}
+
+/// 判断两个名称是否使用不同的文字体系书写，例如拉丁字母与假名、假名与谚文。
+///
+/// 同一文字体系内（例如两个都只含汉字的名称）不属于跨文字比较，
+/// 否则 `他说` 与 `她说` 这样的同音词也会得到相同的读音键。
+#[must_use]
+pub fn is_cross_script(name1: &str, name2: &str) -> bool {
+    let (scripts1, scripts2) = (scripts(name1), scripts(name2));
+    !scripts1.is_empty() && !scripts2.is_empty() && scripts1 != scripts2
+}
+
+/// 判断两个跨文字书写的名称是否读音相同。
+///
+/// 同一文字体系的名称总是返回 `false`。假名与汉字混写的日语名称按
+/// 假名锚点加汉字通配符的方式与另一方的读音键比较，其余情况比较 [`phonetic_key`]。
+#[must_use]
+pub fn transliteration_matches(name1: &str, name2: &str) -> bool {
+    if !is_cross_script(name1, name2) {
+        return false;
+    }
+    let is_mixed_japanese = |name: &str| {
+        let scripts = scripts(name);
+        scripts.contains(&Script::Kana) && scripts.contains(&Script::Han)
+    };
+    if is_mixed_japanese(name1) {
+        return matches_japanese_pattern(name1, &phonetic_key(name2));
+    }
+    if is_mixed_japanese(name2) {
+        return matches_japanese_pattern(name2, &phonetic_key(name1));
+    }
+    let key1 = phonetic_key(name1);
+    !key1.is_empty() && key1 == phonetic_key(name2)
+}
+
+/// 日语名称拆分出的片段。
+#[derive(Debug, PartialEq, Eq)]
+enum JapaneseSegment {
+    /// 假名等可以直接转写的部分的读音键。
+    Reading(String),
+    /// 连续的汉字个数。
+    Kanji(usize),
+}
+
+fn japanese_segments(name: &str) -> Vec<JapaneseSegment> {
+    let mut segments = Vec::new();
+    let mut run = String::new();
+    let mut kanji = 0;
+    let flush_run = |run: &mut String, segments: &mut Vec<JapaneseSegment>| {
+        let key = phonetic_key(run);
+        if !key.is_empty() {
+            segments.push(JapaneseSegment::Reading(key));
+        }
+        run.clear();
+    };
+
+    for c in name.chars() {
+        if script_of(c) == Some(Script::Han) {
+            flush_run(&mut run, &mut segments);
+            kanji += 1;
+        } else {
+            if kanji > 0 {
+                segments.push(JapaneseSegment::Kanji(std::mem::take(&mut kanji)));
+            }
+            run.push(c);
+        }
+    }
+    flush_run(&mut run, &mut segments);
+    if kanji > 0 {
+        segments.push(JapaneseSegment::Kanji(kanji));
+    }
+    segments
+}
+
+/// 用假名锚点和汉字通配符匹配另一方的读音键。
+///
+/// 为避免汉字过多时几乎任意读音都能匹配，要求假名读音的总长度不少于汉字个数。
+fn matches_japanese_pattern(name: &str, key: &str) -> bool {
+    let segments = japanese_segments(name);
+    let (reading_len, kanji_count) =
+        segments
+            .iter()
+            .fold((0, 0), |(reading, kanji), segment| match segment {
+                JapaneseSegment::Reading(r) => (reading + r.len(), kanji),
+                JapaneseSegment::Kanji(n) => (reading, kanji + n),
+            });
+    if reading_len == 0 || reading_len < kanji_count || key.is_empty() {
+        return false;
+    }
+    matches_segments(&segments, key)
+}
+
+fn matches_segments(segments: &[JapaneseSegment], key: &str) -> bool {
+    match segments.split_first() {
+        None => key.is_empty(),
+        Some((JapaneseSegment::Reading(reading), rest)) => key
+            .strip_prefix(reading.as_str())
+            .is_some_and(|key| matches_segments(rest, key)),
+        Some((JapaneseSegment::Kanji(count), rest)) => {
+            let max_len = (count * MAX_KANJI_READING_LEN).min(key.len());
+            (*count..=max_len)
+                .any(|len| key.is_char_boundary(len) && matches_segments(rest, &key[len..]))
+        }
+    }
+}
+
+fn katakana_to_hiragana(c: char) -> char {
+    match c {
+        'ァ'..='ヶ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+        _ => c,
+    }
+}
+
+fn kana_syllable(c: char) -> Option<&'static str> {
+    KANA_TABLE
+        .iter()
+        .find(|&&(kana, _)| kana == c)
+        .map(|&(_, romaji)| romaji)
+}
+
+/// 处理拗音，例如 `きゃ` → `kya`、`しゃ` → `sha`。
+fn apply_youon(out: &mut String, small: char) {
+    let vowel = match small {
+        'ゃ' => "a",
+        'ゅ' => "u",
+        _ => "o",
+    };
+    if out.ends_with('i') {
+        out.pop();
+        if !(out.ends_with("sh") || out.ends_with("ch") || out.ends_with('j')) {
+            out.push('y');
+        }
+    } else {
+        out.push('y');
+    }
+    out.push_str(vowel);
+}
+
+/// 处理外来语中的小写元音，例如 `ファ` → `fa`、`ティ` → `ti`。
+fn apply_small_vowel(out: &mut String, small: char) {
+    let vowel = match small {
+        'ぁ' => 'a',
+        'ぃ' => 'i',
+        'ぅ' => 'u',
+        'ぇ' => 'e',
+        _ => 'o',
+    };
+    if out.ends_with(['a', 'i', 'u', 'e', 'o']) && out.len() > 1 {
+        out.pop();
+    }
+    out.push(vowel);
+}
+
+fn hangul_syllable(c: char) -> Option<String> {
+    let index = (c as u32).checked_sub(0xAC00)?;
+    if index >= 11172 {
+        return None;
+    }
+    let initial = (index / (21 * 28)) as usize;
+    let medial = ((index % (21 * 28)) / 28) as usize;
+    let final_ = (index % 28) as usize;
+    Some(format!(
+        "{}{}{}",
+        HANGUL_INITIALS[initial], HANGUL_MEDIALS[medial], HANGUL_FINALS[final_]
+    ))
+}
+
+fn latin(c: char) -> String {
+    match c {
+        'ā' | 'â' => "a".to_string(),
+        'ī' | 'î' => "i".to_string(),
+        'ū' | 'û' => "u".to_string(),
+        'ē' | 'ê' => "e".to_string(),
+        'ō' | 'ô' => "o".to_string(),
+        _ => c.to_lowercase().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_romanize_kana_and_hangul() {
+        assert_eq!(romanize("よるにかける"), "yorunikakeru");
+        assert_eq!(romanize("レモン"), "remon");
+        assert_eq!(romanize("きゃりーぱみゅぱみゅ"), "kyaripamyupamyu");
+        assert_eq!(romanize("ちょっと"), "chotto");
+        assert_eq!(romanize("マッチ"), "matchi");
+        assert_eq!(romanize("ファンタジー"), "fantaji");
+        assert_eq!(romanize("아이유"), "aiyu");
+        assert_eq!(romanize("방탄소년단"), "bangtansonyeondan");
+        assert_eq!(romanize("Yoru ni Kakeru!"), "yorunikakeru");
+    }
+
+    #[test]
+    fn test_phonetic_key() {
+        assert_eq!(phonetic_key("Lemon"), phonetic_key("レモン"));
+        assert_eq!(phonetic_key("Tokyo"), phonetic_key("とうきょう"));
+        assert_ne!(phonetic_key("Lemon"), phonetic_key("Melon"));
+    }
+
+    #[test]
+    fn test_transliteration_matches() {
+        assert!(transliteration_matches("Lemon", "レモン"));
+        assert!(transliteration_matches("Yoru ni Kakeru", "夜に駆ける"));
+        assert!(transliteration_matches("夜に駆ける", "よるにかける"));
+        assert!(!transliteration_matches("Hana ni Naru", "夜に駆ける"));
+        // 同一文字体系内的同音词不算跨文字匹配
+        assert!(!transliteration_matches("他说", "她说"));
+        assert!(!transliteration_matches("Lemon", "Lemon"));
+    }
+}
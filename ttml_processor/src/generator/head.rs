@@ -126,10 +126,24 @@ fn write_itunes_metadata<W: std::io::Write>(
     lines: &[LyricLine],
     options: &TtmlGenerationOptions,
 ) -> Result<(), ConvertError> {
-    let valid_songwriters: Vec<&String> = metadata_store
-        .get_multiple_values(&CanonicalMetadataKey::Songwriter)
-        .map(|vec| vec.iter().filter(|s| !s.trim().is_empty()).collect())
-        .unwrap_or_default();
+    // Apple Music 的 songwriters 同时包含作词和作曲
+    let mut valid_songwriters: Vec<&String> = Vec::new();
+    for key in [
+        CanonicalMetadataKey::Songwriter,
+        CanonicalMetadataKey::Lyricist,
+        CanonicalMetadataKey::Composer,
+    ] {
+        for name in metadata_store
+            .get_multiple_values(&key)
+            .into_iter()
+            .flatten()
+        {
+            if !name.trim().is_empty() && !valid_songwriters.iter().any(|s| s.trim() == name.trim())
+            {
+                valid_songwriters.push(name);
+            }
+        }
+    }
 
     let lyric_offset = metadata_store
         .get_multiple_values_by_key("lyricOffset")
@@ -443,11 +457,33 @@ fn write_amll_metadata<W: std::io::Write>(
         (CanonicalMetadataKey::NcmMusicId, "ncmMusicId"),
         (CanonicalMetadataKey::SpotifyId, "spotifyId"),
         (CanonicalMetadataKey::QqMusicId, "qqMusicId"),
+        (CanonicalMetadataKey::KugouHash, "kugouHash"),
+        (CanonicalMetadataKey::KugouAlbumAudioId, "kugouAlbumAudioId"),
+        (CanonicalMetadataKey::NcmAlbumId, "ncmAlbumId"),
+        (CanonicalMetadataKey::QqAlbumId, "qqAlbumId"),
+        (
+            CanonicalMetadataKey::MusicBrainzRecordingId,
+            "musicBrainzRecordingId",
+        ),
+        (CanonicalMetadataKey::ReleaseYear, "releaseYear"),
+        (CanonicalMetadataKey::TrackNumber, "trackNumber"),
+        (CanonicalMetadataKey::Duration, "duration"),
         (CanonicalMetadataKey::TtmlAuthorGithub, "ttmlAuthorGithub"),
         (
             CanonicalMetadataKey::TtmlAuthorGithubLogin,
             "ttmlAuthorGithubLogin",
         ),
+        (CanonicalMetadataKey::LyricsContributor, "lyricsContributor"),
+        (
+            CanonicalMetadataKey::TranslationContributor,
+            "translationContributor",
+        ),
+        (CanonicalMetadataKey::Lyricist, "lyricist"),
+        (CanonicalMetadataKey::Composer, "composer"),
+        (CanonicalMetadataKey::Arranger, "arranger"),
+        (CanonicalMetadataKey::Producer, "producer"),
+        (CanonicalMetadataKey::MixingEngineer, "mixingEngineer"),
+        (CanonicalMetadataKey::MasteringEngineer, "masteringEngineer"),
     ];
 
     let mut written_keys = std::collections::HashSet::new();
@@ -467,24 +503,34 @@ fn write_amll_metadata<W: std::io::Write>(
         }
     }
 
-    let mut custom_metadata = Vec::new();
+    // 本地化键写为 `musicName[ja]` 的形式
+    let mut custom_metadata: Vec<(String, &Vec<String>)> = Vec::new();
     for (key, values) in metadata_store.get_all_data() {
-        if !written_keys.contains(key)
-            && let CanonicalMetadataKey::Custom(s) = key
-            && s != "lyricOffset"
-        {
-            custom_metadata.push((s.as_str(), values));
+        if written_keys.contains(key) {
+            continue;
+        }
+        match key {
+            CanonicalMetadataKey::LocalizedTitle(lang) => {
+                custom_metadata.push((format!("musicName[{lang}]"), values));
+            }
+            CanonicalMetadataKey::LocalizedArtist(lang) => {
+                custom_metadata.push((format!("artists[{lang}]"), values));
+            }
+            CanonicalMetadataKey::Custom(s) if s != "lyricOffset" => {
+                custom_metadata.push((s.clone(), values));
+            }
+            _ => {}
         }
     }
 
-    custom_metadata.sort_unstable_by_key(|(k, _)| *k);
+    custom_metadata.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
 
     for (key_name, values) in custom_metadata {
         for value_str in values {
             if !value_str.trim().is_empty() {
                 writer
                     .create_element("amll:meta")
-                    .with_attribute(("key", key_name))
+                    .with_attribute(("key", key_name.as_str()))
                     .with_attribute(("value", value_str.trim()))
                     .write_empty()?;
             }
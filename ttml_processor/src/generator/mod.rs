@@ -86,12 +86,35 @@ fn generate_ttml_inner<W: std::io::Write>(
         CanonicalMetadataKey::NcmMusicId,
         CanonicalMetadataKey::QqMusicId,
         CanonicalMetadataKey::SpotifyId,
+        CanonicalMetadataKey::KugouHash,
+        CanonicalMetadataKey::KugouAlbumAudioId,
+        CanonicalMetadataKey::NcmAlbumId,
+        CanonicalMetadataKey::QqAlbumId,
+        CanonicalMetadataKey::MusicBrainzRecordingId,
+        CanonicalMetadataKey::ReleaseYear,
+        CanonicalMetadataKey::TrackNumber,
+        CanonicalMetadataKey::Duration,
         CanonicalMetadataKey::TtmlAuthorGithub,
         CanonicalMetadataKey::TtmlAuthorGithubLogin,
+        CanonicalMetadataKey::LyricsContributor,
+        CanonicalMetadataKey::TranslationContributor,
+        CanonicalMetadataKey::Lyricist,
+        CanonicalMetadataKey::Composer,
+        CanonicalMetadataKey::Arranger,
+        CanonicalMetadataKey::Producer,
+        CanonicalMetadataKey::MixingEngineer,
+        CanonicalMetadataKey::MasteringEngineer,
     ];
-    if amll_keys_to_check_for_namespace
-        .iter()
-        .any(|key| metadata_store.get_multiple_values(key).is_some())
+    let has_localized_keys = metadata_store.get_all_data().keys().any(|key| {
+        matches!(
+            key,
+            CanonicalMetadataKey::LocalizedTitle(_) | CanonicalMetadataKey::LocalizedArtist(_)
+        )
+    });
+    if has_localized_keys
+        || amll_keys_to_check_for_namespace
+            .iter()
+            .any(|key| metadata_store.get_multiple_values(key).is_some())
     {
         namespace_attrs.push(("xmlns:amll", "http://www.example.com/ns/amll".to_string()));
     }
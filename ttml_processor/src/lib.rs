@@ -9,6 +9,9 @@
 //! - [`parse_ttml`]: Converts a TTML string into a `ParsedSourceData` object from `lyrics_helper_core`.
 //! - [`generate_ttml`]: Creates a TTML string from `LyricLine` data structures.
 //!
+//! For contributors to the AMLL TTML Database, [`validate_amll_db_submission`] checks a file
+//! against the database's conventions and produces a normalized, submission-ready TTML.
+//!
 //! ## ⚠️ Important: Not a General-Purpose Parser
 //!
 //! This library is **not** designed for generic TTML subtitle files. It is specifically
@@ -81,6 +84,8 @@
 mod generator;
 mod parser;
 mod utils;
+mod validator;
 
 pub use generator::generate_ttml;
 pub use parser::parse_ttml;
+pub use validator::validate_amll_db_submission;
@@ -4,11 +4,11 @@
 //! 不建议用于解析通用的 TTML 字幕文件。
 
 mod body;
-mod constants;
+pub(super) mod constants;
 mod handlers;
 mod metadata;
 mod state;
-mod utils;
+pub(super) mod utils;
 
 use std::collections::HashMap;
 
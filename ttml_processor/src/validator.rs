@@ -0,0 +1,484 @@
+//! # AMLL TTML Database 投稿校验器
+//!
+//! 按照 amll-ttml-db 的投稿规范检查 TTML 歌词文件，生成机器可读的校验报告，
+//! 并输出修复了可自动修复问题的规范化 TTML。
+//!
+//! 部分规范（例如演唱者声明、音节内的空白）在解析后就无法区分，因此会先对原始 XML 做一次扫描，
+//! 其余检查基于解析结果进行。
+
+use std::collections::{HashMap, HashSet};
+
+use lyrics_helper_core::{
+    AmllDbIssue, AmllDbIssueKind, AmllDbSubmission, AmllDbValidationOptions,
+    AmllDbValidationReport, ConvertError, DefaultLanguageOptions, LyricLine, MetadataStore,
+    TtmlGenerationOptions, TtmlParsingOptions, TtmlTimingMode, ValidationSeverity,
+};
+use quick_xml::{Reader, events::Event};
+
+use crate::{
+    generate_ttml, parse_ttml,
+    parser::{
+        constants::{
+            ATTR_AGENT, ATTR_AGENT_ALIAS, ATTR_BEGIN, ATTR_ITUNES_TIMING, ATTR_ROLE,
+            ATTR_ROLE_ALIAS, ATTR_XML_ID, ATTR_XML_LANG, ROLE_ROMANIZATION, ROLE_TRANSLATION,
+            TAG_AGENT, TAG_AGENT_TTM, TAG_P, TAG_SPAN, TAG_TRANSLATION, TAG_TRANSLITERATION,
+            TAG_TT,
+        },
+        utils::get_string_attribute,
+    },
+};
+
+/// 投稿时必需的元数据键及其描述。
+const REQUIRED_METADATA_KEYS: [(&str, &str); 3] = [
+    ("musicName", "歌曲名"),
+    ("artists", "艺术家"),
+    ("album", "专辑"),
+];
+
+/// 翻译或罗马音。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AuxiliaryKind {
+    Translation,
+    Romanization,
+}
+
+impl AuxiliaryKind {
+    const fn description(self) -> &'static str {
+        match self {
+            Self::Translation => "翻译",
+            Self::Romanization => "罗马音",
+        }
+    }
+}
+
+/// 对原始 XML 扫描得到的信息。
+#[derive(Debug, Default)]
+struct DocumentScan {
+    /// 是否找到了 `<tt>` 根元素。
+    has_root: bool,
+    /// `<tt>` 的 `itunes:timing` 属性。
+    timing_mode: Option<String>,
+    /// `<tt>` 的 `xml:lang` 属性。
+    main_language: Option<String>,
+    /// 在 `<head>` 中声明的演唱者 ID。
+    declared_agents: Vec<String>,
+    /// 被歌词行使用的演唱者 ID 及首次使用的行号。
+    used_agents: Vec<(String, usize)>,
+    /// 翻译和罗马音的语言标签及其所在行号。
+    language_tags: Vec<(AuxiliaryKind, Option<String>, Option<usize>)>,
+    /// 首尾带有空白的音节文本及其所在行号。
+    padded_syllables: Vec<(String, usize)>,
+}
+
+/// 按照 AMLL TTML Database 的投稿规范校验 TTML 歌词，并生成规范化的 TTML。
+///
+/// 检查的内容包括：
+/// - 必需的 `amll:meta` 元数据（`musicName`、`artists`、`album`）和至少一个平台 ID；
+/// - `<head>` 中声明的演唱者与歌词行实际使用的演唱者是否一致；
+/// - `<tt>` 的 `itunes:timing` 属性；
+/// - 翻译和罗马音的 `xml:lang` 语言标签；
+/// - 相邻音节的时间重叠，以及音节和行末多余的空白。
+///
+/// # 参数
+///
+/// * `content` - TTML 格式的歌词文件内容字符串。
+/// * `options` - 校验与规范化选项。
+///
+/// # 返回
+///
+/// * `Ok(AmllDbSubmission)` - 校验报告和规范化后的 TTML。报告中 `fixed` 为 `true` 的问题已在
+///   规范化输出中修复。
+///
+/// # Errors
+///
+/// 当输入不是有效的 XML，或在解析、生成 TTML 时出错，返回 `ConvertError`。
+pub fn validate_amll_db_submission(
+    content: &str,
+    options: &AmllDbValidationOptions,
+) -> Result<AmllDbSubmission, ConvertError> {
+    let scan = scan_document(content)?;
+
+    let parsing_options = TtmlParsingOptions {
+        default_languages: DefaultLanguageOptions {
+            main: None,
+            translation: options.default_translation_language.clone(),
+            romanization: options.default_romanization_language.clone(),
+        },
+        force_timing_mode: None,
+    };
+    let mut parsed = parse_ttml(content, &parsing_options)?;
+
+    let mut report = AmllDbValidationReport::default();
+    check_metadata(&parsed.raw_metadata, options, &mut report);
+    check_agents(&scan, &mut report);
+    check_timing_mode(&scan, &mut report);
+    check_language_tags(&scan, options, &mut report);
+    check_whitespace(&scan, &mut parsed.lines, &mut report);
+    if !parsed.is_line_timed_source {
+        fix_overlapping_syllables(&mut parsed.lines, &mut report);
+    }
+
+    // 去掉未被使用的演唱者；没有任何行指定演唱者时保持原样
+    let used_agents: HashSet<String> = parsed
+        .lines
+        .iter()
+        .filter_map(|line| line.agent.clone())
+        .collect();
+    if !used_agents.is_empty() {
+        parsed
+            .agents
+            .agents_by_id
+            .retain(|id, _| used_agents.contains(id));
+    }
+
+    let generation_options = TtmlGenerationOptions {
+        timing_mode: if parsed.is_line_timed_source {
+            TtmlTimingMode::Line
+        } else {
+            TtmlTimingMode::Word
+        },
+        main_language: scan.main_language,
+        format: options.format_output,
+        ..Default::default()
+    };
+    let normalized_ttml = generate_ttml(
+        &parsed.lines,
+        &MetadataStore::from(&parsed),
+        &parsed.agents,
+        &generation_options,
+    )?;
+
+    Ok(AmllDbSubmission {
+        report,
+        normalized_ttml,
+    })
+}
+
+/// 扫描原始 XML，收集解析后无法还原的信息。
+fn scan_document(content: &str) -> Result<DocumentScan, ConvertError> {
+    let mut reader = Reader::from_str(content);
+    reader.config_mut().expand_empty_elements = true;
+
+    let mut scan = DocumentScan::default();
+    let mut line_count = 0;
+    let mut current_line: Option<usize> = None;
+    // 每个打开的 <span>；带时间戳的音节 span 会收集其文本
+    let mut span_stack: Vec<Option<String>> = Vec::new();
+
+    loop {
+        match reader.read_event().map_err(ConvertError::new_parse)? {
+            Event::Start(e) => match e.name().as_ref() {
+                TAG_TT => {
+                    scan.has_root = true;
+                    scan.timing_mode = get_string_attribute(&e, &reader, &[ATTR_ITUNES_TIMING])?;
+                    scan.main_language = get_string_attribute(&e, &reader, &[ATTR_XML_LANG])?;
+                }
+                TAG_AGENT | TAG_AGENT_TTM => {
+                    if let Some(id) = get_string_attribute(&e, &reader, &[ATTR_XML_ID])? {
+                        scan.declared_agents.push(id);
+                    }
+                }
+                TAG_P => {
+                    current_line = Some(line_count);
+                    if let Some(agent) =
+                        get_string_attribute(&e, &reader, &[ATTR_AGENT, ATTR_AGENT_ALIAS])?
+                    {
+                        scan.used_agents.push((agent, line_count));
+                    }
+                    line_count += 1;
+                }
+                TAG_SPAN => {
+                    let role = get_string_attribute(&e, &reader, &[ATTR_ROLE, ATTR_ROLE_ALIAS])?;
+                    let kind = match role.as_deref().map(str::as_bytes) {
+                        Some(ROLE_TRANSLATION) => Some(AuxiliaryKind::Translation),
+                        Some(ROLE_ROMANIZATION) => Some(AuxiliaryKind::Romanization),
+                        _ => None,
+                    };
+                    if let Some(kind) = kind {
+                        let lang = get_string_attribute(&e, &reader, &[ATTR_XML_LANG])?;
+                        scan.language_tags.push((kind, lang, current_line));
+                    }
+
+                    let is_syllable = role.is_none()
+                        && current_line.is_some()
+                        && get_string_attribute(&e, &reader, &[ATTR_BEGIN])?.is_some();
+                    span_stack.push(is_syllable.then(String::new));
+                }
+                TAG_TRANSLATION | TAG_TRANSLITERATION => {
+                    let kind = if e.name().as_ref() == TAG_TRANSLATION {
+                        AuxiliaryKind::Translation
+                    } else {
+                        AuxiliaryKind::Romanization
+                    };
+                    let lang = get_string_attribute(&e, &reader, &[ATTR_XML_LANG])?;
+                    scan.language_tags.push((kind, lang, None));
+                }
+                _ => {}
+            },
+            Event::Text(e) => {
+                if let Some(Some(text)) = span_stack.last_mut() {
+                    text.push_str(&e.xml_content().map_err(ConvertError::new_parse)?);
+                }
+            }
+            Event::GeneralRef(_) => {
+                // 实体引用不会是空白，用占位符代替即可
+                if let Some(Some(text)) = span_stack.last_mut() {
+                    text.push('&');
+                }
+            }
+            Event::End(e) => match e.name().as_ref() {
+                TAG_P => current_line = None,
+                TAG_SPAN => {
+                    if let Some(Some(text)) = span_stack.pop()
+                        && text.trim() != text
+                        && let Some(line) = current_line
+                    {
+                        scan.padded_syllables.push((text, line));
+                    }
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(scan)
+}
+
+fn check_metadata(
+    raw_metadata: &HashMap<String, Vec<String>>,
+    options: &AmllDbValidationOptions,
+    report: &mut AmllDbValidationReport,
+) {
+    let has_value = |key: &str| {
+        raw_metadata
+            .get(key)
+            .is_some_and(|values| values.iter().any(|value| !value.trim().is_empty()))
+    };
+
+    for (key, description) in REQUIRED_METADATA_KEYS {
+        if !has_value(key) {
+            report.issues.push(issue(
+                AmllDbIssueKind::MissingMetadata,
+                ValidationSeverity::Error,
+                format!("缺少{description}元数据 `{key}`。"),
+                None,
+                false,
+            ));
+        }
+    }
+
+    if !options.platform_id_keys.iter().any(|key| has_value(key)) {
+        report.issues.push(issue(
+            AmllDbIssueKind::MissingPlatformId,
+            ValidationSeverity::Error,
+            format!(
+                "至少需要提供一个平台 ID：{}。",
+                options.platform_id_keys.join("、")
+            ),
+            None,
+            false,
+        ));
+    }
+}
+
+fn check_agents(scan: &DocumentScan, report: &mut AmllDbValidationReport) {
+    let declared: HashSet<&str> = scan.declared_agents.iter().map(String::as_str).collect();
+    let mut reported = HashSet::new();
+    for (agent, line) in &scan.used_agents {
+        if !declared.contains(agent.as_str()) && reported.insert(agent.as_str()) {
+            report.issues.push(issue(
+                AmllDbIssueKind::UndeclaredAgent,
+                ValidationSeverity::Error,
+                format!("演唱者 `{agent}` 未在 <head> 中声明。"),
+                Some(*line),
+                true,
+            ));
+        }
+    }
+
+    let used: HashSet<&str> = scan
+        .used_agents
+        .iter()
+        .map(|(agent, _)| agent.as_str())
+        .collect();
+    for agent in &scan.declared_agents {
+        if !used.contains(agent.as_str()) {
+            report.issues.push(issue(
+                AmllDbIssueKind::UnusedAgent,
+                ValidationSeverity::Warning,
+                format!("声明了演唱者 `{agent}`，但没有歌词行使用。"),
+                None,
+                !used.is_empty(),
+            ));
+        }
+    }
+}
+
+fn check_timing_mode(scan: &DocumentScan, report: &mut AmllDbValidationReport) {
+    if !scan.has_root {
+        return;
+    }
+    match &scan.timing_mode {
+        None => report.issues.push(issue(
+            AmllDbIssueKind::MissingTimingMode,
+            ValidationSeverity::Error,
+            "<tt> 缺少 `itunes:timing` 属性。".to_string(),
+            None,
+            true,
+        )),
+        Some(mode) if !mode.eq_ignore_ascii_case("word") && !mode.eq_ignore_ascii_case("line") => {
+            report.issues.push(issue(
+                AmllDbIssueKind::InvalidTimingMode,
+                ValidationSeverity::Error,
+                format!("`itunes:timing` 的值 `{mode}` 无效，应为 `Word` 或 `Line`。"),
+                None,
+                true,
+            ));
+        }
+        _ => {}
+    }
+}
+
+fn check_language_tags(
+    scan: &DocumentScan,
+    options: &AmllDbValidationOptions,
+    report: &mut AmllDbValidationReport,
+) {
+    for (kind, lang, line) in &scan.language_tags {
+        match lang {
+            None => {
+                let default_language = match kind {
+                    AuxiliaryKind::Translation => &options.default_translation_language,
+                    AuxiliaryKind::Romanization => &options.default_romanization_language,
+                };
+                report.issues.push(issue(
+                    AmllDbIssueKind::MissingLanguageTag,
+                    ValidationSeverity::Warning,
+                    format!("{}缺少 `xml:lang` 语言标签。", kind.description()),
+                    *line,
+                    default_language.is_some(),
+                ));
+            }
+            Some(lang) if !is_valid_language_tag(lang) => {
+                report.issues.push(issue(
+                    AmllDbIssueKind::InvalidLanguageTag,
+                    ValidationSeverity::Error,
+                    format!(
+                        "{}的语言标签 `{lang}` 不是合法的 BCP 47 标签。",
+                        kind.description()
+                    ),
+                    *line,
+                    false,
+                ));
+            }
+            Some(_) => {}
+        }
+    }
+}
+
+/// 报告音节内和行末多余的空白，并去掉行末的空格。
+///
+/// 音节内的首尾空白在解析时已经被移到音节之间，规范化输出中自然会得到修复。
+fn check_whitespace(
+    scan: &DocumentScan,
+    lines: &mut [LyricLine],
+    report: &mut AmllDbValidationReport,
+) {
+    for (text, line) in &scan.padded_syllables {
+        report.issues.push(issue(
+            AmllDbIssueKind::TrailingWhitespace,
+            ValidationSeverity::Warning,
+            format!("音节 `{text}` 的首尾带有空白，空格应放在 <span> 之间。"),
+            Some(*line),
+            true,
+        ));
+    }
+
+    for (index, line) in lines.iter_mut().enumerate() {
+        let mut has_trailing_space = false;
+        for track in &mut line.tracks {
+            if let Some(last) = track
+                .content
+                .words
+                .iter_mut()
+                .rev()
+                .find_map(|word| word.syllables.last_mut())
+                && last.ends_with_space
+            {
+                last.ends_with_space = false;
+                has_trailing_space = true;
+            }
+        }
+        if has_trailing_space {
+            report.issues.push(issue(
+                AmllDbIssueKind::TrailingWhitespace,
+                ValidationSeverity::Warning,
+                "行末带有多余的空格。".to_string(),
+                Some(index),
+                true,
+            ));
+        }
+    }
+}
+
+/// 将与下一个音节重叠的音节的结束时间截断到下一个音节的开始时间。
+fn fix_overlapping_syllables(lines: &mut [LyricLine], report: &mut AmllDbValidationReport) {
+    for (index, line) in lines.iter_mut().enumerate() {
+        let mut overlaps = 0;
+        for track in &mut line.tracks {
+            let mut syllables: Vec<_> = track
+                .content
+                .words
+                .iter_mut()
+                .flat_map(|word| word.syllables.iter_mut())
+                .collect();
+            for i in 1..syllables.len() {
+                let next_start = syllables[i].start_ms;
+                let previous = &mut *syllables[i - 1];
+                if next_start < previous.end_ms {
+                    previous.end_ms = next_start.max(previous.start_ms);
+                    overlaps += 1;
+                }
+            }
+        }
+        if overlaps > 0 {
+            report.issues.push(issue(
+                AmllDbIssueKind::OverlappingSpans,
+                ValidationSeverity::Warning,
+                format!("有 {overlaps} 个音节与下一个音节的时间重叠。"),
+                Some(index),
+                true,
+            ));
+        }
+    }
+}
+
+/// 粗略检查 BCP 47 语言标签的格式，例如 `zh-Hans`、`ja-Latn`、`en`。
+fn is_valid_language_tag(tag: &str) -> bool {
+    let mut subtags = tag.split('-');
+    let primary_is_valid = subtags.next().is_some_and(|primary| {
+        matches!(primary.len(), 2 | 3 | 5..=8) && primary.chars().all(|c| c.is_ascii_alphabetic())
+    });
+    primary_is_valid
+        && subtags.all(|subtag| {
+            (1..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+}
+
+const fn issue(
+    kind: AmllDbIssueKind,
+    severity: ValidationSeverity,
+    message: String,
+    line_index: Option<usize>,
+    fixed: bool,
+) -> AmllDbIssue {
+    AmllDbIssue {
+        kind,
+        severity,
+        message,
+        line_index,
+        fixed,
+    }
+}
@@ -0,0 +1,104 @@
+use lyrics_helper_core::*;
+use ttml_processor::{parse_ttml, validate_amll_db_submission};
+
+const VALID_SUBMISSION: &str = r#"<tt xmlns="http://www.w3.org/ns/ttml" xmlns:ttm="http://www.w3.org/ns/ttml#metadata" xmlns:amll="http://www.example.com/ns/amll" xmlns:itunes="http://music.apple.com/lyric-ttml-internal" itunes:timing="Word"><head><metadata><ttm:agent type="person" xml:id="v1"/><amll:meta key="musicName" value="测试歌曲"/><amll:meta key="artists" value="测试歌手"/><amll:meta key="album" value="测试专辑"/><amll:meta key="ncmMusicId" value="123456"/></metadata></head><body dur="00:04.000"><div begin="00:01.000" end="00:04.000"><p begin="00:01.000" end="00:04.000" ttm:agent="v1"><span begin="00:01.000" end="00:02.000">Hello</span> <span begin="00:02.000" end="00:04.000">world</span><span ttm:role="x-translation" xml:lang="zh-Hans">你好世界</span></p></div></body></tt>"#;
+
+fn issue_kinds(report: &AmllDbValidationReport) -> Vec<AmllDbIssueKind> {
+    report.issues.iter().map(|issue| issue.kind).collect()
+}
+
+#[test]
+fn test_valid_submission_has_no_issues() {
+    let submission =
+        validate_amll_db_submission(VALID_SUBMISSION, &AmllDbValidationOptions::default()).unwrap();
+
+    assert!(
+        submission.report.issues.is_empty(),
+        "不应发现问题: {:?}",
+        submission.report.issues
+    );
+    assert!(submission.report.is_submittable());
+
+    let reparsed = parse_ttml(&submission.normalized_ttml, &TtmlParsingOptions::default()).unwrap();
+    assert_eq!(reparsed.lines.len(), 1);
+    assert_eq!(
+        reparsed.lines[0].main_text().as_deref(),
+        Some("Hello world")
+    );
+}
+
+#[test]
+fn test_report_and_normalize_common_problems() {
+    let content = r#"<tt xmlns="http://www.w3.org/ns/ttml" xmlns:ttm="http://www.w3.org/ns/ttml#metadata" xmlns:amll="http://www.example.com/ns/amll"><head><metadata><ttm:agent type="person" xml:id="v1"/><ttm:agent type="person" xml:id="v9"/><amll:meta key="musicName" value="测试歌曲"/><amll:meta key="artists" value="测试歌手"/></metadata></head><body><div><p begin="00:01.000" end="00:04.000" ttm:agent="v1"><span begin="00:01.000" end="00:02.500">Hello </span><span begin="00:02.000" end="00:04.000">world</span> <span ttm:role="x-translation">你好世界</span></p><p begin="00:05.000" end="00:06.000" ttm:agent="v2"><span begin="00:05.000" end="00:06.000">Bye</span><span ttm:role="x-roman" xml:lang="ja_Latn">bai</span></p></div></body></tt>"#;
+    let options = AmllDbValidationOptionsBuilder::default()
+        .default_translation_language(Some("zh-Hans".to_string()))
+        .build()
+        .unwrap();
+
+    let submission = validate_amll_db_submission(content, &options).unwrap();
+    let report = &submission.report;
+
+    assert_eq!(
+        issue_kinds(report),
+        vec![
+            AmllDbIssueKind::MissingMetadata,
+            AmllDbIssueKind::MissingPlatformId,
+            AmllDbIssueKind::UndeclaredAgent,
+            AmllDbIssueKind::UnusedAgent,
+            AmllDbIssueKind::MissingTimingMode,
+            AmllDbIssueKind::MissingLanguageTag,
+            AmllDbIssueKind::InvalidLanguageTag,
+            // 音节 "Hello " 内的空格和 "world" 之后、翻译之前的空格
+            AmllDbIssueKind::TrailingWhitespace,
+            AmllDbIssueKind::TrailingWhitespace,
+            AmllDbIssueKind::OverlappingSpans,
+        ],
+        "{:#?}",
+        report.issues
+    );
+    // 缺少专辑、平台 ID 和非法的语言标签无法自动修复
+    assert!(!report.is_submittable());
+    let unfixed: Vec<_> = report
+        .issues
+        .iter()
+        .filter(|issue| !issue.fixed)
+        .map(|issue| issue.kind)
+        .collect();
+    assert_eq!(
+        unfixed,
+        vec![
+            AmllDbIssueKind::MissingMetadata,
+            AmllDbIssueKind::MissingPlatformId,
+            AmllDbIssueKind::InvalidLanguageTag,
+        ]
+    );
+    let overlap = report
+        .issues_of_kind(AmllDbIssueKind::OverlappingSpans)
+        .next()
+        .unwrap();
+    assert_eq!(overlap.line_index, Some(0));
+
+    // 规范化输出中的问题已被修复
+    let normalized = &submission.normalized_ttml;
+    assert!(normalized.contains(r#"itunes:timing="Word""#));
+    assert!(!normalized.contains(r#"xml:id="v9""#));
+    let reparsed = parse_ttml(normalized, &TtmlParsingOptions::default()).unwrap();
+    let first_line = reparsed.lines[0].main_track().unwrap();
+    let syllables: Vec<_> = first_line.content.syllables().collect();
+    assert_eq!(syllables[0].text, "Hello");
+    assert_eq!(syllables[0].end_ms, 2000);
+    assert_eq!(
+        first_line.translations[0]
+            .metadata
+            .get(&TrackMetadataKey::Language)
+            .map(String::as_str),
+        Some("zh-Hans")
+    );
+
+    let revalidated = validate_amll_db_submission(normalized, &options).unwrap();
+    assert!(
+        revalidated.report.issues.iter().all(|issue| !issue.fixed),
+        "{:#?}",
+        revalidated.report.issues
+    );
+}
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use lyrics_helper_core::*;
-use ttml_processor::generate_ttml;
+use ttml_processor::{generate_ttml, parse_ttml};
 
 #[test]
 fn test_generate_line_timed_snapshot() {
@@ -303,3 +303,83 @@ fn test_generate_timed_translation_snapshot() {
 
     insta::assert_snapshot!(ttml_output);
 }
+
+#[test]
+fn test_generate_credits_metadata() {
+    let mut line = LyricLine::new(0, 1000);
+    line.add_content_track(ContentType::Main, "歌词");
+
+    let mut metadata = MetadataStore::new();
+    metadata.add("songwriters", "作者A").unwrap();
+    metadata.add("lyricist", "作者A").unwrap();
+    metadata.add("composer", "作者B").unwrap();
+    metadata.add("arranger", "编曲者").unwrap();
+
+    let options = TtmlGenerationOptions::default();
+    let ttml_output = generate_ttml(&[line], &metadata, &AgentStore::default(), &options).unwrap();
+
+    assert_eq!(
+        ttml_output
+            .matches("<songwriter>作者A</songwriter>")
+            .count(),
+        1
+    );
+    assert!(ttml_output.contains("<songwriter>作者B</songwriter>"));
+    assert!(ttml_output.contains(r#"<amll:meta key="lyricist" value="作者A"/>"#));
+    assert!(ttml_output.contains(r#"<amll:meta key="arranger" value="编曲者"/>"#));
+    assert!(ttml_output.contains("xmlns:amll"));
+}
+
+#[test]
+fn test_generate_contributor_metadata() {
+    let mut line = LyricLine::new(0, 1000);
+    line.add_content_track(ContentType::Main, "歌词");
+
+    let mut metadata = MetadataStore::new();
+    metadata.add("LyricsContributor", "上传者").unwrap();
+    metadata.add("TranslationContributor", "翻译者").unwrap();
+
+    let options = TtmlGenerationOptions::default();
+    let ttml_output = generate_ttml(&[line], &metadata, &AgentStore::default(), &options).unwrap();
+
+    assert!(ttml_output.contains(r#"<amll:meta key="lyricsContributor" value="上传者"/>"#));
+    assert!(ttml_output.contains(r#"<amll:meta key="translationContributor" value="翻译者"/>"#));
+    assert!(ttml_output.contains("xmlns:amll"));
+
+    // 写入的键可以被解析回相同的规范化键
+    let parsed = parse_ttml(&ttml_output, &TtmlParsingOptions::default()).unwrap();
+    let reparsed = MetadataStore::from(&parsed);
+    assert_eq!(
+        reparsed.get_single_value(&CanonicalMetadataKey::TranslationContributor),
+        Some(&"翻译者".to_string())
+    );
+}
+
+#[test]
+fn test_generate_song_part_divs() {
+    let parts = ["Verse", "Verse", "Chorus", "Chorus"];
+    let lines: Vec<LyricLine> = parts
+        .iter()
+        .enumerate()
+        .map(|(i, part)| {
+            let start = i as u64 * 1000;
+            let mut line = LyricLine::new(start, start + 1000);
+            line.add_content_track(ContentType::Main, format!("行{i}"));
+            line.song_part = Some((*part).to_string());
+            line
+        })
+        .collect();
+
+    let options = TtmlGenerationOptions::default();
+    let ttml_output = generate_ttml(
+        &lines,
+        &MetadataStore::new(),
+        &AgentStore::default(),
+        &options,
+    )
+    .unwrap();
+
+    assert_eq!(ttml_output.matches("<div ").count(), 2);
+    assert!(ttml_output.contains(r#"<div begin="0.000" end="2.000" itunes:song-part="Verse">"#));
+    assert!(ttml_output.contains(r#"<div begin="2.000" end="4.000" itunes:song-part="Chorus">"#));
+}
@@ -709,3 +709,50 @@ fn test_parse_space_only_span_as_whitespace_in_metadata() {
         "翻译音节 'One' 后面不应有空格"
     );
 }
+
+#[test]
+fn test_extended_metadata_round_trip() {
+    let mut line = LyricLine::new(0, 1000);
+    line.add_content_track(ContentType::Main, "歌词");
+
+    let mut metadata_store = MetadataStore::new();
+    metadata_store.add("kugouHash", "ABCDEF0123456789").unwrap();
+    metadata_store.add("NCMAlbumId", "123456").unwrap();
+    metadata_store.add("releaseYear", "2024").unwrap();
+    metadata_store.add("Title[ja]", "日本語タイトル").unwrap();
+    metadata_store.add("Artist[en]", "English Artist").unwrap();
+
+    let generated_ttml = generate_ttml(
+        &[line],
+        &metadata_store,
+        &AgentStore::default(),
+        &TtmlGenerationOptions::default(),
+    )
+    .unwrap();
+    assert!(generated_ttml.contains(r#"<amll:meta key="musicName[ja]" value="日本語タイトル"/>"#));
+
+    let parsed_data = parse_ttml(&generated_ttml, &TtmlParsingOptions::default()).unwrap();
+    let reloaded = MetadataStore::from(&parsed_data);
+
+    let get = |key: CanonicalMetadataKey| reloaded.get_single_value(&key).cloned();
+    assert_eq!(
+        get(CanonicalMetadataKey::KugouHash).as_deref(),
+        Some("ABCDEF0123456789")
+    );
+    assert_eq!(
+        get(CanonicalMetadataKey::NcmAlbumId).as_deref(),
+        Some("123456")
+    );
+    assert_eq!(
+        get(CanonicalMetadataKey::ReleaseYear).as_deref(),
+        Some("2024")
+    );
+    assert_eq!(
+        get(CanonicalMetadataKey::LocalizedTitle("ja".to_string())).as_deref(),
+        Some("日本語タイトル")
+    );
+    assert_eq!(
+        get(CanonicalMetadataKey::LocalizedArtist("en".to_string())).as_deref(),
+        Some("English Artist")
+    );
+}